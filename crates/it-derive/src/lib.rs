@@ -0,0 +1,230 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Provides `#[derive(IRecord)]` that maps a Rust struct onto an IT record.
+//!
+//! The derive implements `IRecord` and `InterfaceType` to build the record type,
+//! `IValueConvertible`, `From<T> for IValue` and `TryFrom<IValue> for T` to convert values.
+//! Every field type has to implement `IValueConvertible`, e.g. be a native type,
+//! another record or a container of them like `Vec<T>` or `Option<Box<T>>`.
+//!
+//! The generated code refers to the `fluence_it_types` crate, another path
+//! to it can be set with `#[it(crate = "...")]`. The record and its fields
+//! can be renamed with `#[it(rename = "...")]`.
+
+#![deny(
+    dead_code,
+    nonstandard_style,
+    unused_imports,
+    unused_mut,
+    unused_variables,
+    unused_unsafe,
+    unreachable_patterns
+)]
+#![warn(rust_2018_idioms)]
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::LitStr;
+use syn::Member;
+use syn::Path;
+
+/// Derives `IRecord`, `InterfaceType`, `IValueConvertible`, `From<T> for IValue`
+/// and `TryFrom<IValue> for T`.
+#[proc_macro_derive(IRecord, attributes(it))]
+pub fn derive_irecord(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Options set with `#[it(...)]`.
+#[derive(Default)]
+struct Options {
+    krate: Option<Path>,
+    rename: Option<String>,
+}
+
+impl Options {
+    fn parse(attrs: &[Attribute], allow_crate: bool) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("it")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if allow_crate && meta.path.is_ident("crate") {
+                    options.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `it` attribute"))
+                }
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`IRecord` can be derived only for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "a record must contain at least one field",
+        ));
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`IRecord` can't be derived for generic structs",
+        ));
+    }
+
+    let options = Options::parse(&input.attrs, true)?;
+    let krate = options
+        .krate
+        .unwrap_or_else(|| syn::parse_quote!(::fluence_it_types));
+    let ident = &input.ident;
+    let record_name = options.rename.unwrap_or_else(|| ident.to_string());
+    let fields_count = fields.len();
+
+    let mut members = Vec::with_capacity(fields_count);
+    let mut field_types = Vec::with_capacity(fields_count);
+    let mut segments = Vec::with_capacity(fields_count);
+    for (position, field) in fields.iter().enumerate() {
+        let options = Options::parse(&field.attrs, false)?;
+        let ty = &field.ty;
+
+        // fields of tuple structs are anonymous
+        let name = match (&field.ident, &options.rename, fields) {
+            (_, Some(name), _) => {
+                quote! { ::std::option::Option::Some(::std::string::String::from(#name)) }
+            }
+            (Some(ident), None, Fields::Named(_)) => {
+                let name = ident.to_string();
+                quote! { ::std::option::Option::Some(::std::string::String::from(#name)) }
+            }
+            _ => quote! { ::std::option::Option::None },
+        };
+
+        // errors point to fields by their Rust names, even if they are renamed
+        let segment = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => position.to_string(),
+        };
+        segments.push(quote! {
+            #krate::PathSegment::Field(::std::string::String::from(#segment))
+        });
+
+        members.push(match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(position.into()),
+        });
+        field_types.push(quote! {
+            #krate::IRecordFieldType {
+                name: #name,
+                ty: <#ty as #krate::InterfaceType>::interface_type(registry),
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #krate::InterfaceType for #ident {
+            fn interface_type(registry: &mut #krate::RecordRegistry) -> #krate::IType {
+                #krate::IType::Record(registry.register::<Self>())
+            }
+        }
+
+        impl #krate::IRecord for #ident {
+            fn record_type(registry: &mut #krate::RecordRegistry) -> #krate::IRecordType {
+                let fields = ::std::vec![#(#field_types),*];
+
+                #krate::IRecordType {
+                    name: ::std::string::String::from(#record_name),
+                    // the derive rejects structs without fields
+                    fields: #krate::ne_vec::NEVec::new(fields).unwrap(),
+                }
+            }
+        }
+
+        impl #krate::IValueConvertible for #ident {
+            fn into_ivalue(self) -> #krate::IValue {
+                let fields = ::std::vec![
+                    #(#krate::IValueConvertible::into_ivalue(self.#members)),*
+                ];
+
+                // the derive rejects structs without fields
+                #krate::IValue::Record(#krate::ne_vec::NEVec::new(fields).unwrap())
+            }
+
+            fn try_from_ivalue(
+                value: #krate::IValue,
+            ) -> ::std::result::Result<Self, #krate::WasmValueNativeCastError> {
+                let fields = match value {
+                    #krate::IValue::Record(fields) if fields.len() == #fields_count => fields.into_vec(),
+                    value => {
+                        return ::std::result::Result::Err(#krate::WasmValueNativeCastError::new(
+                            value,
+                            <Self as #krate::InterfaceType>::interface_type(
+                                &mut #krate::RecordRegistry::new(),
+                            ),
+                        ))
+                    }
+                };
+                let mut fields = fields.into_iter();
+
+                // the number of fields is checked above
+                ::std::result::Result::Ok(Self {
+                    #(#members: #krate::IValueConvertible::try_from_ivalue(fields.next().unwrap())
+                        .map_err(|e| e.at(#segments))?,)*
+                })
+            }
+        }
+
+        impl ::std::convert::From<#ident> for #krate::IValue {
+            fn from(value: #ident) -> Self {
+                #krate::IValueConvertible::into_ivalue(value)
+            }
+        }
+
+        impl ::std::convert::TryFrom<#krate::IValue> for #ident {
+            type Error = #krate::WasmValueNativeCastError;
+
+            fn try_from(value: #krate::IValue) -> ::std::result::Result<Self, Self::Error> {
+                #krate::IValueConvertible::try_from_ivalue(value)
+            }
+        }
+    })
+}
@@ -0,0 +1,187 @@
+use fluence_it_types::ne_vec::NEVec;
+use fluence_it_types::IRecord;
+use fluence_it_types::IRecordFieldType;
+use fluence_it_types::IRecordType;
+use fluence_it_types::IType;
+use fluence_it_types::IValue;
+use fluence_it_types::InterfaceType;
+use fluence_it_types::RecordRegistry;
+
+use std::convert::TryFrom;
+
+#[derive(IRecord, Debug, Clone, PartialEq)]
+struct Point(i32, i32);
+
+#[derive(IRecord, Debug, Clone, PartialEq)]
+#[it(rename = "Line")]
+struct Segment {
+    start: Point,
+    end: Point,
+    #[it(rename = "label")]
+    name: String,
+    visible: bool,
+    data: Vec<u8>,
+}
+
+fn segment() -> Segment {
+    Segment {
+        start: Point(1, 2),
+        end: Point(3, 4),
+        name: "diagonal".to_string(),
+        visible: true,
+        data: vec![42],
+    }
+}
+
+#[test]
+fn test_record_types() {
+    let mut registry = RecordRegistry::new();
+
+    assert_eq!(Segment::interface_type(&mut registry), IType::Record(0));
+    assert_eq!(registry.register::<Point>(), 1);
+    assert_eq!(registry.records().count(), 2);
+
+    let field = |name: Option<&str>, ty| IRecordFieldType {
+        name: name.map(str::to_string),
+        ty,
+    };
+    assert_eq!(
+        registry.record_type(0),
+        Some(&IRecordType {
+            name: "Line".to_string(),
+            fields: NEVec::new(vec![
+                field(Some("start"), IType::Record(1)),
+                field(Some("end"), IType::Record(1)),
+                field(Some("label"), IType::String),
+                field(Some("visible"), IType::Boolean),
+                field(Some("data"), IType::ByteArray),
+            ])
+            .unwrap(),
+        })
+    );
+    assert_eq!(
+        registry.record_type(1),
+        Some(&IRecordType {
+            name: "Point".to_string(),
+            fields: NEVec::new(vec![field(None, IType::I32), field(None, IType::I32)]).unwrap(),
+        })
+    );
+    assert_eq!(
+        Point::record_type(&mut RecordRegistry::new()),
+        registry.record_type(1).unwrap().clone()
+    );
+}
+
+#[test]
+fn test_conversions() {
+    let point = |x, y| IValue::Record(NEVec::new(vec![IValue::I32(x), IValue::I32(y)]).unwrap());
+    let value = IValue::Record(
+        NEVec::new(vec![
+            point(1, 2),
+            point(3, 4),
+            IValue::String("diagonal".to_string()),
+            IValue::Boolean(true),
+            IValue::ByteArray(vec![42]),
+        ])
+        .unwrap(),
+    );
+
+    assert_eq!(IValue::from(segment()), value);
+    assert_eq!(Segment::try_from(value).unwrap(), segment());
+
+    let error = Point::try_from(IValue::Record(
+        NEVec::new(vec![IValue::I32(1), IValue::S32(2)]).unwrap(),
+    ))
+    .unwrap_err();
+    assert_eq!(error.from, IValue::S32(2));
+    assert_eq!(error.to, IType::I32);
+
+    let error = Point::try_from(IValue::I32(1)).unwrap_err();
+    assert_eq!(error.to, IType::Record(0));
+}
+
+#[derive(IRecord, Debug, Clone, PartialEq)]
+struct Tree {
+    labels: Vec<Vec<String>>,
+    points: Vec<(i32, i32)>,
+    children: Vec<Tree>,
+    parent: Option<Box<Tree>>,
+}
+
+#[test]
+fn test_containers() {
+    let leaf = Tree {
+        labels: vec![vec!["a".to_string()], vec![]],
+        points: vec![(1, 2)],
+        children: vec![],
+        parent: None,
+    };
+    let tree = Tree {
+        labels: vec![],
+        points: vec![],
+        children: vec![leaf.clone(), leaf.clone()],
+        parent: Some(Box::new(leaf)),
+    };
+
+    let mut registry = RecordRegistry::new();
+    assert_eq!(Tree::interface_type(&mut registry), IType::Record(0));
+    assert_eq!(
+        registry
+            .record_type(0)
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| &f.ty)
+            .collect::<Vec<_>>(),
+        vec![
+            &IType::Array(Box::new(IType::Array(Box::new(IType::String)))),
+            &IType::Array(Box::new(IType::Record(1))),
+            &IType::Array(Box::new(IType::Record(0))),
+            &IType::Optional(Box::new(IType::Record(0))),
+        ]
+    );
+
+    let value = IValue::from(tree.clone());
+    assert_eq!(Tree::try_from(value).unwrap(), tree);
+
+    // the second point of the first child has a coordinate of another type
+    let record = |fields| IValue::Record(NEVec::new(fields).unwrap());
+    let point = |x, y| record(vec![IValue::I32(x), y]);
+    let child = record(vec![
+        IValue::Array(vec![]),
+        IValue::Array(vec![point(1, IValue::I32(2)), point(3, IValue::S32(4))]),
+        IValue::Array(vec![]),
+        IValue::Optional(None),
+    ]);
+    let value = record(vec![
+        IValue::Array(vec![]),
+        IValue::Array(vec![]),
+        IValue::Array(vec![child]),
+        IValue::Optional(None),
+    ]);
+
+    let error = Tree::try_from(value).unwrap_err();
+    assert_eq!(error.path.to_string(), "children[0].points[1].1");
+    assert_eq!(error.from, IValue::S32(4));
+    assert_eq!(error.to, IType::I32);
+}
+
+#[test]
+fn test_native_containers() {
+    let value = IValue::from([(1u8, "a".to_string())]);
+    assert_eq!(
+        <[(u8, String); 1]>::try_from(value).unwrap(),
+        [(1, "a".to_string())]
+    );
+
+    assert_eq!(IValue::from(vec![1u8, 2]), IValue::ByteArray(vec![1, 2]));
+    assert_eq!(
+        Option::<Vec<u8>>::try_from(IValue::Optional(Some(Box::new(IValue::ByteArray(vec![3])))))
+            .unwrap(),
+        Some(vec![3])
+    );
+
+    let error = <[i64; 2]>::try_from(IValue::Array(vec![IValue::I64(1)])).unwrap_err();
+    assert!(error.path.is_empty());
+    assert_eq!(error.to, IType::Array(Box::new(IType::I64)));
+}
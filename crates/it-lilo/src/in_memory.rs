@@ -0,0 +1,185 @@
+/*
+ * Copyright 2022 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! An allocator for [`InMemory`], e.g. to test lowering without a Wasm engine.
+
+use crate::traits::Allocatable;
+use crate::traits::AllocatableError;
+use crate::traits::DEFAULT_MEMORY_INDEX;
+
+use it_memory_traits::InMemory;
+use it_memory_traits::InMemoryStore;
+use it_memory_traits::InMemoryView;
+use it_memory_traits::Memory;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+/// Allocations are aligned like the most aligned value a lowered value may contain.
+const ALIGNMENT: u64 = 8;
+
+/// An allocator that never frees memory, allocations are placed one after another
+/// after the initial content of the memory, the memory grows when it runs out of space.
+/// Nothing is allocated at offset 0, since it is the null pointer of optional values.
+///
+/// The memory is the only one, its index is [`DEFAULT_MEMORY_INDEX`].
+#[derive(Debug, Clone)]
+pub struct BumpAllocator {
+    memory: InMemory,
+    next_offset: u64,
+    allocations: Vec<Allocation>,
+}
+
+/// An allocation made by [`BumpAllocator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocation {
+    pub offset: u64,
+    pub size: u64,
+    /// The type tag of allocated values, see [`type_tag_form_itype`](crate::utils::type_tag_form_itype).
+    pub type_tag: u32,
+}
+
+impl BumpAllocator {
+    pub fn new(memory: InMemory) -> Self {
+        Self {
+            next_offset: memory.size().max(ALIGNMENT),
+            memory,
+            allocations: Vec::new(),
+        }
+    }
+
+    pub fn memory(&self) -> &InMemory {
+        &self.memory
+    }
+
+    /// Returns the allocations in the order they were made.
+    pub fn allocations(&self) -> &[Allocation] {
+        &self.allocations
+    }
+
+    /// Allocates `size` bytes synchronously and returns their offset,
+    /// e.g. to emulate the `allocate` export of a module.
+    pub fn bump(&mut self, size: u64, type_tag: u32) -> Result<u64, AllocatableError> {
+        let out_of_memory = || {
+            AllocatableError::UserDefinedError(format!(
+                "failed to allocate {} bytes, the memory can't grow",
+                size
+            ))
+        };
+
+        let offset = self
+            .next_offset
+            .checked_add(ALIGNMENT - 1)
+            .ok_or_else(out_of_memory)?
+            / ALIGNMENT
+            * ALIGNMENT;
+        let end = offset.checked_add(size).ok_or_else(out_of_memory)?;
+
+        let memory_size = self.memory.size();
+        if end > memory_size {
+            self.memory
+                .grow(end - memory_size)
+                .ok_or_else(out_of_memory)?;
+        }
+
+        self.next_offset = end;
+        self.allocations.push(Allocation {
+            offset,
+            size,
+            type_tag,
+        });
+
+        Ok(offset)
+    }
+}
+
+impl Allocatable<InMemoryView, InMemoryStore> for BumpAllocator {
+    fn allocate<'this, 'store: 'this, 'store_inner: 'this>(
+        &'this mut self,
+        _store: &'store mut (),
+        memory_index: usize,
+        size: u64,
+        type_tag: u32,
+    ) -> BoxFuture<'this, Result<(u64, InMemoryView), AllocatableError>> {
+        async move {
+            if memory_index != DEFAULT_MEMORY_INDEX {
+                return Err(AllocatableError::MemoryIsMissing { memory_index });
+            }
+
+            let offset = self.bump(size, type_tag)?;
+
+            Ok((offset, self.memory.view()))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lifter::array_lift_memory;
+    use crate::lifter::optional_lift_memory;
+    use crate::lifter::ILifter;
+    use crate::lifter::LiError;
+    use crate::lowerer::optional_lower_memory;
+    use crate::lowerer::ILowerer;
+    use crate::type_checker::HandleCheck;
+    use crate::IType;
+    use crate::IValue;
+
+    use fluence_it_types::RecordRegistry;
+
+    #[test]
+    fn test_optional_roundtrip_on_empty_memory() {
+        let memory = InMemory::default();
+        let view = memory.view();
+        let mut allocator = BumpAllocator::new(memory);
+        let value = Box::new(IValue::U32(7));
+
+        let mut lowerer =
+            ILowerer::new(view.clone(), DEFAULT_MEMORY_INDEX, &mut allocator).unwrap();
+        let offset =
+            futures::executor::block_on(optional_lower_memory(&mut (), &mut lowerer, Some(value)))
+                .unwrap();
+        assert_ne!(offset, 0);
+
+        let registry = RecordRegistry::new();
+        let lifter = ILifter::new(view, &registry);
+        let lifted = optional_lift_memory(&mut (), &lifter, &IType::U32, offset).unwrap();
+        assert_eq!(lifted, IValue::Optional(Some(Box::new(IValue::U32(7)))));
+    }
+
+    #[test]
+    fn test_handle_check_on_lift() {
+        let memory = InMemory::from(vec![1, 0, 0, 0, 2, 0, 0, 0]);
+        let registry = RecordRegistry::new();
+        let check_handle: &HandleCheck<'_> = &|resource_id, handle| {
+            if resource_id == 3 && handle == 1 {
+                Ok(())
+            } else {
+                Err(format!("handle {} isn't live", handle).into())
+            }
+        };
+        let lifter = ILifter::new(memory.view(), &registry).with_handle_check(check_handle);
+
+        let lifted = array_lift_memory(&mut (), &lifter, &IType::Handle(3), 0, 1).unwrap();
+        assert_eq!(lifted, IValue::Array(vec![IValue::Handle(1)]));
+
+        let error = array_lift_memory(&mut (), &lifter, &IType::Handle(3), 0, 2).unwrap_err();
+        assert!(matches!(error, LiError::InvalidHandle(_)));
+        assert_eq!(error.to_string(), "handle 2 isn't live");
+    }
+}
@@ -25,12 +25,17 @@
 )]
 #![warn(rust_2018_idioms)]
 
+pub mod in_memory;
 pub mod lifter;
 pub mod lowerer;
 pub mod traits;
+pub mod type_checker;
 pub mod utils;
 
 pub use fluence_it_types::ne_vec::NEVec;
+pub use fluence_it_types::IEnumType;
+pub use fluence_it_types::IFlagsType;
 pub use fluence_it_types::IRecordType;
 pub use fluence_it_types::IType;
 pub use fluence_it_types::IValue;
+pub use fluence_it_types::IVariantType;
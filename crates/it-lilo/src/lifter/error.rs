@@ -34,4 +34,36 @@ pub enum LiError {
     /// This error occurred when a record is created from empty values array.
     #[error("Record with name '{0}' can't be empty")]
     EmptyRecord(String),
+
+    /// This error occurred when a variant discriminant doesn't correspond to any of its cases.
+    #[error(
+        "Variant with name '{variant_name}' doesn't have a case with discriminant {discriminant}"
+    )]
+    InvalidVariantDiscriminant {
+        variant_name: String,
+        discriminant: u32,
+    },
+
+    /// This error occurred when a result discriminant is neither 0 (ok) nor 1 (err).
+    #[error("Result discriminant must be 0 or 1, but {0} was found")]
+    InvalidResultDiscriminant(u32),
+
+    /// This error occurred when a lifted char isn't a valid Unicode scalar value.
+    #[error("{0:#x} isn't a valid Unicode scalar value")]
+    InvalidChar(u32),
+
+    /// This error occurred when flags have bits set that don't correspond to any flag.
+    #[error("Flags with name '{flags_name}' don't have flags for some of bits in {bits:#b}")]
+    InvalidFlags { flags_name: String, bits: u64 },
+
+    /// This error occurred when an enum discriminant doesn't correspond to any of its members.
+    #[error("Enum with name '{enum_name}' doesn't have a member with discriminant {discriminant}")]
+    InvalidEnumDiscriminant {
+        enum_name: String,
+        discriminant: u32,
+    },
+
+    /// This error occurred when the handle check failed, e.g. the handle isn't live.
+    #[error("{0}")]
+    InvalidHandle(Box<dyn std::error::Error + Send + Sync>),
 }
@@ -14,10 +14,17 @@
  * limitations under the License.
  */
 
+use super::enum_lift_value;
+use super::flags_lift_value;
+use super::map_lift_memory;
+use super::optional_lift_memory;
 use super::record_lift_memory;
+use super::result_lift_memory;
+use super::variant_lift_memory;
 use super::ILifter;
 use super::LiResult;
 use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
 use crate::utils::ser_type_size;
 use crate::IType;
 use crate::IValue;
@@ -32,8 +39,8 @@ pub fn array_lift_memory<
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
     value_type: &IType,
-    offset: u32,
-    elements_count: u32,
+    offset: u64,
+    elements_count: u64,
 ) -> LiResult<IValue> {
     if elements_count == 0 {
         return Ok(IValue::Array(vec![]));
@@ -55,12 +62,45 @@ pub fn array_lift_memory<
         IType::U64 => reader.read_u64_array(store, offset, elements_count)?,
         IType::F32 => reader.read_f32_array(store, offset, elements_count)?,
         IType::F64 => reader.read_f64_array(store, offset, elements_count)?,
+        IType::Char => reader.read_char_array(store, offset, elements_count)?,
+        IType::U128 => reader.read_u128_array(store, offset, elements_count)?,
+        IType::S128 => reader.read_s128_array(store, offset, elements_count)?,
+        IType::Handle(resource_id) => {
+            let handles = reader.read_handle_array(store, offset, elements_count)?;
+            for handle in handles.iter() {
+                if let IValue::Handle(handle) = handle {
+                    lifter.check_handle(*resource_id, *handle)?;
+                }
+            }
+
+            handles
+        }
         IType::String => read_string_array(store, lifter, offset, elements_count)?,
         IType::ByteArray => read_array_array(store, lifter, &IType::U8, offset, elements_count)?,
         IType::Array(ty) => read_array_array(store, lifter, &ty, offset, elements_count)?,
         IType::Record(record_type_id) => {
             read_record_array(store, lifter, *record_type_id, offset, elements_count)?
         }
+        IType::Optional(ty) => read_optional_array(store, lifter, ty, offset, elements_count)?,
+        IType::Variant(variant_type_id) => {
+            read_variant_array(store, lifter, *variant_type_id, offset, elements_count)?
+        }
+        IType::Result(ok_ty, err_ty) => {
+            read_result_array(store, lifter, ok_ty, err_ty, offset, elements_count)?
+        }
+        IType::Map(key_ty, value_ty) => {
+            read_map_array(store, lifter, key_ty, value_ty, offset, elements_count)?
+        }
+        IType::Flags(flags_type_id) => {
+            read_flags_array(store, lifter, *flags_type_id, offset, elements_count)?
+        }
+        IType::Enum(enum_type_id) => {
+            read_enum_array(store, lifter, *enum_type_id, offset, elements_count)?
+        }
+        IType::Alias(_) => {
+            let value_type = resolve_alias(lifter.resolver, value_type)?;
+            return array_lift_memory(store, lifter, value_type, offset, elements_count);
+        }
     };
 
     Ok(IValue::Array(ivalues))
@@ -69,19 +109,21 @@ pub fn array_lift_memory<
 fn read_string_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
-    offset: u32,
-    elements_count: u32,
+    offset: u64,
+    elements_count: u64,
 ) -> LiResult<Vec<IValue>> {
     let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
     let seq_reader = lifter.reader.sequential_reader(
         store,
         offset,
-        ser_type_size(&IType::String) * elements_count,
+        ser_type_size(lifter.resolver, &IType::String, address_type)?
+            .saturating_mul(elements_count),
     )?;
 
     for _ in 0..elements_count {
-        let offset = seq_reader.read_u32(store);
-        let size = seq_reader.read_u32(store);
+        let offset = seq_reader.read_pointer(store)?;
+        let size = seq_reader.read_pointer(store)?;
 
         let raw_str = lifter.reader.read_raw_u8_array(store, offset, size)?;
         let str = String::from_utf8(raw_str)?;
@@ -95,18 +137,17 @@ fn read_array_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
     ty: &IType,
-    offset: u32,
-    elements_count: u32,
+    offset: u64,
+    elements_count: u64,
 ) -> LiResult<Vec<IValue>> {
     let mut result = Vec::with_capacity(elements_count as usize);
-    let seq_reader =
-        lifter
-            .reader
-            .sequential_reader(store, offset, ser_type_size(ty) * elements_count)?;
+    let address_type = lifter.reader.address_type();
+    let size = ser_type_size(lifter.resolver, ty, address_type)?.saturating_mul(elements_count);
+    let seq_reader = lifter.reader.sequential_reader(store, offset, size)?;
 
     for _ in 0..elements_count {
-        let offset = seq_reader.read_u32(store);
-        let size = seq_reader.read_u32(store);
+        let offset = seq_reader.read_pointer(store)?;
+        let size = seq_reader.read_pointer(store)?;
 
         let array = array_lift_memory(store, lifter, ty, offset, size)?;
         result.push(array);
@@ -119,18 +160,20 @@ fn read_record_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memor
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
     record_type_id: u64,
-    offset: u32,
-    elements_count: u32,
+    offset: u64,
+    elements_count: u64,
 ) -> LiResult<Vec<IValue>> {
     let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
     let seq_reader = lifter.reader.sequential_reader(
         store,
         offset,
-        ser_type_size(&IType::Record(0)) * elements_count,
+        ser_type_size(lifter.resolver, &IType::Record(0), address_type)?
+            .saturating_mul(elements_count),
     )?;
 
     for _ in 0..elements_count {
-        let offset = seq_reader.read_u32(store);
+        let offset = seq_reader.read_pointer(store)?;
         let record_ty = lifter.resolver.resolve_record(record_type_id)?;
 
         let record = record_lift_memory(store, lifter, &record_ty, offset)?;
@@ -139,3 +182,181 @@ fn read_record_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memor
 
     Ok(result)
 }
+
+fn read_optional_array<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    ty: &IType,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(
+            lifter.resolver,
+            &IType::Optional(Box::new(ty.clone())),
+            address_type,
+        )?
+        .saturating_mul(elements_count),
+    )?;
+
+    for _ in 0..elements_count {
+        let offset = seq_reader.read_pointer(store)?;
+
+        let optional = optional_lift_memory(store, lifter, ty, offset)?;
+        result.push(optional);
+    }
+
+    Ok(result)
+}
+
+fn read_variant_array<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    variant_type_id: u64,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(lifter.resolver, &IType::Variant(0), address_type)?
+            .saturating_mul(elements_count),
+    )?;
+
+    for _ in 0..elements_count {
+        let offset = seq_reader.read_pointer(store)?;
+        let variant_ty = lifter.resolver.resolve_variant(variant_type_id)?;
+
+        let variant = variant_lift_memory(store, lifter, variant_ty, offset)?;
+        result.push(variant);
+    }
+
+    Ok(result)
+}
+
+fn read_result_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    ok_ty: &IType,
+    err_ty: &IType,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(
+            lifter.resolver,
+            &IType::Result(Box::new(ok_ty.clone()), Box::new(err_ty.clone())),
+            address_type,
+        )?
+        .saturating_mul(elements_count),
+    )?;
+
+    for _ in 0..elements_count {
+        let offset = seq_reader.read_pointer(store)?;
+
+        let value = result_lift_memory(store, lifter, ok_ty, err_ty, offset)?;
+        result.push(value);
+    }
+
+    Ok(result)
+}
+
+fn read_map_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    key_ty: &IType,
+    value_ty: &IType,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(
+            lifter.resolver,
+            &IType::Map(Box::new(key_ty.clone()), Box::new(value_ty.clone())),
+            address_type,
+        )?
+        .saturating_mul(elements_count),
+    )?;
+
+    for _ in 0..elements_count {
+        let offset = seq_reader.read_pointer(store)?;
+        let size = seq_reader.read_pointer(store)?;
+
+        let map = map_lift_memory(store, lifter, key_ty, value_ty, offset, size)?;
+        result.push(map);
+    }
+
+    Ok(result)
+}
+
+fn read_flags_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    flags_type_id: u64,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(lifter.resolver, &IType::Flags(flags_type_id), address_type)?
+            .saturating_mul(elements_count),
+    )?;
+    let flags_type = lifter.resolver.resolve_flags(flags_type_id)?;
+
+    for _ in 0..elements_count {
+        let flags = flags_lift_value(flags_type, seq_reader.read_u64(store)?)?;
+        result.push(flags);
+    }
+
+    Ok(result)
+}
+
+fn read_enum_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    enum_type_id: u64,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<Vec<IValue>> {
+    let mut result = Vec::with_capacity(elements_count as usize);
+    let address_type = lifter.reader.address_type();
+    let seq_reader = lifter.reader.sequential_reader(
+        store,
+        offset,
+        ser_type_size(lifter.resolver, &IType::Enum(enum_type_id), address_type)?
+            .saturating_mul(elements_count),
+    )?;
+    let enum_type = lifter.resolver.resolve_enum(enum_type_id)?;
+
+    for _ in 0..elements_count {
+        let member = enum_lift_value(enum_type, seq_reader.read_u32(store)?)?;
+        result.push(member);
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,31 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::LiError;
+use super::LiResult;
+use crate::IEnumType;
+use crate::IValue;
+
+/// Lifts an enum discriminant, it must correspond to one of the enum members.
+pub fn enum_lift_value(enum_type: &IEnumType, discriminant: u32) -> LiResult<IValue> {
+    if discriminant as usize >= enum_type.members.len() {
+        return Err(LiError::InvalidEnumDiscriminant {
+            enum_name: enum_type.name.clone(),
+            discriminant,
+        });
+    }
+
+    Ok(IValue::Enum(discriminant))
+}
@@ -0,0 +1,37 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use super::LiError;
+use super::LiResult;
+use crate::IFlagsType;
+use crate::IValue;
+
+/// Lifts flags packed into a `u64`, bits that don't correspond to any flag are rejected.
+pub fn flags_lift_value(flags_type: &IFlagsType, bits: u64) -> LiResult<IValue> {
+    let flags_count = flags_type.flags.len();
+    let unknown_bits = match flags_count {
+        count if count >= IFlagsType::MAX_FLAGS_COUNT => 0,
+        count => bits >> count,
+    };
+
+    if unknown_bits != 0 {
+        return Err(LiError::InvalidFlags {
+            flags_name: flags_type.name.clone(),
+            bits,
+        });
+    }
+
+    Ok(IValue::Flags(bits))
+}
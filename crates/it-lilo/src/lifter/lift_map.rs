@@ -0,0 +1,66 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::lift_record::read_value;
+use super::ILifter;
+use super::LiResult;
+use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
+use crate::utils::ser_type_size;
+use crate::IType;
+use crate::IValue;
+
+use it_memory_traits::MemoryView;
+
+/// Lifts a map, it's laid out as an array of pointers to key-value pairs.
+pub fn map_lift_memory<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    key_type: &IType,
+    value_type: &IType,
+    offset: u64,
+    elements_count: u64,
+) -> LiResult<IValue> {
+    if elements_count == 0 {
+        return Ok(IValue::Map(vec![]));
+    }
+
+    let key_type = resolve_alias(lifter.resolver, key_type)?;
+    let value_type = resolve_alias(lifter.resolver, value_type)?;
+
+    let reader = &lifter.reader;
+    let address_type = reader.address_type();
+    let pair_size = ser_type_size(lifter.resolver, key_type, address_type)?
+        + ser_type_size(lifter.resolver, value_type, address_type)?;
+    let pointers_size = address_type.pointer_size().saturating_mul(elements_count);
+    let seq_reader = reader.sequential_reader(store, offset, pointers_size)?;
+
+    let mut entries = Vec::with_capacity(elements_count as usize);
+    for _ in 0..elements_count {
+        let pair_offset = seq_reader.read_pointer(store)?;
+        let pair_reader = reader.sequential_reader(store, pair_offset, pair_size)?;
+
+        let key = read_value(store, lifter, &pair_reader, key_type)?;
+        let value = read_value(store, lifter, &pair_reader, value_type)?;
+        entries.push((key, value));
+    }
+
+    Ok(IValue::Map(entries))
+}
@@ -0,0 +1,49 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::lift_record::read_value;
+use super::ILifter;
+use super::LiResult;
+use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
+use crate::utils::ser_type_size;
+use crate::IType;
+use crate::IValue;
+
+use it_memory_traits::MemoryView;
+
+/// Lifts an optional value, it's passed by pointer and a null pointer stands for `None`.
+pub fn optional_lift_memory<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    value_type: &IType,
+    offset: u64,
+) -> LiResult<IValue> {
+    if offset == 0 {
+        return Ok(IValue::Optional(None));
+    }
+
+    let value_type = resolve_alias(lifter.resolver, value_type)?;
+    let size = ser_type_size(lifter.resolver, value_type, lifter.reader.address_type())?;
+    let seq_reader = lifter.reader.sequential_reader(store, offset, size)?;
+    let value = read_value(store, lifter, &seq_reader, value_type)?;
+
+    Ok(IValue::Optional(Some(Box::new(value))))
+}
@@ -21,6 +21,7 @@ use super::MemoryReader;
 use crate::lifter::memory_reader::SequentialReader;
 use crate::traits::RecordResolvable;
 use crate::utils::record_size;
+use crate::utils::resolve_alias;
 use crate::IRecordType;
 use crate::IType;
 use crate::IValue;
@@ -36,36 +37,16 @@ pub fn record_lift_memory<
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
     record_type: &IRecordType,
-    offset: u32,
+    offset: u64,
 ) -> LiResult<IValue> {
     let mut values = Vec::with_capacity(record_type.fields.len());
 
-    let size = record_size(record_type);
+    let size = record_size(lifter.resolver, record_type, lifter.reader.address_type())?;
     let reader = &lifter.reader;
     let seq_reader = reader.sequential_reader(store, offset, size)?;
 
     for field in (*record_type.fields).iter() {
-        match &field.ty {
-            IType::Boolean => values.push(IValue::Boolean(seq_reader.read_u8(store) != 0)),
-            IType::S8 => values.push(IValue::S8(seq_reader.read_i8(store))),
-            IType::S16 => values.push(IValue::S16(seq_reader.read_i16(store))),
-            IType::S32 => values.push(IValue::S32(seq_reader.read_i32(store))),
-            IType::S64 => values.push(IValue::S64(seq_reader.read_i64(store))),
-            IType::I32 => values.push(IValue::I32(seq_reader.read_i32(store))),
-            IType::I64 => values.push(IValue::I64(seq_reader.read_i64(store))),
-            IType::U8 => values.push(IValue::U8(seq_reader.read_u8(store))),
-            IType::U16 => values.push(IValue::U16(seq_reader.read_u16(store))),
-            IType::U32 => values.push(IValue::U32(seq_reader.read_u32(store))),
-            IType::U64 => values.push(IValue::U64(seq_reader.read_u64(store))),
-            IType::F32 => values.push(IValue::F32(seq_reader.read_f32(store))),
-            IType::F64 => values.push(IValue::F64(seq_reader.read_f64(store))),
-            IType::String => values.push(IValue::String(read_string(store, reader, &seq_reader)?)),
-            IType::ByteArray => values.push(read_byte_array(store, reader, &seq_reader)?),
-            IType::Array(ty) => values.push(read_array(store, &lifter, &seq_reader, &**ty)?),
-            IType::Record(record_type_id) => {
-                values.push(read_record(store, lifter, &seq_reader, *record_type_id)?)
-            }
-        }
+        values.push(read_value(store, lifter, &seq_reader, &field.ty)?);
     }
 
     let record = NEVec::new(values.into_iter().collect())
@@ -74,13 +55,76 @@ pub fn record_lift_memory<
     Ok(IValue::Record(record))
 }
 
+/// Reads a value of the provided type from the current position of the sequential reader.
+pub(super) fn read_value<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    seq_reader: &SequentialReader<'_, MV, Store>,
+    ty: &IType,
+) -> LiResult<IValue> {
+    let reader = &lifter.reader;
+
+    let value = match ty {
+        IType::Boolean => IValue::Boolean(seq_reader.read_u8(store)? != 0),
+        IType::S8 => IValue::S8(seq_reader.read_i8(store)?),
+        IType::S16 => IValue::S16(seq_reader.read_i16(store)?),
+        IType::S32 => IValue::S32(seq_reader.read_i32(store)?),
+        IType::S64 => IValue::S64(seq_reader.read_i64(store)?),
+        IType::I32 => IValue::I32(seq_reader.read_i32(store)?),
+        IType::I64 => IValue::I64(seq_reader.read_i64(store)?),
+        IType::U8 => IValue::U8(seq_reader.read_u8(store)?),
+        IType::U16 => IValue::U16(seq_reader.read_u16(store)?),
+        IType::U32 => IValue::U32(seq_reader.read_u32(store)?),
+        IType::U64 => IValue::U64(seq_reader.read_u64(store)?),
+        IType::F32 => IValue::F32(seq_reader.read_f32(store)?),
+        IType::F64 => IValue::F64(seq_reader.read_f64(store)?),
+        IType::Char => IValue::Char(seq_reader.read_char(store)?),
+        IType::U128 => IValue::U128(seq_reader.read_u128(store)?),
+        IType::S128 => IValue::S128(seq_reader.read_i128(store)?),
+        IType::Handle(resource_id) => {
+            let handle = seq_reader.read_u32(store)?;
+            lifter.check_handle(*resource_id, handle)?;
+
+            IValue::Handle(handle)
+        }
+        IType::Alias(_) => {
+            let ty = resolve_alias(lifter.resolver, ty)?;
+            read_value(store, lifter, seq_reader, ty)?
+        }
+        IType::String => IValue::String(read_string(store, reader, seq_reader)?),
+        IType::ByteArray => read_byte_array(store, reader, seq_reader)?,
+        IType::Array(ty) => read_array(store, lifter, seq_reader, &**ty)?,
+        IType::Record(record_type_id) => read_record(store, lifter, seq_reader, *record_type_id)?,
+        IType::Optional(ty) => read_optional(store, lifter, seq_reader, &**ty)?,
+        IType::Variant(variant_type_id) => {
+            read_variant(store, lifter, seq_reader, *variant_type_id)?
+        }
+        IType::Result(ok_ty, err_ty) => read_result(store, lifter, seq_reader, ok_ty, err_ty)?,
+        IType::Map(key_ty, value_ty) => read_map(store, lifter, seq_reader, key_ty, value_ty)?,
+        IType::Flags(flags_type_id) => {
+            let flags_type = lifter.resolver.resolve_flags(*flags_type_id)?;
+            super::flags_lift_value(flags_type, seq_reader.read_u64(store)?)?
+        }
+        IType::Enum(enum_type_id) => {
+            let enum_type = lifter.resolver.resolve_enum(*enum_type_id)?;
+            super::enum_lift_value(enum_type, seq_reader.read_u32(store)?)?
+        }
+    };
+
+    Ok(value)
+}
+
 fn read_string<MV: MemoryView<Store>, Store: it_memory_traits::Store>(
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     reader: &MemoryReader<MV, Store>,
     seq_reader: &SequentialReader<'_, MV, Store>,
 ) -> LiResult<String> {
-    let offset = seq_reader.read_u32(store);
-    let size = seq_reader.read_u32(store);
+    let offset = seq_reader.read_pointer(store)?;
+    let size = seq_reader.read_pointer(store)?;
 
     let string_mem = reader.read_raw_u8_array(store, offset, size)?;
 
@@ -93,8 +137,8 @@ fn read_byte_array<MV: MemoryView<Store>, Store: it_memory_traits::Store>(
     reader: &MemoryReader<MV, Store>,
     seq_reader: &SequentialReader<'_, MV, Store>,
 ) -> LiResult<IValue> {
-    let offset = seq_reader.read_u32(store);
-    let size = seq_reader.read_u32(store);
+    let offset = seq_reader.read_pointer(store)?;
+    let size = seq_reader.read_pointer(store)?;
 
     let array = reader.read_raw_u8_array(store, offset, size)?;
 
@@ -107,21 +151,70 @@ fn read_array<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_trait
     seq_reader: &SequentialReader<'_, MV, Store>,
     value_type: &IType,
 ) -> LiResult<IValue> {
-    let offset = seq_reader.read_u32(store);
-    let size = seq_reader.read_u32(store);
+    let offset = seq_reader.read_pointer(store)?;
+    let size = seq_reader.read_pointer(store)?;
 
     super::array_lift_memory(store, lifter, value_type, offset, size)
 }
 
+fn read_map<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    seq_reader: &SequentialReader<'_, MV, Store>,
+    key_type: &IType,
+    value_type: &IType,
+) -> LiResult<IValue> {
+    let offset = seq_reader.read_pointer(store)?;
+    let size = seq_reader.read_pointer(store)?;
+
+    super::map_lift_memory(store, lifter, key_type, value_type, offset, size)
+}
+
 fn read_record<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lifter: &ILifter<'_, R, MV, Store>,
     seq_reader: &SequentialReader<'_, MV, Store>,
     record_type_id: u64,
 ) -> LiResult<IValue> {
-    let offset = seq_reader.read_u32(store);
+    let offset = seq_reader.read_pointer(store)?;
 
     let record_type = lifter.resolver.resolve_record(record_type_id)?;
 
     record_lift_memory(store, lifter, &record_type, offset)
 }
+
+fn read_optional<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    seq_reader: &SequentialReader<'_, MV, Store>,
+    value_type: &IType,
+) -> LiResult<IValue> {
+    let offset = seq_reader.read_pointer(store)?;
+
+    super::optional_lift_memory(store, lifter, value_type, offset)
+}
+
+fn read_variant<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    seq_reader: &SequentialReader<'_, MV, Store>,
+    variant_type_id: u64,
+) -> LiResult<IValue> {
+    let offset = seq_reader.read_pointer(store)?;
+
+    let variant_type = lifter.resolver.resolve_variant(variant_type_id)?;
+
+    super::variant_lift_memory(store, lifter, variant_type, offset)
+}
+
+fn read_result<R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    seq_reader: &SequentialReader<'_, MV, Store>,
+    ok_type: &IType,
+    err_type: &IType,
+) -> LiResult<IValue> {
+    let offset = seq_reader.read_pointer(store)?;
+
+    super::result_lift_memory(store, lifter, ok_type, err_type, offset)
+}
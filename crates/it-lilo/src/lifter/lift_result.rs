@@ -0,0 +1,70 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::lift_record::read_value;
+use super::ILifter;
+use super::LiError;
+use super::LiResult;
+use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
+use crate::utils::ser_type_size;
+use crate::IType;
+use crate::IValue;
+
+use it_memory_traits::MemoryView;
+
+/// Lifts a result, it's laid out as a `u32` discriminant (0 for ok and 1 for err)
+/// followed by the corresponding value.
+pub fn result_lift_memory<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    ok_type: &IType,
+    err_type: &IType,
+    offset: u64,
+) -> LiResult<IValue> {
+    const DISCRIMINANT_SIZE: u64 = 4;
+
+    let reader = &lifter.reader;
+    let discriminant = reader
+        .sequential_reader(store, offset, DISCRIMINANT_SIZE)?
+        .read_u32(store)?;
+
+    let ty = match discriminant {
+        0 => ok_type,
+        1 => err_type,
+        _ => return Err(LiError::InvalidResultDiscriminant(discriminant)),
+    };
+
+    let ty = resolve_alias(lifter.resolver, ty)?;
+    let value_offset = offset.saturating_add(DISCRIMINANT_SIZE);
+    let seq_reader = reader.sequential_reader(
+        store,
+        value_offset,
+        ser_type_size(lifter.resolver, ty, reader.address_type())?,
+    )?;
+    let value = Box::new(read_value(store, lifter, &seq_reader, ty)?);
+
+    let result = match discriminant {
+        0 => Ok(value),
+        _ => Err(value),
+    };
+
+    Ok(IValue::Result(result))
+}
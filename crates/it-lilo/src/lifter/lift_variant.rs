@@ -0,0 +1,72 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::lift_record::read_value;
+use super::ILifter;
+use super::LiError;
+use super::LiResult;
+use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
+use crate::utils::ser_type_size;
+use crate::IValue;
+use crate::IVariantType;
+
+use it_memory_traits::MemoryView;
+
+/// Lifts a variant, it's laid out as a `u32` discriminant followed by the case payload.
+pub fn variant_lift_memory<
+    R: RecordResolvable,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lifter: &ILifter<'_, R, MV, Store>,
+    variant_type: &IVariantType,
+    offset: u64,
+) -> LiResult<IValue> {
+    const DISCRIMINANT_SIZE: u64 = 4;
+
+    let reader = &lifter.reader;
+    let discriminant = reader
+        .sequential_reader(store, offset, DISCRIMINANT_SIZE)?
+        .read_u32(store)?;
+
+    let case = variant_type
+        .cases
+        .get(discriminant as usize)
+        .ok_or_else(|| LiError::InvalidVariantDiscriminant {
+            variant_name: variant_type.name.clone(),
+            discriminant,
+        })?;
+
+    let payload = match &case.ty {
+        Some(ty) => {
+            let ty = resolve_alias(lifter.resolver, ty)?;
+            let payload_offset = offset.saturating_add(DISCRIMINANT_SIZE);
+            let seq_reader = reader.sequential_reader(
+                store,
+                payload_offset,
+                ser_type_size(lifter.resolver, ty, reader.address_type())?,
+            )?;
+            let value = read_value(store, lifter, &seq_reader, ty)?;
+
+            Some(Box::new(value))
+        }
+        None => None,
+    };
+
+    Ok(IValue::Variant(discriminant, payload))
+}
@@ -14,14 +14,17 @@
  * limitations under the License.
  */
 
+use super::LiError;
 use super::LiResult;
 use crate::read_array_ty;
 use crate::read_ty;
 use crate::IValue;
 
+use it_memory_traits::AddressType;
 use it_memory_traits::MemoryView;
 
 use std::cell::Cell;
+use std::convert::TryInto;
 use std::marker::PhantomData;
 
 pub struct MemoryReader<MV: MemoryView<Store>, Store: it_memory_traits::Store> {
@@ -37,14 +40,19 @@ impl<MV: MemoryView<Store>, Store: it_memory_traits::Store> MemoryReader<MV, Sto
         }
     }
 
-    /// Returns reader that allows read sequentially. It's important that memory limit is checked
-    /// only inside this function. All others functions of the returned reader don't have any
-    /// checks assuming that reader is well-formed.
+    /// Returns the type of addresses of the memory, i.e. the size of pointers stored in it.
+    pub fn address_type(&self) -> AddressType {
+        self.view.address_type()
+    }
+
+    /// Returns reader that allows read sequentially. The whole range is checked here to fail early,
+    /// functions of the returned reader check bounds of each read as well, so reading past
+    /// the range returns an error instead of panicking.
     pub fn sequential_reader(
         &self,
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
-        offset: u32,
-        size: u32,
+        offset: u64,
+        size: u64,
     ) -> LiResult<SequentialReader<'_, MV, Store>> {
         self.view.check_bounds(store, offset, size)?;
         let seq_reader = SequentialReader::new(&self, offset);
@@ -54,16 +62,10 @@ impl<MV: MemoryView<Store>, Store: it_memory_traits::Store> MemoryReader<MV, Sto
     pub fn read_raw_u8_array(
         &self,
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
-        offset: u32,
-        elements_count: u32,
+        offset: u64,
+        elements_count: u64,
     ) -> LiResult<Vec<u8>> {
-        let reader = self.sequential_reader(store, offset, elements_count)?;
-        let mut result = Vec::with_capacity(elements_count as usize);
-
-        for _ in 0..elements_count {
-            let value = reader.read_u8(store);
-            result.push(value);
-        }
+        let result = self.view.try_read_vec(store, offset, elements_count)?;
 
         Ok(result)
     }
@@ -71,16 +73,56 @@ impl<MV: MemoryView<Store>, Store: it_memory_traits::Store> MemoryReader<MV, Sto
     pub fn read_bool_array(
         &self,
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
-        offset: u32,
-        elements_count: u32,
+        offset: u64,
+        elements_count: u64,
     ) -> LiResult<Vec<IValue>> {
-        let reader = self.sequential_reader(store, offset, elements_count)?;
-        let mut result = Vec::with_capacity(elements_count as usize);
+        let result = self
+            .view
+            .try_read_with(store, offset, elements_count, |bytes| {
+                bytes
+                    .iter()
+                    .map(|&value| IValue::Boolean(value != 0))
+                    .collect()
+            })?;
 
-        for _ in 0..elements_count {
-            let value = reader.read_u8(store);
-            result.push(IValue::Boolean(value != 0));
-        }
+        Ok(result)
+    }
+
+    pub fn read_char_array(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+        offset: u64,
+        elements_count: u64,
+    ) -> LiResult<Vec<IValue>> {
+        let size = elements_count.saturating_mul(4);
+        self.view.try_read_with(store, offset, size, |bytes| {
+            bytes
+                .chunks_exact(4)
+                .map(|chunk| {
+                    // chunks have exactly 4 bytes, so the conversion never fails
+                    let value = u32::from_le_bytes(chunk.try_into().unwrap());
+                    char::from_u32(value)
+                        .map(IValue::Char)
+                        .ok_or(LiError::InvalidChar(value))
+                })
+                .collect()
+        })?
+    }
+
+    pub fn read_handle_array(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+        offset: u64,
+        elements_count: u64,
+    ) -> LiResult<Vec<IValue>> {
+        let size = elements_count.saturating_mul(4);
+        let result = self.view.try_read_with(store, offset, size, |bytes| {
+            bytes
+                .chunks_exact(4)
+                // chunks have exactly 4 bytes, so the conversion never fails
+                .map(|chunk| IValue::Handle(u32::from_le_bytes(chunk.try_into().unwrap())))
+                .collect()
+        })?;
 
         Ok(result)
     }
@@ -97,15 +139,17 @@ impl<MV: MemoryView<Store>, Store: it_memory_traits::Store> MemoryReader<MV, Sto
     read_array_ty!(read_s64_array, i64, S64);
     read_array_ty!(read_i64_array, i64, I64);
     read_array_ty!(read_f64_array, f64, F64);
+    read_array_ty!(read_u128_array, u128, U128);
+    read_array_ty!(read_s128_array, i128, S128);
 }
 
 pub struct SequentialReader<'r, MV: MemoryView<Store>, Store: it_memory_traits::Store> {
     reader: &'r MemoryReader<MV, Store>,
-    offset: Cell<u32>,
+    offset: Cell<u64>,
 }
 
 impl<'r, MV: MemoryView<Store>, Store: it_memory_traits::Store> SequentialReader<'r, MV, Store> {
-    fn new(reader: &'r MemoryReader<MV, Store>, offset: u32) -> Self {
+    fn new(reader: &'r MemoryReader<MV, Store>, offset: u64) -> Self {
         Self {
             reader,
             offset: Cell::new(offset),
@@ -115,8 +159,29 @@ impl<'r, MV: MemoryView<Store>, Store: it_memory_traits::Store> SequentialReader
     pub fn read_bool(
         &self,
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
-    ) -> bool {
-        self.read_u8(store) != 0
+    ) -> LiResult<bool> {
+        Ok(self.read_u8(store)? != 0)
+    }
+
+    /// Reads a `u32` and checks that it's a valid Unicode scalar value.
+    pub fn read_char(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    ) -> LiResult<char> {
+        let value = self.read_u32(store)?;
+
+        char::from_u32(value).ok_or(LiError::InvalidChar(value))
+    }
+
+    /// Reads a pointer or a size, its width is defined by the address type of the memory.
+    pub fn read_pointer(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    ) -> LiResult<u64> {
+        match self.reader.address_type() {
+            AddressType::I32 => self.read_u32(store).map(Into::into),
+            AddressType::I64 => self.read_u64(store),
+        }
     }
 
     read_ty!(read_u8, u8, 1);
@@ -129,4 +194,6 @@ impl<'r, MV: MemoryView<Store>, Store: it_memory_traits::Store> SequentialReader
     read_ty!(read_u64, u64, 8);
     read_ty!(read_i64, i64, 8);
     read_ty!(read_f64, f64, 8);
+    read_ty!(read_u128, u128, 16);
+    read_ty!(read_i128, i128, 16);
 }
@@ -16,31 +16,67 @@
 
 mod error;
 mod lift_array;
+mod lift_enum;
+mod lift_flags;
+mod lift_map;
+mod lift_optional;
 mod lift_record;
+mod lift_result;
+mod lift_variant;
 mod macros;
 mod memory_reader;
 
 pub use error::LiError;
 pub use lift_array::array_lift_memory;
+pub use lift_enum::enum_lift_value;
+pub use lift_flags::flags_lift_value;
+pub use lift_map::map_lift_memory;
+pub use lift_optional::optional_lift_memory;
 pub use lift_record::record_lift_memory;
+pub use lift_result::result_lift_memory;
+pub use lift_variant::variant_lift_memory;
 pub use memory_reader::MemoryReader;
 
 use super::traits::RecordResolvable;
+use super::type_checker::HandleCheck;
 
 pub use it_memory_traits::MemoryView;
 
 pub type LiResult<T> = std::result::Result<T, error::LiError>;
 
+/// Lifts values from memory.
+///
+/// Handles are lifted as they are, unless a handle check is provided
+/// with [`ILifter::with_handle_check`].
 pub struct ILifter<'r, R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store> {
     pub reader: MemoryReader<MV, Store>,
     pub resolver: &'r R,
+    check_handle: Option<&'r HandleCheck<'r>>,
 }
 
 impl<'r, R: RecordResolvable, MV: MemoryView<Store>, Store: it_memory_traits::Store>
     ILifter<'r, R, MV, Store>
 {
+    /// Creates a lifter that reads values from the memory `view` belongs to.
     pub fn new(view: MV, resolver: &'r R) -> Self {
         let reader = MemoryReader::new(view);
-        Self { reader, resolver }
+        Self {
+            reader,
+            resolver,
+            check_handle: None,
+        }
+    }
+
+    /// Checks every handle read from memory with `check_handle`.
+    pub fn with_handle_check(mut self, check_handle: &'r HandleCheck<'r>) -> Self {
+        self.check_handle = Some(check_handle);
+        self
+    }
+
+    pub(crate) fn check_handle(&self, resource_id: u64, handle: u32) -> LiResult<()> {
+        match self.check_handle {
+            Some(check_handle) => check_handle(resource_id, handle).map_err(LiError::InvalidHandle),
+            None => Ok(()),
+        }
     }
 }
@@ -16,6 +16,7 @@
 
 use crate::traits::AllocatableError;
 use crate::traits::RecordResolvableError;
+use crate::IValue;
 use it_memory_traits::MemoryAccessError;
 use thiserror::Error as ThisError;
 
@@ -29,4 +30,12 @@ pub enum LoError {
 
     #[error("{0}")]
     MemoryAccessError(#[from] MemoryAccessError),
+
+    /// The null pointer stands for `None`, so a present optional value can't be placed there.
+    #[error("a present optional value was allocated at offset 0 that is reserved for None")]
+    OptionalAtNullPointer,
+
+    /// Keys of a map must be unique to lower it deterministically.
+    #[error("map contains the key {0:?} more than once")]
+    DuplicateMapKey(IValue),
 }
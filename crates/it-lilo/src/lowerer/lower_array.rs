@@ -24,12 +24,12 @@ use crate::IValue;
 use it_memory_traits::MemoryView;
 
 pub struct LoweredArray {
-    pub offset: u32,
-    pub size: u32,
+    pub offset: u64,
+    pub size: u64,
 }
 
 impl LoweredArray {
-    pub fn new(offset: u32, size: u32) -> Self {
+    pub fn new(offset: u64, size: u64) -> Self {
         Self { offset, size }
     }
 
@@ -52,9 +52,10 @@ pub async fn array_lower_memory<
         return Ok(LoweredArray::empty());
     }
 
-    let elements_count = array_values.len() as u32;
-    let size = ser_value_size(&array_values[0]) * elements_count;
-    let type_tag = type_tag_form_ivalue(&array_values[0]);
+    let address_type = lowerer.writer.address_type();
+    let elements_count = array_values.len() as u64;
+    let size = ser_value_size(&array_values[0], address_type) * elements_count;
+    let type_tag = type_tag_form_ivalue(&array_values[0], address_type);
     let seq_writer = lowerer
         .writer
         .sequential_writer(store, size, type_tag)
@@ -63,67 +64,89 @@ pub async fn array_lower_memory<
     // here it's known that all interface values have the same type
     for value in array_values {
         match value {
-            IValue::Boolean(value) => seq_writer.write_u8(store, &lowerer.writer, value as _),
-            IValue::S8(value) => seq_writer.write_u8(store, &lowerer.writer, value as _),
+            IValue::Boolean(value) => seq_writer.write_u8(store, &lowerer.writer, value as _)?,
+            IValue::S8(value) => seq_writer.write_u8(store, &lowerer.writer, value as _)?,
             IValue::S16(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::S32(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::S64(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::U8(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::U16(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::U32(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
-            IValue::U64(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+            IValue::Char(value) => seq_writer.write_u32(store, &lowerer.writer, value as u32)?,
+            IValue::U128(value) => seq_writer.write_u128(store, &lowerer.writer, value)?,
+            IValue::S128(value) => seq_writer.write_u128(store, &lowerer.writer, value as u128)?,
+            IValue::Enum(value) | IValue::Handle(value) => {
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
+            }
+            IValue::U64(value) | IValue::Flags(value) => {
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::I32(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::I64(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::F32(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::F64(value) => {
-                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())
+                seq_writer.write_bytes(store, &lowerer.writer, &value.to_le_bytes())?
             }
             IValue::String(value) => {
-                let offset = lowerer.writer.write_bytes(store, &value.as_bytes()).await? as u32;
+                let offset = lowerer.writer.write_bytes(store, &value.as_bytes()).await?;
 
-                seq_writer.write_bytes(store, &lowerer.writer, &offset.to_le_bytes());
-                seq_writer.write_bytes(store, &lowerer.writer, &(value.len() as u32).to_le_bytes());
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+                seq_writer.write_pointer(store, &lowerer.writer, value.len() as u64)?;
             }
             IValue::ByteArray(values) => {
-                let offset = lowerer.writer.write_bytes(store, &values).await? as u32;
+                let offset = lowerer.writer.write_bytes(store, &values).await?;
 
-                seq_writer.write_bytes(store, &lowerer.writer, &offset.to_le_bytes());
-                seq_writer.write_bytes(
-                    store,
-                    &lowerer.writer,
-                    &(values.len() as u32).to_le_bytes(),
-                );
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+                seq_writer.write_pointer(store, &lowerer.writer, values.len() as u64)?;
             }
             IValue::Array(values) => {
                 let LoweredArray { offset, size } =
                     array_lower_memory(store, lowerer, values).await?;
 
-                seq_writer.write_bytes(store, &lowerer.writer, &(offset as u32).to_le_bytes());
-                seq_writer.write_bytes(store, &lowerer.writer, &(size as u32).to_le_bytes());
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+                seq_writer.write_pointer(store, &lowerer.writer, size)?;
             }
             IValue::Record(values) => {
-                let offset = super::record_lower_memory(store, lowerer, values).await? as u32;
-                seq_writer.write_bytes(store, &lowerer.writer, &offset.to_le_bytes());
+                let offset = super::record_lower_memory(store, lowerer, values).await?;
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+            }
+            IValue::Optional(value) => {
+                let offset = super::optional_lower_memory(store, lowerer, value).await?;
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+            }
+            IValue::Variant(discriminant, payload) => {
+                let offset =
+                    super::variant_lower_memory(store, lowerer, discriminant, payload).await?;
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+            }
+            IValue::Result(value) => {
+                let offset = super::result_lower_memory(store, lowerer, value).await?;
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+            }
+            IValue::Map(entries) => {
+                let LoweredArray { offset, size } =
+                    super::map_lower_memory(store, lowerer, entries).await?;
+
+                seq_writer.write_pointer(store, &lowerer.writer, offset)?;
+                seq_writer.write_pointer(store, &lowerer.writer, size)?;
             }
         }
     }
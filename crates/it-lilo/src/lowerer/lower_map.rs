@@ -0,0 +1,63 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::array_lower_memory;
+use super::ILowerer;
+use super::LoError;
+use super::LoResult;
+use super::LoweredArray;
+use crate::traits::Allocatable;
+use crate::IValue;
+use crate::NEVec;
+
+use fluence_it_types::CanonicalIValue;
+use it_memory_traits::MemoryView;
+
+/// Lowers a map as an array of pointers to key-value pairs, each pair has the same layout
+/// as a record with two fields.
+///
+/// To make the memory layout deterministic, the pairs are sorted by their keys
+/// in the total order of [`CanonicalIValue`], so a map with keys equal in this order
+/// is rejected.
+pub async fn map_lower_memory<
+    A: Allocatable<MV, Store>,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lowerer: &mut ILowerer<'_, A, MV, Store>,
+    entries: Vec<(IValue, IValue)>,
+) -> LoResult<LoweredArray> {
+    let mut entries = entries
+        .into_iter()
+        .map(|(key, value)| (CanonicalIValue(key), value))
+        .collect::<Vec<_>>();
+    entries.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+
+    if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        return Err(LoError::DuplicateMapKey(pair[0].0.clone().into_inner()));
+    }
+
+    let pairs = entries
+        .into_iter()
+        .map(|(key, value)| {
+            // unwrap is safe here - a pair always consists of two values
+            IValue::Record(NEVec::new(vec![key.into_inner(), value]).unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    array_lower_memory(store, lowerer, pairs).await
+}
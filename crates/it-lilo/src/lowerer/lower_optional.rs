@@ -0,0 +1,51 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ILowerer;
+use super::LoError;
+use super::LoResult;
+use super::LoweredArray;
+use crate::traits::Allocatable;
+use crate::IValue;
+
+use it_memory_traits::MemoryView;
+
+/// Lowers an optional value and returns a pointer to it, `None` is lowered to a null pointer,
+/// so the allocator must not return offset 0 for a present value.
+pub async fn optional_lower_memory<
+    A: Allocatable<MV, Store>,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lowerer: &mut ILowerer<'_, A, MV, Store>,
+    value: Option<Box<IValue>>,
+) -> LoResult<u64> {
+    let value = match value {
+        Some(value) => *value,
+        None => return Ok(0),
+    };
+
+    // a present value is laid out as a one-element array
+    let LoweredArray { offset, .. } =
+        super::array_lower_memory(store, lowerer, vec![value]).await?;
+
+    if offset == 0 {
+        return Err(LoError::OptionalAtNullPointer);
+    }
+
+    Ok(offset)
+}
@@ -21,6 +21,7 @@ use crate::traits::Allocatable;
 use crate::IValue;
 use crate::NEVec;
 
+use it_memory_traits::AddressType;
 use it_memory_traits::MemoryView;
 
 #[async_recursion::async_recursion]
@@ -32,7 +33,8 @@ pub async fn record_lower_memory<
     store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
     lowerer: &mut ILowerer<'_, A, MV, Store>,
     values: NEVec<IValue>,
-) -> LoResult<u32> {
+) -> LoResult<u64> {
+    let address_type = lowerer.writer.address_type();
     let average_field_size = 4;
     // TODO: avoid this additional allocation after fixing github.com/fluencelabs/fce/issues/77
     let mut result: Vec<u8> = Vec::with_capacity(average_field_size * values.len());
@@ -47,7 +49,15 @@ pub async fn record_lower_memory<
             IValue::U8(value) => result.push(value),
             IValue::U16(value) => result.extend_from_slice(&value.to_le_bytes()),
             IValue::U32(value) => result.extend_from_slice(&value.to_le_bytes()),
-            IValue::U64(value) => result.extend_from_slice(&value.to_le_bytes()),
+            IValue::U64(value) | IValue::Flags(value) => {
+                result.extend_from_slice(&value.to_le_bytes())
+            }
+            IValue::Enum(value) | IValue::Handle(value) => {
+                result.extend_from_slice(&value.to_le_bytes())
+            }
+            IValue::Char(value) => result.extend_from_slice(&(value as u32).to_le_bytes()),
+            IValue::U128(value) => result.extend_from_slice(&value.to_le_bytes()),
+            IValue::S128(value) => result.extend_from_slice(&value.to_le_bytes()),
             IValue::I32(value) => result.extend_from_slice(&value.to_le_bytes()),
             IValue::I64(value) => result.extend_from_slice(&value.to_le_bytes()),
             IValue::F32(value) => result.extend_from_slice(&value.to_le_bytes()),
@@ -55,28 +65,55 @@ pub async fn record_lower_memory<
             IValue::String(value) => {
                 let offset = lowerer.writer.write_bytes(store, value.as_bytes()).await?;
 
-                result.extend_from_slice(&offset.to_le_bytes());
-                result.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                extend_pointer(&mut result, offset, address_type);
+                extend_pointer(&mut result, value.len() as u64, address_type);
             }
             IValue::ByteArray(value) => {
                 let offset = lowerer.writer.write_bytes(store, &value).await?;
 
-                result.extend_from_slice(&offset.to_le_bytes());
-                result.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                extend_pointer(&mut result, offset, address_type);
+                extend_pointer(&mut result, value.len() as u64, address_type);
             }
 
             IValue::Array(values) => {
                 let LoweredArray { offset, size } =
                     super::array_lower_memory(store, lowerer, values).await?;
 
-                result.extend_from_slice(&(offset).to_le_bytes());
-                result.extend_from_slice(&(size).to_le_bytes());
+                extend_pointer(&mut result, offset, address_type);
+                extend_pointer(&mut result, size, address_type);
             }
 
             IValue::Record(values) => {
                 let offset = record_lower_memory(store, lowerer, values).await?;
 
-                result.extend_from_slice(&offset.to_le_bytes());
+                extend_pointer(&mut result, offset, address_type);
+            }
+
+            IValue::Optional(value) => {
+                let offset = super::optional_lower_memory(store, lowerer, value).await?;
+
+                extend_pointer(&mut result, offset, address_type);
+            }
+
+            IValue::Variant(discriminant, payload) => {
+                let offset =
+                    super::variant_lower_memory(store, lowerer, discriminant, payload).await?;
+
+                extend_pointer(&mut result, offset, address_type);
+            }
+
+            IValue::Result(value) => {
+                let offset = super::result_lower_memory(store, lowerer, value).await?;
+
+                extend_pointer(&mut result, offset, address_type);
+            }
+
+            IValue::Map(entries) => {
+                let LoweredArray { offset, size } =
+                    super::map_lower_memory(store, lowerer, entries).await?;
+
+                extend_pointer(&mut result, offset, address_type);
+                extend_pointer(&mut result, size, address_type);
             }
         }
     }
@@ -85,3 +122,11 @@ pub async fn record_lower_memory<
 
     Ok(result_pointer)
 }
+
+/// Appends a pointer or a size, its width is defined by the address type of the memory.
+fn extend_pointer(result: &mut Vec<u8>, value: u64, address_type: AddressType) {
+    match address_type {
+        AddressType::I32 => result.extend_from_slice(&(value as u32).to_le_bytes()),
+        AddressType::I64 => result.extend_from_slice(&value.to_le_bytes()),
+    }
+}
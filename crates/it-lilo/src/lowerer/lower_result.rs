@@ -0,0 +1,45 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ILowerer;
+use super::LoResult;
+use crate::traits::Allocatable;
+use crate::IValue;
+use crate::NEVec;
+
+use it_memory_traits::MemoryView;
+
+/// Lowers a result and returns a pointer to it. A result has the same layout as a record
+/// consisting of a `u32` discriminant (0 for ok and 1 for err) and the corresponding value.
+pub async fn result_lower_memory<
+    A: Allocatable<MV, Store>,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lowerer: &mut ILowerer<'_, A, MV, Store>,
+    value: Result<Box<IValue>, Box<IValue>>,
+) -> LoResult<u64> {
+    let (discriminant, value) = match value {
+        Ok(value) => (0, value),
+        Err(value) => (1, value),
+    };
+
+    // unwrap is safe here - there are always two values
+    let values = NEVec::new(vec![IValue::U32(discriminant), *value]).unwrap();
+
+    super::record_lower_memory(store, lowerer, values).await
+}
@@ -0,0 +1,46 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ILowerer;
+use super::LoResult;
+use crate::traits::Allocatable;
+use crate::IValue;
+use crate::NEVec;
+
+use it_memory_traits::MemoryView;
+
+/// Lowers a variant and returns a pointer to it. A variant has the same layout as a record
+/// consisting of a `u32` discriminant and the case payload.
+pub async fn variant_lower_memory<
+    A: Allocatable<MV, Store>,
+    MV: MemoryView<Store>,
+    Store: it_memory_traits::Store,
+>(
+    store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+    lowerer: &mut ILowerer<'_, A, MV, Store>,
+    discriminant: u32,
+    payload: Option<Box<IValue>>,
+) -> LoResult<u64> {
+    let mut values = vec![IValue::U32(discriminant)];
+    if let Some(payload) = payload {
+        values.push(*payload);
+    }
+
+    // unwrap is safe here - there is always a discriminant
+    let values = NEVec::new(values).unwrap();
+
+    super::record_lower_memory(store, lowerer, values).await
+}
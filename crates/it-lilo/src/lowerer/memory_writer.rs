@@ -16,8 +16,9 @@
 
 use super::LoResult;
 use crate::traits::Allocatable;
-use crate::utils::type_tag_form_itype;
+use crate::utils::type_tag_form_ivalue;
 
+use it_memory_traits::AddressType;
 use it_memory_traits::MemoryView;
 
 use std::cell::{Cell, RefCell};
@@ -30,6 +31,7 @@ pub struct MemoryWriter<
     Store: it_memory_traits::Store,
 > {
     heap_manager: &'i mut R,
+    memory_index: usize,
     view: RefCell<MV>,
     _store: PhantomData<Store>,
 }
@@ -37,25 +39,31 @@ pub struct MemoryWriter<
 impl<'i, A: Allocatable<MV, Store>, MV: MemoryView<Store>, Store: it_memory_traits::Store>
     MemoryWriter<'i, A, MV, Store>
 {
-    pub fn new(view: MV, heap_manager: &'i mut A) -> LoResult<Self> {
+    pub fn new(view: MV, memory_index: usize, heap_manager: &'i mut A) -> LoResult<Self> {
         let writer = Self {
             heap_manager,
+            memory_index,
             view: RefCell::new(view),
             _store: PhantomData,
         };
         Ok(writer)
     }
 
+    /// Returns the type of addresses of the memory, i.e. the size of pointers stored in it.
+    pub fn address_type(&self) -> AddressType {
+        self.view.borrow().address_type()
+    }
+
     pub async fn write_bytes<'store, 'store_inner: 'store>(
         &mut self,
         store: &'store mut <Store as it_memory_traits::Store>::ActualStore<'store_inner>,
         bytes: &[u8],
-    ) -> LoResult<u32> {
-        let byte_type_tag = type_tag_form_itype(&crate::IType::U8);
+    ) -> LoResult<u64> {
+        let byte_type_tag = type_tag_form_ivalue(&crate::IValue::U8(0), self.address_type());
         let seq_writer = self
-            .sequential_writer(store, bytes.len() as u32, byte_type_tag)
+            .sequential_writer(store, bytes.len() as u64, byte_type_tag)
             .await?;
-        seq_writer.write_bytes(store, &self, bytes);
+        seq_writer.write_bytes(store, &self, bytes)?;
 
         Ok(seq_writer.start_offset())
     }
@@ -63,10 +71,13 @@ impl<'i, A: Allocatable<MV, Store>, MV: MemoryView<Store>, Store: it_memory_trai
     pub async fn sequential_writer(
         &mut self,
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
-        size: u32,
+        size: u64,
         type_tag: u32,
     ) -> LoResult<SequentialWriter> {
-        let (offset, view) = self.heap_manager.allocate(store, size, type_tag).await?;
+        let (offset, view) = self
+            .heap_manager
+            .allocate(store, self.memory_index, size, type_tag)
+            .await?;
         self.view.replace(view);
         let seq_writer = SequentialWriter::new(offset);
         Ok(seq_writer)
@@ -74,19 +85,19 @@ impl<'i, A: Allocatable<MV, Store>, MV: MemoryView<Store>, Store: it_memory_trai
 }
 
 pub struct SequentialWriter {
-    start_offset: u32,
-    offset: Cell<u32>,
+    start_offset: u64,
+    offset: Cell<u64>,
 }
 
 impl SequentialWriter {
-    pub(self) fn new(offset: u32) -> Self {
+    pub(self) fn new(offset: u64) -> Self {
         Self {
             offset: Cell::new(offset),
             start_offset: offset,
         }
     }
 
-    pub fn start_offset(&self) -> u32 {
+    pub fn start_offset(&self) -> u64 {
         self.start_offset
     }
 
@@ -100,12 +111,16 @@ impl SequentialWriter {
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
         writer: &MemoryWriter<'_, A, MV, Store>,
         values: [u8; N],
-    ) {
+    ) -> LoResult<()> {
         let offset = self.offset.get();
 
-        writer.view.borrow().write_bytes(store, offset, &values);
+        writer
+            .view
+            .borrow()
+            .try_write_bytes(store, offset, &values)?;
 
-        self.offset.set(offset + N as u32);
+        self.offset.set(offset + N as u64);
+        Ok(())
     }
 
     pub fn write_u8<
@@ -117,12 +132,13 @@ impl SequentialWriter {
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
         writer: &MemoryWriter<'_, A, MV, Store>,
         value: u8,
-    ) {
+    ) -> LoResult<()> {
         let offset = self.offset.get();
 
-        writer.view.borrow().write_byte(store, offset, value);
+        writer.view.borrow().try_write_byte(store, offset, value)?;
 
         self.offset.set(offset + 1);
+        Ok(())
     }
 
     pub fn write_u32<
@@ -134,13 +150,56 @@ impl SequentialWriter {
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
         writer: &MemoryWriter<'_, A, MV, Store>,
         value: u32,
-    ) {
+    ) -> LoResult<()> {
         let offset = self.offset.get();
 
         let value = value.to_le_bytes();
-        writer.view.borrow().write_bytes(store, offset, &value);
+        writer
+            .view
+            .borrow()
+            .try_write_bytes(store, offset, &value)?;
 
         self.offset.set(offset + 4);
+        Ok(())
+    }
+
+    /// Writes a pointer or a size, its width is defined by the address type of the memory.
+    pub fn write_pointer<
+        MV: MemoryView<Store>,
+        Store: it_memory_traits::Store,
+        A: Allocatable<MV, Store>,
+    >(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+        writer: &MemoryWriter<'_, A, MV, Store>,
+        value: u64,
+    ) -> LoResult<()> {
+        match writer.address_type() {
+            AddressType::I32 => self.write_bytes(store, writer, &(value as u32).to_le_bytes()),
+            AddressType::I64 => self.write_bytes(store, writer, &value.to_le_bytes()),
+        }
+    }
+
+    pub fn write_u128<
+        MV: MemoryView<Store>,
+        Store: it_memory_traits::Store,
+        A: Allocatable<MV, Store>,
+    >(
+        &self,
+        store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
+        writer: &MemoryWriter<'_, A, MV, Store>,
+        value: u128,
+    ) -> LoResult<()> {
+        let offset = self.offset.get();
+
+        let value = value.to_le_bytes();
+        writer
+            .view
+            .borrow()
+            .try_write_bytes(store, offset, &value)?;
+
+        self.offset.set(offset + 16);
+        Ok(())
     }
 
     pub fn write_bytes<
@@ -152,11 +211,12 @@ impl SequentialWriter {
         store: &mut <Store as it_memory_traits::Store>::ActualStore<'_>,
         writer: &MemoryWriter<'_, A, MV, Store>,
         bytes: &[u8],
-    ) {
+    ) -> LoResult<()> {
         let offset = self.offset.get();
 
-        writer.view.borrow().write_bytes(store, offset, bytes);
+        writer.view.borrow().try_write_bytes(store, offset, bytes)?;
 
-        self.offset.set(offset + bytes.len() as u32);
+        self.offset.set(offset + bytes.len() as u64);
+        Ok(())
     }
 }
@@ -16,7 +16,11 @@
 
 mod error;
 mod lower_array;
+mod lower_map;
+mod lower_optional;
 mod lower_record;
+mod lower_result;
+mod lower_variant;
 mod memory_writer;
 
 use crate::lowerer::memory_writer::MemoryWriter;
@@ -25,7 +29,11 @@ use crate::traits::Allocatable;
 pub use error::LoError;
 pub use lower_array::array_lower_memory;
 pub use lower_array::LoweredArray;
+pub use lower_map::map_lower_memory;
+pub use lower_optional::optional_lower_memory;
 pub use lower_record::record_lower_memory;
+pub use lower_result::result_lower_memory;
+pub use lower_variant::variant_lower_memory;
 
 pub use it_memory_traits::MemoryView;
 
@@ -43,8 +51,10 @@ pub struct ILowerer<
 impl<'m, A: Allocatable<MV, Store>, MV: MemoryView<Store>, Store: it_memory_traits::Store>
     ILowerer<'m, A, MV, Store>
 {
-    pub fn new(view: MV, allocatable: &'m mut A) -> LoResult<Self> {
-        let writer = MemoryWriter::new(view, allocatable)?;
+    /// Creates a lowerer that allocates and writes values in the memory with index `memory_index`,
+    /// `view` is a view of this memory.
+    pub fn new(view: MV, memory_index: usize, allocatable: &'m mut A) -> LoResult<Self> {
+        let writer = MemoryWriter::new(view, memory_index, allocatable)?;
         let lowerer = Self { writer };
 
         Ok(lowerer)
@@ -21,12 +21,20 @@ use thiserror::Error as ThisError;
 pub const DEFAULT_MEMORY_INDEX: usize = 0;
 
 pub trait Allocatable<MV: MemoryView<Store>, Store: it_memory_traits::Store>: Send {
+    /// Allocates `size` bytes in the memory with index `memory_index`,
+    /// returns the offset of the allocated bytes and a view of this memory.
+    ///
+    /// The size and the offset are passed to and returned from the Wasm module as `i32` or `i64`
+    /// values according to the address type of the memory.
+    ///
+    /// The returned offset must never be 0, the null pointer stands for an absent optional value.
     fn allocate<'this, 'store: 'this, 'store_inner: 'this>(
         &'this mut self,
         store: &'store mut <Store as it_memory_traits::Store>::ActualStore<'store_inner>,
-        size: u32,
+        memory_index: usize,
+        size: u64,
         type_tag: u32,
-    ) -> BoxFuture<'this, Result<(u32, MV), AllocatableError>>;
+    ) -> BoxFuture<'this, Result<(u64, MV), AllocatableError>>;
 }
 
 #[derive(Debug, ThisError)]
@@ -55,14 +63,14 @@ pub enum AllocatableError {
 
     /// Allocate input types doesn't match with needed.
     #[error(
-        "allocate func doesn't receive two i32 values,\
+        "allocate func doesn't receive a size of the pointer type and an i32 type tag,\
              probably a Wasm module's built with unsupported sdk version"
     )]
     AllocateFuncIncompatibleSignature,
 
     /// Allocate output types doesn't match with needed.
     #[error(
-        "allocate func doesn't return a one value of I32 type,\
+        "allocate func doesn't return a one value of the pointer type,\
              probably a Wasm module's built with unsupported sdk version"
     )]
     AllocateFuncIncompatibleOutput,
@@ -14,11 +14,38 @@
  * limitations under the License.
  */
 
+use crate::IEnumType;
+use crate::IFlagsType;
 use crate::IRecordType;
+use crate::IType;
+use crate::IVariantType;
 use thiserror::Error as ThisError;
 
 pub trait RecordResolvable {
     fn resolve_record(&self, record_type_id: u64) -> Result<&IRecordType, RecordResolvableError>;
+
+    /// Resolves a variant type, by default no variants are known.
+    fn resolve_variant(
+        &self,
+        variant_type_id: u64,
+    ) -> Result<&IVariantType, RecordResolvableError> {
+        Err(RecordResolvableError::VariantNotFound(variant_type_id))
+    }
+
+    /// Resolves a flags type, by default no flags are known.
+    fn resolve_flags(&self, flags_type_id: u64) -> Result<&IFlagsType, RecordResolvableError> {
+        Err(RecordResolvableError::FlagsNotFound(flags_type_id))
+    }
+
+    /// Resolves an enum type, by default no enums are known.
+    fn resolve_enum(&self, enum_type_id: u64) -> Result<&IEnumType, RecordResolvableError> {
+        Err(RecordResolvableError::EnumNotFound(enum_type_id))
+    }
+
+    /// Resolves the type an alias refers to, by default no aliases are known.
+    fn resolve_alias(&self, alias_type_id: u64) -> Result<&IType, RecordResolvableError> {
+        Err(RecordResolvableError::AliasNotFound(alias_type_id))
+    }
 }
 
 #[derive(Debug, ThisError)]
@@ -27,6 +54,35 @@ pub enum RecordResolvableError {
     #[error("Record with type id '{0}' not found")]
     RecordNotFound(u64),
 
+    /// Variant for such type is wasn't found.
+    #[error("Variant with type id '{0}' not found")]
+    VariantNotFound(u64),
+
+    /// Flags for such type is wasn't found.
+    #[error("Flags with type id '{0}' not found")]
+    FlagsNotFound(u64),
+
+    /// Enum for such type is wasn't found.
+    #[error("Enum with type id '{0}' not found")]
+    EnumNotFound(u64),
+
+    /// Alias for such type is wasn't found.
+    #[error("Alias with type id '{0}' not found")]
+    AliasNotFound(u64),
+
+    /// Alias refers to itself, directly or through other aliases.
+    #[error("Alias with type id '{0}' is recursive")]
+    RecursiveAlias(u64),
+
     #[error("Memory with index '{memory_index}' not found")]
     MemoryIsMissing { memory_index: usize },
 }
+
+/// Records registered from Rust types, e.g. with `#[derive(IRecord)]`,
+/// only records are resolved.
+impl RecordResolvable for fluence_it_types::RecordRegistry {
+    fn resolve_record(&self, record_type_id: u64) -> Result<&IRecordType, RecordResolvableError> {
+        self.record_type(record_type_id)
+            .ok_or(RecordResolvableError::RecordNotFound(record_type_id))
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::traits::RecordResolvableError;
+use crate::IType;
+use crate::IValue;
+pub use fluence_it_types::PathSegment;
+pub use fluence_it_types::ValuePath;
+
+use thiserror::Error as ThisError;
+
+use std::fmt;
+
+/// Describes why a value doesn't match a type.
+#[derive(Debug, ThisError)]
+pub enum TypeCheckErrorKind {
+    /// The value has another type.
+    #[error("expected a value of type '{}', but {received_value:?} was found", .expected_type.to_string())]
+    TypeMismatch {
+        expected_type: IType,
+        received_value: IValue,
+    },
+
+    /// The record value has more or less fields than its type.
+    #[error("record with name '{record_name}' has {expected} fields, but {actual} were found")]
+    FieldsCountMismatch {
+        record_name: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// The variant discriminant doesn't correspond to any of its cases.
+    #[error(
+        "variant with name '{variant_name}' doesn't have a case with discriminant {discriminant}"
+    )]
+    InvalidVariantDiscriminant {
+        variant_name: String,
+        discriminant: u32,
+    },
+
+    /// The variant case has a payload, but the value doesn't, or vice versa.
+    #[error("case '{case_name}' of variant with name '{variant_name}' has an unexpected payload")]
+    VariantPayloadMismatch {
+        variant_name: String,
+        case_name: String,
+    },
+
+    /// The flags have bits set that don't correspond to any flag.
+    #[error("flags with name '{flags_name}' don't have flags for some of bits in {bits:#b}")]
+    InvalidFlags { flags_name: String, bits: u64 },
+
+    /// The enum discriminant doesn't correspond to any of its members.
+    #[error("enum with name '{enum_name}' doesn't have a member with discriminant {discriminant}")]
+    InvalidEnumDiscriminant {
+        enum_name: String,
+        discriminant: u32,
+    },
+
+    /// The handle check failed, e.g. the handle isn't live.
+    #[error("{0}")]
+    InvalidHandle(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("{0}")]
+    RecordResolvableError(#[from] RecordResolvableError),
+}
+
+/// A type check error along with the path to the offending value.
+#[derive(Debug)]
+pub struct TypeCheckError {
+    pub path: ValuePath,
+    pub kind: TypeCheckErrorKind,
+}
+
+impl fmt::Display for TypeCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+impl std::error::Error for TypeCheckError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * Copyright 2021 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Structural validation of interface values against interface types,
+//! it doesn't need a module instance, only a way to resolve type ids.
+
+mod error;
+
+pub use error::PathSegment;
+pub use error::TypeCheckError;
+pub use error::TypeCheckErrorKind;
+pub use error::ValuePath;
+
+use crate::traits::RecordResolvable;
+use crate::utils::resolve_alias;
+use crate::IFlagsType;
+use crate::IType;
+use crate::IValue;
+
+pub type TcResult<T> = std::result::Result<T, TypeCheckError>;
+
+/// Checks a handle of the resource with the provided id, e.g. that it is
+/// present in a table of live handles.
+pub type HandleCheck<'h> =
+    dyn Fn(u64, u32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + 'h;
+
+/// Checks that `value` is a value of type `ty`.
+pub fn check_value<R: RecordResolvable>(resolver: &R, ty: &IType, value: &IValue) -> TcResult<()> {
+    TypeChecker::new(resolver).check_value(ty, value)
+}
+
+/// Checks that `value` is a value of type `ty`, paths in errors start with `name`,
+/// e.g. the name of the function argument the value is passed as.
+pub fn check_named_value<R: RecordResolvable>(
+    resolver: &R,
+    name: &str,
+    ty: &IType,
+    value: &IValue,
+) -> TcResult<()> {
+    TypeChecker::new(resolver).check_named_value(name, ty, value)
+}
+
+/// Checks that `fields` are fields of a record with type `record_type_id`.
+pub fn check_record_fields<R: RecordResolvable>(
+    resolver: &R,
+    record_type_id: u64,
+    fields: &[IValue],
+) -> TcResult<()> {
+    TypeChecker::new(resolver).check_record_fields(record_type_id, fields)
+}
+
+/// Checks values against types, the free functions of this module are shortcuts for it.
+///
+/// Handles are only checked to be handles, unless a handle check is provided
+/// with [`TypeChecker::with_handle_check`].
+pub struct TypeChecker<'r, R: RecordResolvable> {
+    resolver: &'r R,
+    check_handle: Option<&'r HandleCheck<'r>>,
+    path: Vec<PathSegment>,
+}
+
+impl<'r, R: RecordResolvable> TypeChecker<'r, R> {
+    pub fn new(resolver: &'r R) -> Self {
+        Self {
+            resolver,
+            check_handle: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Checks every handle met in values with `check_handle`.
+    pub fn with_handle_check(mut self, check_handle: &'r HandleCheck<'r>) -> Self {
+        self.check_handle = Some(check_handle);
+        self
+    }
+
+    /// Checks that `value` is a value of type `ty`.
+    pub fn check_value(&mut self, ty: &IType, value: &IValue) -> TcResult<()> {
+        self.path.clear();
+        self.check(ty, value)
+    }
+
+    /// Checks that `value` is a value of type `ty`, paths in errors start with `name`.
+    pub fn check_named_value(&mut self, name: &str, ty: &IType, value: &IValue) -> TcResult<()> {
+        self.path.clear();
+        self.check_inner(PathSegment::Field(name.to_string()), ty, value)
+    }
+
+    /// Checks that `fields` are fields of a record with type `record_type_id`.
+    pub fn check_record_fields(&mut self, record_type_id: u64, fields: &[IValue]) -> TcResult<()> {
+        self.path.clear();
+        self.check_fields(record_type_id, fields)
+    }
+
+    fn check(&mut self, ty: &IType, value: &IValue) -> TcResult<()> {
+        match (ty, value) {
+            (IType::Boolean, IValue::Boolean(_))
+            | (IType::S8, IValue::S8(_))
+            | (IType::S16, IValue::S16(_))
+            | (IType::S32, IValue::S32(_))
+            | (IType::S64, IValue::S64(_))
+            | (IType::U8, IValue::U8(_))
+            | (IType::U16, IValue::U16(_))
+            | (IType::U32, IValue::U32(_))
+            | (IType::U64, IValue::U64(_))
+            | (IType::I32, IValue::I32(_))
+            | (IType::I64, IValue::I64(_))
+            | (IType::F32, IValue::F32(_))
+            | (IType::F64, IValue::F64(_))
+            | (IType::Char, IValue::Char(_))
+            | (IType::U128, IValue::U128(_))
+            | (IType::S128, IValue::S128(_))
+            | (IType::String, IValue::String(_))
+            | (IType::ByteArray, IValue::ByteArray(_))
+            | (IType::Optional(_), IValue::Optional(None)) => Ok(()),
+            (IType::Array(ty), IValue::ByteArray(_)) if ty.as_ref() == &IType::U8 => Ok(()),
+            (IType::Array(ty), IValue::Array(values)) => self.check_elements(ty, values),
+            (IType::ByteArray, IValue::Array(values)) => self.check_elements(&IType::U8, values),
+            (IType::Record(record_type_id), IValue::Record(fields)) => {
+                self.check_fields(*record_type_id, fields)
+            }
+            (IType::Optional(ty), IValue::Optional(Some(value))) => self.check(ty, value),
+            (IType::Variant(variant_type_id), IValue::Variant(discriminant, payload)) => {
+                self.check_variant(*variant_type_id, *discriminant, payload.as_deref())
+            }
+            (IType::Result(ok_ty, _), IValue::Result(Ok(value))) => {
+                self.check_inner(PathSegment::Field("ok".to_string()), ok_ty, value)
+            }
+            (IType::Result(_, err_ty), IValue::Result(Err(value))) => {
+                self.check_inner(PathSegment::Field("err".to_string()), err_ty, value)
+            }
+            (IType::Map(key_ty, value_ty), IValue::Map(entries)) => {
+                for (position, (key, value)) in entries.iter().enumerate() {
+                    self.path.push(PathSegment::Index(position));
+                    self.check_inner(PathSegment::Field("key".to_string()), key_ty, key)?;
+                    self.check_inner(PathSegment::Field("value".to_string()), value_ty, value)?;
+                    self.path.pop();
+                }
+
+                Ok(())
+            }
+            (IType::Flags(flags_type_id), IValue::Flags(bits)) => {
+                let flags_type = self.resolve(|r| r.resolve_flags(*flags_type_id))?;
+                let flags_count = flags_type.flags.len();
+
+                if flags_count < IFlagsType::MAX_FLAGS_COUNT && bits >> flags_count != 0 {
+                    return Err(self.error(TypeCheckErrorKind::InvalidFlags {
+                        flags_name: flags_type.name.clone(),
+                        bits: *bits,
+                    }));
+                }
+
+                Ok(())
+            }
+            (IType::Enum(enum_type_id), IValue::Enum(discriminant)) => {
+                let enum_type = self.resolve(|r| r.resolve_enum(*enum_type_id))?;
+
+                if *discriminant as usize >= enum_type.members.len() {
+                    return Err(self.error(TypeCheckErrorKind::InvalidEnumDiscriminant {
+                        enum_name: enum_type.name.clone(),
+                        discriminant: *discriminant,
+                    }));
+                }
+
+                Ok(())
+            }
+            (IType::Handle(resource_id), IValue::Handle(handle)) => match self.check_handle {
+                Some(check_handle) => check_handle(*resource_id, *handle)
+                    .map_err(|e| self.error(TypeCheckErrorKind::InvalidHandle(e))),
+                None => Ok(()),
+            },
+            (IType::Alias(_), _) => {
+                let resolver = self.resolver;
+                let ty = resolve_alias(resolver, ty).map_err(|e| self.error(e.into()))?;
+
+                self.check(ty, value)
+            }
+            _ => Err(self.error(TypeCheckErrorKind::TypeMismatch {
+                expected_type: ty.clone(),
+                received_value: value.clone(),
+            })),
+        }
+    }
+
+    fn check_fields(&mut self, record_type_id: u64, fields: &[IValue]) -> TcResult<()> {
+        let record_type = self.resolve(|r| r.resolve_record(record_type_id))?;
+
+        if fields.len() != record_type.fields.len() {
+            return Err(self.error(TypeCheckErrorKind::FieldsCountMismatch {
+                record_name: record_type.name.clone(),
+                expected: record_type.fields.len(),
+                actual: fields.len(),
+            }));
+        }
+
+        for (position, (field_type, field)) in record_type.fields.iter().zip(fields).enumerate() {
+            let name = match &field_type.name {
+                Some(name) => name.clone(),
+                None => position.to_string(),
+            };
+
+            self.check_inner(PathSegment::Field(name), &field_type.ty, field)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_variant(
+        &mut self,
+        variant_type_id: u64,
+        discriminant: u32,
+        payload: Option<&IValue>,
+    ) -> TcResult<()> {
+        let variant_type = self.resolve(|r| r.resolve_variant(variant_type_id))?;
+
+        let case = match variant_type.cases.get(discriminant as usize) {
+            Some(case) => case,
+            None => {
+                return Err(self.error(TypeCheckErrorKind::InvalidVariantDiscriminant {
+                    variant_name: variant_type.name.clone(),
+                    discriminant,
+                }))
+            }
+        };
+
+        match (&case.ty, payload) {
+            (None, None) => Ok(()),
+            (Some(ty), Some(value)) => {
+                self.check_inner(PathSegment::Field(case.name.clone()), ty, value)
+            }
+            _ => Err(self.error(TypeCheckErrorKind::VariantPayloadMismatch {
+                variant_name: variant_type.name.clone(),
+                case_name: case.name.clone(),
+            })),
+        }
+    }
+
+    fn check_elements(&mut self, ty: &IType, values: &[IValue]) -> TcResult<()> {
+        for (position, value) in values.iter().enumerate() {
+            self.check_inner(PathSegment::Index(position), ty, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_inner(&mut self, segment: PathSegment, ty: &IType, value: &IValue) -> TcResult<()> {
+        self.path.push(segment);
+        self.check(ty, value)?;
+        self.path.pop();
+
+        Ok(())
+    }
+
+    fn resolve<T: ?Sized>(
+        &self,
+        resolve: impl FnOnce(&'r R) -> Result<&'r T, crate::traits::RecordResolvableError>,
+    ) -> TcResult<&'r T> {
+        resolve(self.resolver).map_err(|e| self.error(e.into()))
+    }
+
+    fn error(&self, kind: TypeCheckErrorKind) -> TypeCheckError {
+        TypeCheckError {
+            path: ValuePath(self.path.clone()),
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::RecordResolvableError;
+    use crate::IRecordType;
+    use crate::NEVec;
+
+    use fluence_it_types::IRecordFieldType;
+
+    /// Record 0 is `Address { street: string, zip: u32 }`,
+    /// record 1 is `User { name: string, addresses: array(Address) }`.
+    struct Resolver {
+        records: Vec<IRecordType>,
+    }
+
+    impl RecordResolvable for Resolver {
+        fn resolve_record(
+            &self,
+            record_type_id: u64,
+        ) -> Result<&IRecordType, RecordResolvableError> {
+            self.records
+                .get(record_type_id as usize)
+                .ok_or(RecordResolvableError::RecordNotFound(record_type_id))
+        }
+    }
+
+    fn record_type(name: &str, fields: Vec<(&str, IType)>) -> IRecordType {
+        let fields = fields
+            .into_iter()
+            .map(|(name, ty)| IRecordFieldType {
+                name: Some(name.to_string()),
+                ty,
+            })
+            .collect();
+
+        IRecordType {
+            name: name.to_string(),
+            fields: NEVec::new(fields).unwrap(),
+        }
+    }
+
+    fn resolver() -> Resolver {
+        let address = record_type(
+            "Address",
+            vec![("street", IType::String), ("zip", IType::U32)],
+        );
+        let user = record_type(
+            "User",
+            vec![
+                ("name", IType::String),
+                ("addresses", IType::Array(Box::new(IType::Record(0)))),
+            ],
+        );
+
+        Resolver {
+            records: vec![address, user],
+        }
+    }
+
+    fn address(zip: IValue) -> IValue {
+        IValue::Record(NEVec::new(vec![IValue::String("Main st".to_string()), zip]).unwrap())
+    }
+
+    fn user(addresses: Vec<IValue>) -> IValue {
+        IValue::Record(
+            NEVec::new(vec![
+                IValue::String("Alice".to_string()),
+                IValue::Array(addresses),
+            ])
+            .unwrap(),
+        )
+    }
+
+    fn path(error: &TypeCheckError) -> String {
+        error.path.to_string()
+    }
+
+    #[test]
+    fn test_check_value() {
+        let addresses = vec![address(IValue::U32(1)), address(IValue::U32(2))];
+
+        check_value(&resolver(), &IType::Record(1), &user(addresses)).unwrap();
+    }
+
+    #[test]
+    fn test_check_named_value_nested_in_record_and_array() {
+        let addresses = vec![
+            address(IValue::U32(1)),
+            address(IValue::U32(2)),
+            address(IValue::S32(3)),
+        ];
+
+        let error = check_named_value(&resolver(), "user", &IType::Record(1), &user(addresses))
+            .unwrap_err();
+
+        assert_eq!(path(&error), "user.addresses[2].zip");
+        assert!(matches!(
+            error.kind,
+            TypeCheckErrorKind::TypeMismatch {
+                expected_type: IType::U32,
+                received_value: IValue::S32(3),
+            }
+        ));
+        assert!(error.to_string().starts_with("user.addresses[2].zip: "));
+    }
+
+    #[test]
+    fn test_check_value_nested_in_map() {
+        let ty = IType::Map(
+            Box::new(IType::String),
+            Box::new(IType::Array(Box::new(IType::U8))),
+        );
+        let value = IValue::Map(vec![
+            (IValue::String("a".to_string()), IValue::ByteArray(vec![1])),
+            (
+                IValue::String("b".to_string()),
+                IValue::Array(vec![IValue::U8(1), IValue::U16(2)]),
+            ),
+        ]);
+
+        let error = check_value(&resolver(), &ty, &value).unwrap_err();
+
+        assert_eq!(path(&error), "[1].value[1]");
+        assert!(matches!(
+            error.kind,
+            TypeCheckErrorKind::TypeMismatch {
+                expected_type: IType::U8,
+                received_value: IValue::U16(2),
+            }
+        ));
+
+        let value = IValue::Map(vec![(IValue::U8(1), IValue::ByteArray(vec![]))]);
+        let error = check_value(&resolver(), &ty, &value).unwrap_err();
+
+        assert_eq!(path(&error), "[0].key");
+    }
+
+    #[test]
+    fn test_check_record_fields() {
+        let fields = [IValue::String("Main st".to_string()), IValue::U32(1)];
+        check_record_fields(&resolver(), 0, &fields).unwrap();
+
+        let error = check_record_fields(&resolver(), 0, &fields[..1]).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            TypeCheckErrorKind::FieldsCountMismatch {
+                expected: 2,
+                actual: 1,
+                ..
+            }
+        ));
+
+        let error = check_record_fields(&resolver(), 2, &fields).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            TypeCheckErrorKind::RecordResolvableError(RecordResolvableError::RecordNotFound(2))
+        ));
+    }
+
+    #[test]
+    fn test_check_handle() {
+        let ty = IType::Array(Box::new(IType::Handle(7)));
+        let value = IValue::Array(vec![IValue::Handle(1), IValue::Handle(2)]);
+
+        // without a handle check, any handle is valid
+        check_value(&resolver(), &ty, &value).unwrap();
+
+        let check_handle = |resource_id: u64, handle: u32| match (resource_id, handle) {
+            (7, 1) => Ok(()),
+            _ => Err(format!("handle {} is forged", handle).into()),
+        };
+        let resolver = resolver();
+        let mut checker = TypeChecker::new(&resolver).with_handle_check(&check_handle);
+
+        let error = checker.check_value(&ty, &value).unwrap_err();
+        assert_eq!(error.to_string(), "[1]: handle 2 is forged");
+
+        // the path of the previous error doesn't leak into the next one
+        let error = checker
+            .check_named_value("handle", &IType::Handle(7), &IValue::Handle(3))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "handle: handle 3 is forged");
+    }
+}
@@ -14,82 +14,346 @@
  * limitations under the License.
  */
 
+use crate::traits::RecordResolvable;
+use crate::traits::RecordResolvableError;
 use crate::IRecordType;
 use crate::IType;
 use crate::IValue;
 
-/// Size of a value in a serialized view.
-pub fn ser_type_size(ty: &IType) -> u32 {
-    const WASM_POINTER_SIZE: u32 = 4;
+use it_memory_traits::AddressType;
 
-    match ty {
+/// Follows a chain of aliases and returns the first type that isn't an alias.
+pub fn resolve_alias<'a, R: RecordResolvable>(
+    resolver: &'a R,
+    ty: &'a IType,
+) -> Result<&'a IType, RecordResolvableError> {
+    let mut visited = Vec::new();
+    let mut ty = ty;
+
+    while let IType::Alias(alias_type_id) = ty {
+        if visited.contains(alias_type_id) {
+            return Err(RecordResolvableError::RecursiveAlias(*alias_type_id));
+        }
+
+        visited.push(*alias_type_id);
+        ty = resolver.resolve_alias(*alias_type_id)?;
+    }
+
+    Ok(ty)
+}
+
+/// Size of a value in a serialized view, pointers are of the size of `address_type`,
+/// aliases are resolved with `resolver`.
+pub fn ser_type_size<R: RecordResolvable>(
+    resolver: &R,
+    ty: &IType,
+    address_type: AddressType,
+) -> Result<u64, RecordResolvableError> {
+    let pointer_size = address_type.pointer_size();
+
+    let size = match resolve_alias(resolver, ty)? {
         IType::Boolean | IType::S8 | IType::U8 => 1,
         IType::S16 | IType::U16 => 2,
-        IType::S32 | IType::U32 | IType::I32 | IType::F32 => 4,
-        IType::Record(_) => 4,
-        // Vec-like types are passed by pointer and size
-        IType::String | IType::ByteArray | IType::Array(_) => 2 * WASM_POINTER_SIZE,
+        IType::S32 | IType::U32 | IType::I32 | IType::F32 | IType::Char => 4,
+        IType::Record(_) | IType::Optional(_) | IType::Variant(_) | IType::Result(..) => {
+            pointer_size
+        }
+        // Vec-like types are passed by pointer and size, both are of the pointer size
+        IType::String | IType::ByteArray | IType::Array(_) | IType::Map(..) => 2 * pointer_size,
         IType::S64 | IType::U64 | IType::I64 | IType::F64 => 8,
-    }
+        // flags are packed into u64, enums are passed by their u32 discriminant
+        // and handles by their u32 index in the host-side handle table
+        IType::Flags(_) => 8,
+        IType::Enum(_) | IType::Handle(_) => 4,
+        IType::S128 | IType::U128 => 16,
+        IType::Alias(_) => unreachable!("resolve_alias never returns an alias"),
+    };
+
+    Ok(size)
 }
 
-/// Size of a value in a serialized view.
-pub fn ser_value_size(value: &IValue) -> u32 {
+/// Size of a value in a serialized view, pointers are of the size of `address_type`.
+pub fn ser_value_size(value: &IValue, address_type: AddressType) -> u64 {
+    let pointer_size = address_type.pointer_size();
+
     match value {
         IValue::Boolean(_) | IValue::S8(_) | IValue::U8(_) => 1,
         IValue::S16(_) | IValue::U16(_) => 2,
-        IValue::S32(_) | IValue::U32(_) | IValue::F32(_) | IValue::I32(_) => 4,
-        IValue::S64(_) | IValue::U64(_) | IValue::F64(_) | IValue::I64(_) => 8,
-        IValue::String(_) | IValue::ByteArray(_) | IValue::Array(_) => 2 * 4,
-        IValue::Record(_) => 4,
+        IValue::S32(_) | IValue::U32(_) | IValue::F32(_) | IValue::I32(_) | IValue::Char(_) => 4,
+        IValue::S64(_) | IValue::U64(_) | IValue::F64(_) | IValue::I64(_) | IValue::Flags(_) => 8,
+        IValue::Enum(_) | IValue::Handle(_) => 4,
+        IValue::S128(_) | IValue::U128(_) => 16,
+        IValue::String(_) | IValue::ByteArray(_) | IValue::Array(_) | IValue::Map(_) => {
+            2 * pointer_size
+        }
+        IValue::Record(_) | IValue::Optional(_) | IValue::Variant(..) | IValue::Result(_) => {
+            pointer_size
+        }
     }
 }
 
-/// Returns the record size in bytes.
-pub fn record_size(record_type: &IRecordType) -> u32 {
+/// Size and shape statistics of a value, e.g. to reject too large values before lowering them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValueStats {
+    /// Total size of strings, byte arrays, arrays and maps in bytes, elements are counted
+    /// by their serialized size, i.e. as they are laid out in memory when lowered.
+    pub sequences_size: u64,
+
+    /// Nesting depth, a scalar value and an empty container have depth 1.
+    pub depth: usize,
+
+    /// The number of values including the value itself and all the nested ones.
+    pub values_count: usize,
+}
+
+/// Collects size and shape statistics of a value lowered into a memory with `address_type`.
+pub fn value_stats(value: &IValue, address_type: AddressType) -> ValueStats {
+    let mut stats = ValueStats {
+        sequences_size: 0,
+        depth: 1,
+        values_count: 1,
+    };
+
+    let mut add_inner = |inner: &IValue| {
+        let inner_stats = value_stats(inner, address_type);
+        stats.sequences_size += inner_stats.sequences_size;
+        stats.depth = stats.depth.max(inner_stats.depth + 1);
+        stats.values_count += inner_stats.values_count;
+    };
+
+    match value {
+        IValue::Array(values) => values.iter().for_each(&mut add_inner),
+        IValue::Record(fields) => fields.iter().for_each(&mut add_inner),
+        IValue::Optional(Some(value)) | IValue::Variant(_, Some(value)) => add_inner(value),
+        IValue::Result(Ok(value)) | IValue::Result(Err(value)) => add_inner(value),
+        IValue::Map(entries) => entries.iter().for_each(|(key, value)| {
+            add_inner(key);
+            add_inner(value);
+        }),
+        _ => {}
+    }
+
+    stats.sequences_size += match value {
+        IValue::String(value) => value.len() as u64,
+        IValue::ByteArray(values) => values.len() as u64,
+        IValue::Array(values) => values.iter().map(|v| ser_value_size(v, address_type)).sum(),
+        // entries are lowered as an array of pointers to records with a key and a value
+        IValue::Map(entries) => entries.len() as u64 * address_type.pointer_size(),
+        _ => 0,
+    };
+
+    stats
+}
+
+/// Returns the record size in bytes, aliased field types are resolved with `resolver`.
+pub fn record_size<R: RecordResolvable>(
+    resolver: &R,
+    record_type: &IRecordType,
+    address_type: AddressType,
+) -> Result<u64, RecordResolvableError> {
     record_type
         .fields
         .iter()
-        .map(|f| ser_type_size(&f.ty))
+        .map(|f| ser_type_size(resolver, &f.ty, address_type))
         .sum()
 }
 
-pub fn type_tag_form_itype(itype: &IType) -> u32 {
-    const POINTER_CODE: u32 = 3; // u32 in the sdk
+/// Returns the type tag of a value used by the allocator, pointers are tagged
+/// as `u32` or `u64` according to `address_type`, aliases are resolved with `resolver`.
+pub fn type_tag_form_itype<R: RecordResolvable>(
+    resolver: &R,
+    itype: &IType,
+    address_type: AddressType,
+) -> Result<u32, RecordResolvableError> {
+    let pointer_code = pointer_type_tag(address_type);
 
-    match itype {
-        IType::Boolean => 0,          // u8
-        IType::U8 => 1,               // u8
-        IType::U16 => 2,              // u16
-        IType::U32 => 3,              // u32
-        IType::U64 => 4,              // u64
-        IType::S8 => 5,               // i8
-        IType::S16 => 6,              // i16
-        IType::S32 | IType::I32 => 7, // i32
-        IType::S64 | IType::I64 => 8, // i64
-        IType::F32 => 9,              // f32
-        IType::F64 => 10,             // f64
-        IType::ByteArray | IType::Array(_) | IType::Record(_) | IType::String => POINTER_CODE,
-    }
+    let type_tag = match resolve_alias(resolver, itype)? {
+        IType::Boolean => 0,                                               // u8
+        IType::U8 => 1,                                                    // u8
+        IType::U16 => 2,                                                   // u16
+        IType::U32 | IType::Enum(_) | IType::Char | IType::Handle(_) => 3, // u32
+        IType::U64 | IType::Flags(_) => 4,                                 // u64
+        IType::S8 => 5,                                                    // i8
+        IType::S16 => 6,                                                   // i16
+        IType::S32 | IType::I32 => 7,                                      // i32
+        IType::S64 | IType::I64 => 8,                                      // i64
+        IType::F32 => 9,                                                   // f32
+        IType::F64 => 10,                                                  // f64
+        IType::U128 => 11,                                                 // u128
+        IType::S128 => 12,                                                 // i128
+        IType::ByteArray
+        | IType::Array(_)
+        | IType::Record(_)
+        | IType::Optional(_)
+        | IType::Variant(_)
+        | IType::Result(..)
+        | IType::Map(..)
+        | IType::String => pointer_code,
+        IType::Alias(_) => unreachable!("resolve_alias never returns an alias"),
+    };
+
+    Ok(type_tag)
 }
 
-pub fn type_tag_form_ivalue(itype: &IValue) -> u32 {
-    const POINTER_CODE: u32 = 3; // u32 in the sdk
+pub fn type_tag_form_ivalue(itype: &IValue, address_type: AddressType) -> u32 {
+    let pointer_code = pointer_type_tag(address_type);
 
     match itype {
-        IValue::Boolean(_) => 0,              // u8
-        IValue::U8(_) => 1,                   // u8
-        IValue::U16(_) => 2,                  // u16
-        IValue::U32(_) => 3,                  // u32
-        IValue::U64(_) => 4,                  // u64
-        IValue::S8(_) => 5,                   // i8
-        IValue::S16(_) => 6,                  // i16
+        IValue::Boolean(_) => 0, // u8
+        IValue::U8(_) => 1,      // u8
+        IValue::U16(_) => 2,     // u16
+        IValue::U32(_) | IValue::Enum(_) | IValue::Char(_) | IValue::Handle(_) => 3, // u32
+        IValue::U64(_) | IValue::Flags(_) => 4, // u64
+        IValue::S8(_) => 5,      // i8
+        IValue::S16(_) => 6,     // i16
         IValue::S32(_) | IValue::I32(_) => 7, // i32
         IValue::S64(_) | IValue::I64(_) => 8, // i64
-        IValue::F32(_) => 9,                  // f32
-        IValue::F64(_) => 10,                 // f64
-        IValue::ByteArray(_) | IValue::Array(_) | IValue::Record(_) | IValue::String(_) => {
-            POINTER_CODE
+        IValue::F32(_) => 9,     // f32
+        IValue::F64(_) => 10,    // f64
+        IValue::U128(_) => 11,   // u128
+        IValue::S128(_) => 12,   // i128
+        IValue::ByteArray(_)
+        | IValue::Array(_)
+        | IValue::Record(_)
+        | IValue::Optional(_)
+        | IValue::Variant(..)
+        | IValue::Result(_)
+        | IValue::Map(_)
+        | IValue::String(_) => pointer_code,
+    }
+}
+
+fn pointer_type_tag(address_type: AddressType) -> u32 {
+    match address_type {
+        AddressType::I32 => 3, // u32 in the sdk
+        AddressType::I64 => 4, // u64 in the sdk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NEVec;
+
+    struct Resolver {
+        alias: IType,
+    }
+
+    impl RecordResolvable for Resolver {
+        fn resolve_record(
+            &self,
+            record_type_id: u64,
+        ) -> Result<&IRecordType, RecordResolvableError> {
+            Err(RecordResolvableError::RecordNotFound(record_type_id))
+        }
+
+        fn resolve_alias(&self, alias_type_id: u64) -> Result<&IType, RecordResolvableError> {
+            match alias_type_id {
+                0 => Ok(&self.alias),
+                _ => Err(RecordResolvableError::AliasNotFound(alias_type_id)),
+            }
         }
     }
+
+    #[test]
+    fn test_ser_type_size_alias() {
+        let resolver = Resolver {
+            alias: IType::String,
+        };
+
+        let size = ser_type_size(&resolver, &IType::Alias(0), AddressType::I32);
+        assert_eq!(size.unwrap(), 8);
+
+        let size = ser_type_size(&resolver, &IType::Alias(0), AddressType::I64);
+        assert_eq!(size.unwrap(), 16);
+
+        let size = ser_type_size(&resolver, &IType::Alias(1), AddressType::I32);
+        assert!(matches!(size, Err(RecordResolvableError::AliasNotFound(1))));
+    }
+
+    #[test]
+    fn test_type_tag_form_itype_alias() {
+        let resolver = Resolver { alias: IType::U16 };
+
+        let type_tag = type_tag_form_itype(&resolver, &IType::Alias(0), AddressType::I32);
+        assert_eq!(type_tag.unwrap(), 2);
+
+        let type_tag = type_tag_form_itype(&resolver, &IType::Alias(1), AddressType::I32);
+        assert!(matches!(
+            type_tag,
+            Err(RecordResolvableError::AliasNotFound(1))
+        ));
+    }
+
+    #[test]
+    fn test_value_stats_scalars() {
+        let expected = ValueStats {
+            sequences_size: 0,
+            depth: 1,
+            values_count: 1,
+        };
+
+        assert_eq!(value_stats(&IValue::U32(1), AddressType::I32), expected);
+        assert_eq!(
+            value_stats(&IValue::Array(vec![]), AddressType::I64),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_value_stats_nested_arrays() {
+        let value = IValue::Array(vec![
+            IValue::Array(vec![IValue::U32(1), IValue::U32(2)]),
+            IValue::Array(vec![IValue::String("abc".to_string())]),
+        ]);
+
+        // 8 bytes of u32s, 3 bytes of the string, the string and the inner arrays
+        // are laid out as a pointer and a length each
+        assert_eq!(
+            value_stats(&value, AddressType::I32),
+            ValueStats {
+                sequences_size: 8 + 3 + 3 * 8,
+                depth: 3,
+                values_count: 6,
+            }
+        );
+        assert_eq!(
+            value_stats(&value, AddressType::I64),
+            ValueStats {
+                sequences_size: 8 + 3 + 3 * 16,
+                depth: 3,
+                values_count: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_value_stats_maps() {
+        let map = IValue::Map(vec![
+            (
+                IValue::String("ab".to_string()),
+                IValue::Array(vec![IValue::U8(1), IValue::U8(2), IValue::U8(3)]),
+            ),
+            (IValue::String("c".to_string()), IValue::Array(vec![])),
+        ]);
+        let value = IValue::Record(NEVec::new(vec![map]).unwrap());
+
+        // 3 bytes of keys, 3 bytes of u8s and a pointer per entry
+        assert_eq!(
+            value_stats(&value, AddressType::I32),
+            ValueStats {
+                sequences_size: 3 + 3 + 2 * 4,
+                depth: 4,
+                values_count: 9,
+            }
+        );
+        assert_eq!(
+            value_stats(&value, AddressType::I64),
+            ValueStats {
+                sequences_size: 3 + 3 + 2 * 8,
+                depth: 4,
+                values_count: 9,
+            }
+        );
+    }
 }
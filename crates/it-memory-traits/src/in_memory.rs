@@ -0,0 +1,242 @@
+/*
+ * Copyright 2022 Fluence Labs Limited
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A memory backed by a `Vec<u8>`, e.g. to test lifting, lowering
+//! and adapters without a Wasm engine.
+
+use crate::AddressType;
+use crate::Memory;
+use crate::MemoryAccessError;
+use crate::MemoryReadable;
+use crate::MemoryView;
+use crate::MemoryWritable;
+use crate::Store;
+
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+/// A store that doesn't hold anything, the memory owns its bytes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InMemoryStore;
+
+impl Store for InMemoryStore {
+    type ActualStore<'c> = ();
+}
+
+/// Size limits of an [`InMemory`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InMemoryLimits {
+    /// The initial size in bytes.
+    pub initial_size: u64,
+
+    /// The maximum size in bytes the memory can grow to.
+    pub max_size: u64,
+}
+
+impl Default for InMemoryLimits {
+    fn default() -> Self {
+        Self {
+            initial_size: 0,
+            max_size: u32::MAX as u64,
+        }
+    }
+}
+
+/// A growable memory, its views share the bytes, so changes made
+/// through a view are visible through the memory and other views.
+#[derive(Debug, Clone)]
+pub struct InMemory {
+    view: InMemoryView,
+}
+
+impl InMemory {
+    /// Creates a zeroed memory of `initial_size` bytes that may grow up to `max_size` bytes.
+    pub fn new(limits: InMemoryLimits) -> Self {
+        Self::from_bytes(vec![0; limits.initial_size as usize], limits.max_size)
+    }
+
+    /// Creates a memory with the given content that may grow up to `max_size` bytes.
+    pub fn from_bytes(bytes: Vec<u8>, max_size: u64) -> Self {
+        let inner = Inner {
+            bytes,
+            max_size,
+            address_type: AddressType::I32,
+        };
+
+        Self {
+            view: InMemoryView {
+                inner: Arc::new(Mutex::new(inner)),
+            },
+        }
+    }
+
+    /// Sets the type of addresses, memories have 32-bit addresses by default.
+    pub fn with_address_type(self, address_type: AddressType) -> Self {
+        self.view.lock().address_type = address_type;
+        self
+    }
+
+    /// Returns the current size in bytes.
+    pub fn size(&self) -> u64 {
+        self.view.size()
+    }
+
+    /// Grows the memory by `additional` zeroed bytes, returns the previous size
+    /// or `None` if the new size would exceed the maximum size.
+    pub fn grow(&self, additional: u64) -> Option<u64> {
+        let mut inner = self.view.lock();
+        let size = inner.bytes.len() as u64;
+        let new_size = size.checked_add(additional)?;
+
+        if new_size > inner.max_size {
+            return None;
+        }
+
+        inner.bytes.resize(new_size as usize, 0);
+        Some(size)
+    }
+
+    /// Returns a copy of the memory content.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.view.lock().bytes.clone()
+    }
+}
+
+impl Default for InMemory {
+    fn default() -> Self {
+        Self::new(InMemoryLimits::default())
+    }
+}
+
+impl From<Vec<u8>> for InMemory {
+    /// Creates a memory with the given content that may grow without limits.
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from_bytes(bytes, u64::MAX)
+    }
+}
+
+impl Memory<InMemoryView, InMemoryStore> for InMemory {
+    fn view(&self) -> InMemoryView {
+        self.view.clone()
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    bytes: Vec<u8>,
+    max_size: u64,
+    address_type: AddressType,
+}
+
+/// A view of an [`InMemory`].
+#[derive(Debug, Clone)]
+pub struct InMemoryView {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl InMemoryView {
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // `with_slice` runs user code under the lock, if it panics the lock is poisoned,
+        // but the code only reads the bytes, so they are still consistent
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn size(&self) -> u64 {
+        self.lock().bytes.len() as u64
+    }
+}
+
+impl MemoryReadable<InMemoryStore> for InMemoryView {
+    fn read_byte(&self, _store: &mut (), offset: u64) -> u8 {
+        self.lock().bytes[offset as usize]
+    }
+
+    fn read_array<const COUNT: usize>(&self, _store: &mut (), offset: u64) -> [u8; COUNT] {
+        let offset = offset as usize;
+
+        // the slice has exactly `COUNT` bytes, so the conversion never fails
+        self.lock().bytes[offset..offset + COUNT]
+            .try_into()
+            .unwrap()
+    }
+
+    fn read_vec(&self, _store: &mut (), offset: u64, size: u64) -> Vec<u8> {
+        let offset = offset as usize;
+
+        self.lock().bytes[offset..offset + size as usize].to_vec()
+    }
+
+    fn read_into(&self, _store: &mut (), offset: u64, buffer: &mut [u8]) {
+        let offset = offset as usize;
+
+        buffer.copy_from_slice(&self.lock().bytes[offset..offset + buffer.len()]);
+    }
+
+    fn with_slice<R>(
+        &self,
+        _store: &mut (),
+        offset: u64,
+        size: u64,
+        f: impl FnOnce(&[u8]) -> R,
+    ) -> Option<R> {
+        let offset = offset as usize;
+
+        Some(f(&self.lock().bytes[offset..offset + size as usize]))
+    }
+}
+
+impl MemoryWritable<InMemoryStore> for InMemoryView {
+    fn write_byte(&self, _store: &mut (), offset: u64, value: u8) {
+        self.lock().bytes[offset as usize] = value;
+    }
+
+    fn write_bytes(&self, _store: &mut (), offset: u64, bytes: &[u8]) {
+        let offset = offset as usize;
+
+        self.lock().bytes[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+impl MemoryView<InMemoryStore> for InMemoryView {
+    fn check_bounds(
+        &self,
+        _store: &mut (),
+        offset: u64,
+        size: u64,
+    ) -> Result<(), MemoryAccessError> {
+        let memory_size = self.size();
+
+        if offset
+            .checked_add(size)
+            .map_or(true, |end| end > memory_size)
+        {
+            return Err(MemoryAccessError::OutOfBounds {
+                offset,
+                size,
+                memory_size,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn address_type(&self) -> AddressType {
+        self.lock().address_type
+    }
+}
@@ -15,8 +15,33 @@
  */
 
 mod errors;
+mod in_memory;
 
 pub use errors::MemoryAccessError;
+pub use in_memory::InMemory;
+pub use in_memory::InMemoryLimits;
+pub use in_memory::InMemoryStore;
+pub use in_memory::InMemoryView;
+
+use std::convert::TryFrom;
+
+/// The type of addresses of a memory, 64-bit addresses are used by memory64 modules.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressType {
+    #[default]
+    I32,
+    I64,
+}
+
+impl AddressType {
+    /// Returns the size in bytes of a pointer into a memory with this address type.
+    pub fn pointer_size(&self) -> u64 {
+        match self {
+            Self::I32 => 4,
+            Self::I64 => 8,
+        }
+    }
+}
 
 pub trait Store: Send {
     type ActualStore<'c>: Send;
@@ -26,7 +51,7 @@ pub trait MemoryReadable<Store: self::Store> {
     /// This function will panic if the `offset` is out of bounds.
     /// It is caller's responsibility to check if the offset is in bounds
     /// using `MemoryView::check_bounds` function
-    fn read_byte(&self, store: &mut <Store as self::Store>::ActualStore<'_>, offset: u32) -> u8;
+    fn read_byte(&self, store: &mut <Store as self::Store>::ActualStore<'_>, offset: u64) -> u8;
 
     /// This function will panic if `[offset..offset + COUNT]` is out of bounds.
     /// It is caller's responsibility to check if the offset is in bounds
@@ -34,7 +59,7 @@ pub trait MemoryReadable<Store: self::Store> {
     fn read_array<const COUNT: usize>(
         &self,
         store: &mut <Store as self::Store>::ActualStore<'_>,
-        offset: u32,
+        offset: u64,
     ) -> [u8; COUNT];
 
     /// This function will panic if `[offset..offset + size]` is out of bounds.
@@ -43,9 +68,46 @@ pub trait MemoryReadable<Store: self::Store> {
     fn read_vec(
         &self,
         store: &mut <Store as self::Store>::ActualStore<'_>,
-        offset: u32,
-        size: u32,
+        offset: u64,
+        size: u64,
     ) -> Vec<u8>;
+
+    /// Reads `buffer.len()` bytes into `buffer`.
+    ///
+    /// This function will panic if `[offset..offset + buffer.len()]` is out of bounds.
+    /// It is caller's responsibility to check if the offset is in bounds
+    /// using `MemoryView::check_bounds` function.
+    ///
+    /// The default implementation reads the bytes with `read_vec`, implementations
+    /// should override it to copy the bytes without an intermediate allocation.
+    fn read_into(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        buffer: &mut [u8],
+    ) {
+        let bytes = self.read_vec(store, offset, buffer.len() as u64);
+        buffer.copy_from_slice(&bytes);
+    }
+
+    /// Passes `[offset..offset + size]` to `f` without copying and returns the result of `f`,
+    /// or returns `None` without calling `f` if the memory can't lend its bytes.
+    /// `f` must not access the memory, the memory may be locked while `f` runs.
+    ///
+    /// This function will panic if `[offset..offset + size]` is out of bounds.
+    /// It is caller's responsibility to check if the offset is in bounds
+    /// using `MemoryView::check_bounds` function.
+    ///
+    /// The default implementation never lends the bytes.
+    fn with_slice<R>(
+        &self,
+        _store: &mut <Store as self::Store>::ActualStore<'_>,
+        _offset: u64,
+        _size: u64,
+        _f: impl FnOnce(&[u8]) -> R,
+    ) -> Option<R> {
+        None
+    }
 }
 
 pub trait MemoryWritable<Store: self::Store> {
@@ -55,7 +117,7 @@ pub trait MemoryWritable<Store: self::Store> {
     fn write_byte(
         &self,
         store: &mut <Store as self::Store>::ActualStore<'_>,
-        offset: u32,
+        offset: u64,
         value: u8,
     );
 
@@ -65,7 +127,7 @@ pub trait MemoryWritable<Store: self::Store> {
     fn write_bytes(
         &self,
         store: &mut <Store as self::Store>::ActualStore<'_>,
-        offset: u32,
+        offset: u64,
         bytes: &[u8],
     );
 }
@@ -74,13 +136,125 @@ pub trait MemoryView<Store: self::Store>:
     Send + MemoryWritable<Store> + MemoryReadable<Store>
 {
     /// For optimization purposes, user must check bounds first, then try read-write to memory
-    /// `MemoryWritable` and `MemoryReadable` functions will panic in case of out of bounds access`
+    /// `MemoryWritable` and `MemoryReadable` functions will panic in case of out of bounds access`,
+    /// `try_*` functions check bounds themselves.
+    ///
+    /// `offset + size` may overflow `u64`, such ranges must be reported as out of bounds.
     fn check_bounds(
         &self,
         store: &mut <Store as self::Store>::ActualStore<'_>,
-        offset: u32,
-        size: u32,
+        offset: u64,
+        size: u64,
     ) -> Result<(), MemoryAccessError>;
+
+    /// Returns the type of addresses of the memory, pointers to it stored in the memory
+    /// and passed on the stack are of this size.
+    fn address_type(&self) -> AddressType {
+        AddressType::I32
+    }
+
+    /// Reads a byte, returns an error instead of panicking if `offset` is out of bounds.
+    fn try_read_byte(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+    ) -> Result<u8, MemoryAccessError> {
+        self.check_bounds(store, offset, 1)?;
+        Ok(self.read_byte(store, offset))
+    }
+
+    /// Reads `COUNT` bytes, returns an error instead of panicking
+    /// if `[offset..offset + COUNT]` is out of bounds.
+    fn try_read_array<const COUNT: usize>(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+    ) -> Result<[u8; COUNT], MemoryAccessError> {
+        self.check_bounds(store, offset, access_size(COUNT))?;
+        Ok(self.read_array(store, offset))
+    }
+
+    /// Reads `size` bytes, returns an error instead of panicking
+    /// if `[offset..offset + size]` is out of bounds.
+    fn try_read_vec(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        size: u64,
+    ) -> Result<Vec<u8>, MemoryAccessError> {
+        self.check_bounds(store, offset, size)?;
+        Ok(self.read_vec(store, offset, size))
+    }
+
+    /// Reads `buffer.len()` bytes into `buffer`, returns an error instead of panicking
+    /// if `[offset..offset + buffer.len()]` is out of bounds.
+    fn try_read_into(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        buffer: &mut [u8],
+    ) -> Result<(), MemoryAccessError> {
+        self.check_bounds(store, offset, access_size(buffer.len()))?;
+        self.read_into(store, offset, buffer);
+        Ok(())
+    }
+
+    /// Passes `[offset..offset + size]` to `f` and returns the result of `f`, the bytes
+    /// are borrowed with `with_slice` if the memory lends them and are read with `read_vec`
+    /// otherwise. Returns an error if `[offset..offset + size]` is out of bounds.
+    ///
+    /// Use `try_read_vec` to get the bytes as an owned `Vec`, it avoids copying them twice.
+    fn try_read_with<R>(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        size: u64,
+        f: impl FnOnce(&[u8]) -> R,
+    ) -> Result<R, MemoryAccessError> {
+        self.check_bounds(store, offset, size)?;
+
+        // `f` is taken back if `with_slice` doesn't call it
+        let mut f = Some(f);
+        let borrowed = self.with_slice(store, offset, size, |bytes| f.take().map(|f| f(bytes)));
+        if let Some(Some(result)) = borrowed {
+            return Ok(result);
+        }
+
+        let buffer = self.read_vec(store, offset, size);
+        // `with_slice` calls `f` only when it returns `Some`, so `f` is still here
+        let f = f.expect("with_slice returned None after calling f");
+        Ok(f(&buffer))
+    }
+
+    /// Writes a byte, returns an error instead of panicking if `offset` is out of bounds.
+    fn try_write_byte(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        value: u8,
+    ) -> Result<(), MemoryAccessError> {
+        self.check_bounds(store, offset, 1)?;
+        self.write_byte(store, offset, value);
+        Ok(())
+    }
+
+    /// Writes `bytes`, returns an error instead of panicking
+    /// if `[offset..offset + bytes.len()]` is out of bounds.
+    fn try_write_bytes(
+        &self,
+        store: &mut <Store as self::Store>::ActualStore<'_>,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<(), MemoryAccessError> {
+        self.check_bounds(store, offset, access_size(bytes.len()))?;
+        self.write_bytes(store, offset, bytes);
+        Ok(())
+    }
+}
+
+/// Sizes that don't fit into `u64` are saturated, so they never pass the bounds check.
+fn access_size(size: usize) -> u64 {
+    u64::try_from(size).unwrap_or(u64::MAX)
 }
 
 pub trait Memory<View, Store: self::Store>: Send
@@ -89,3 +263,159 @@ where
 {
     fn view(&self) -> View;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> InMemoryView {
+        InMemory::from(vec![1, 2, 3, 4, 5, 6, 7, 8]).view()
+    }
+
+    fn assert_out_of_bounds<T: std::fmt::Debug>(
+        result: Result<T, MemoryAccessError>,
+        expected_offset: u64,
+        expected_size: u64,
+    ) {
+        match result {
+            Err(MemoryAccessError::OutOfBounds {
+                offset,
+                size,
+                memory_size,
+            }) => {
+                assert_eq!(offset, expected_offset);
+                assert_eq!(size, expected_size);
+                assert_eq!(memory_size, 8);
+            }
+            result => panic!("expected an out-of-bounds error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_try_read_out_of_bounds() {
+        let view = view();
+
+        assert_out_of_bounds(view.try_read_byte(&mut (), 8), 8, 1);
+        assert_out_of_bounds(view.try_read_array::<4>(&mut (), 6), 6, 4);
+        assert_out_of_bounds(view.try_read_vec(&mut (), 9, 0), 9, 0);
+        assert_out_of_bounds(view.try_read_into(&mut (), 5, &mut [0; 4]), 5, 4);
+        assert_out_of_bounds(view.try_read_with(&mut (), 7, 2, |_| ()), 7, 2);
+    }
+
+    #[test]
+    fn test_try_write_out_of_bounds() {
+        let view = view();
+
+        assert_out_of_bounds(view.try_write_byte(&mut (), 8, 0), 8, 1);
+        assert_out_of_bounds(view.try_write_bytes(&mut (), 6, &[0; 3]), 6, 3);
+
+        // nothing is written on failure
+        assert_eq!(view.read_vec(&mut (), 0, 8), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_try_access_ending_at_size() {
+        let view = view();
+
+        assert_eq!(view.try_read_byte(&mut (), 7).unwrap(), 8);
+        assert_eq!(view.try_read_array::<4>(&mut (), 4).unwrap(), [5, 6, 7, 8]);
+        assert_eq!(view.try_read_vec(&mut (), 6, 2).unwrap(), vec![7, 8]);
+        assert_eq!(view.try_read_vec(&mut (), 8, 0).unwrap(), Vec::<u8>::new());
+
+        let mut buffer = [0; 3];
+        view.try_read_into(&mut (), 5, &mut buffer).unwrap();
+        assert_eq!(buffer, [6, 7, 8]);
+
+        let sum = view.try_read_with(&mut (), 0, 8, |bytes| bytes.iter().sum::<u8>());
+        assert_eq!(sum.unwrap(), 36);
+
+        view.try_write_bytes(&mut (), 5, &[0, 0, 0]).unwrap();
+        view.try_write_byte(&mut (), 7, 9).unwrap();
+        assert_eq!(view.read_vec(&mut (), 0, 8), vec![1, 2, 3, 4, 5, 0, 0, 9]);
+    }
+
+    /// A view that doesn't lend its bytes and reads them only with `read_vec`.
+    struct CopyingView(InMemoryView);
+
+    impl MemoryReadable<InMemoryStore> for CopyingView {
+        fn read_byte(&self, store: &mut (), offset: u64) -> u8 {
+            self.0.read_byte(store, offset)
+        }
+
+        fn read_array<const COUNT: usize>(&self, store: &mut (), offset: u64) -> [u8; COUNT] {
+            self.0.read_array(store, offset)
+        }
+
+        fn read_vec(&self, store: &mut (), offset: u64, size: u64) -> Vec<u8> {
+            self.0.read_vec(store, offset, size)
+        }
+    }
+
+    impl MemoryWritable<InMemoryStore> for CopyingView {
+        fn write_byte(&self, store: &mut (), offset: u64, value: u8) {
+            self.0.write_byte(store, offset, value)
+        }
+
+        fn write_bytes(&self, store: &mut (), offset: u64, bytes: &[u8]) {
+            self.0.write_bytes(store, offset, bytes)
+        }
+    }
+
+    impl MemoryView<InMemoryStore> for CopyingView {
+        fn check_bounds(
+            &self,
+            store: &mut (),
+            offset: u64,
+            size: u64,
+        ) -> Result<(), MemoryAccessError> {
+            self.0.check_bounds(store, offset, size)
+        }
+    }
+
+    #[test]
+    fn test_try_read_from_view_without_slices() {
+        let view = CopyingView(view());
+
+        assert!(view.with_slice(&mut (), 0, 8, |_| ()).is_none());
+
+        let sum = view.try_read_with(&mut (), 2, 3, |bytes| bytes.iter().sum::<u8>());
+        assert_eq!(sum.unwrap(), 12);
+        assert_eq!(view.try_read_with(&mut (), 8, 0, <[u8]>::len).unwrap(), 0);
+        assert_out_of_bounds(view.try_read_with(&mut (), 7, 2, |_| ()), 7, 2);
+
+        let mut buffer = [0; 3];
+        view.try_read_into(&mut (), 5, &mut buffer).unwrap();
+        assert_eq!(buffer, [6, 7, 8]);
+        assert_out_of_bounds(view.try_read_into(&mut (), 6, &mut buffer), 6, 3);
+    }
+
+    #[test]
+    fn test_panic_while_lending_bytes() {
+        let view = view();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            view.with_slice(&mut (), 0, 8, |_| panic!("panic while the bytes are lent"))
+        }));
+        assert!(result.is_err());
+
+        assert_eq!(view.try_read_byte(&mut (), 0).unwrap(), 1);
+        view.try_write_byte(&mut (), 0, 9).unwrap();
+        assert_eq!(view.read_vec(&mut (), 0, 2), vec![9, 2]);
+    }
+
+    #[test]
+    fn test_try_access_overflowing_u64() {
+        let view = view();
+
+        assert_out_of_bounds(view.try_read_byte(&mut (), u64::MAX), u64::MAX, 1);
+        assert_out_of_bounds(view.try_read_array::<2>(&mut (), u64::MAX), u64::MAX, 2);
+        assert_out_of_bounds(view.try_read_vec(&mut (), 1, u64::MAX), 1, u64::MAX);
+        assert_out_of_bounds(view.try_read_vec(&mut (), u64::MAX, 1), u64::MAX, 1);
+        assert_out_of_bounds(
+            view.try_read_with(&mut (), 2, u64::MAX - 1, |_| ()),
+            2,
+            u64::MAX - 1,
+        );
+        assert_out_of_bounds(view.try_write_bytes(&mut (), u64::MAX, &[0]), u64::MAX, 1);
+    }
+}
@@ -0,0 +1,433 @@
+//! Canonical equality, hashing and ordering of WIT values.
+
+use crate::IValue;
+
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+/// A WIT value with total equality, hashing and ordering, e.g. to be used
+/// in `HashSet`s or as a key of memoized calls.
+///
+/// Floats are compared by their bits after all NaNs are canonicalized, so
+/// any NaN equals to any other NaN, but `-0.0` and `0.0` are different values.
+/// `IValue::ByteArray` equals to `IValue::Array` of `IValue::U8` with the same bytes.
+/// Maps are compared as lists of entries, i.e. the order of entries matters.
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalIValue(pub IValue);
+
+impl CanonicalIValue {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> IValue {
+        self.0
+    }
+}
+
+impl From<IValue> for CanonicalIValue {
+    fn from(value: IValue) -> Self {
+        Self(value)
+    }
+}
+
+impl From<CanonicalIValue> for IValue {
+    fn from(value: CanonicalIValue) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq for CanonicalIValue {
+    fn eq(&self, other: &Self) -> bool {
+        cmp_values(&self.0, &other.0) == Ordering::Equal
+    }
+}
+
+impl Eq for CanonicalIValue {}
+
+impl PartialOrd for CanonicalIValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CanonicalIValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_values(&self.0, &other.0)
+    }
+}
+
+impl Hash for CanonicalIValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state)
+    }
+}
+
+/// Position of a value kind in the total order, byte arrays are ordered as arrays.
+fn rank(value: &IValue) -> u8 {
+    match value {
+        IValue::Boolean(_) => 0,
+        IValue::S8(_) => 1,
+        IValue::S16(_) => 2,
+        IValue::S32(_) => 3,
+        IValue::S64(_) => 4,
+        IValue::U8(_) => 5,
+        IValue::U16(_) => 6,
+        IValue::U32(_) => 7,
+        IValue::U64(_) => 8,
+        IValue::F32(_) => 9,
+        IValue::F64(_) => 10,
+        IValue::String(_) => 11,
+        IValue::ByteArray(_) | IValue::Array(_) => 12,
+        IValue::I32(_) => 13,
+        IValue::I64(_) => 14,
+        IValue::Record(_) => 15,
+        IValue::Optional(_) => 16,
+        IValue::Variant(..) => 17,
+        IValue::Result(_) => 18,
+        IValue::Map(_) => 19,
+        IValue::Flags(_) => 20,
+        IValue::Enum(_) => 21,
+        IValue::Char(_) => 22,
+        IValue::U128(_) => 23,
+        IValue::S128(_) => 24,
+        IValue::Handle(_) => 25,
+    }
+}
+
+/// Maps a float to an integer with the same order, NaNs are canonicalized
+/// and placed after positive infinity.
+fn float_key(value: f64) -> i64 {
+    let value = if value.is_nan() { f64::NAN } else { value };
+    let bits = value.to_bits() as i64;
+
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+pub(crate) fn cmp_values(lhs: &IValue, rhs: &IValue) -> Ordering {
+    match (lhs, rhs) {
+        (IValue::Boolean(lhs), IValue::Boolean(rhs)) => lhs.cmp(rhs),
+        (IValue::S8(lhs), IValue::S8(rhs)) => lhs.cmp(rhs),
+        (IValue::S16(lhs), IValue::S16(rhs)) => lhs.cmp(rhs),
+        (IValue::S32(lhs), IValue::S32(rhs)) => lhs.cmp(rhs),
+        (IValue::S64(lhs), IValue::S64(rhs)) => lhs.cmp(rhs),
+        (IValue::U8(lhs), IValue::U8(rhs)) => lhs.cmp(rhs),
+        (IValue::U16(lhs), IValue::U16(rhs)) => lhs.cmp(rhs),
+        (IValue::U32(lhs), IValue::U32(rhs)) => lhs.cmp(rhs),
+        (IValue::U64(lhs), IValue::U64(rhs)) => lhs.cmp(rhs),
+        (IValue::F32(lhs), IValue::F32(rhs)) => float_key(*lhs as f64).cmp(&float_key(*rhs as f64)),
+        (IValue::F64(lhs), IValue::F64(rhs)) => float_key(*lhs).cmp(&float_key(*rhs)),
+        (IValue::String(lhs), IValue::String(rhs)) => lhs.cmp(rhs),
+        (IValue::ByteArray(lhs), IValue::ByteArray(rhs)) => lhs.cmp(rhs),
+        (IValue::ByteArray(lhs), IValue::Array(rhs)) => cmp_bytes_with_values(lhs, rhs),
+        (IValue::Array(lhs), IValue::ByteArray(rhs)) => cmp_bytes_with_values(rhs, lhs).reverse(),
+        (IValue::Array(lhs), IValue::Array(rhs)) => cmp_slices(lhs, rhs),
+        (IValue::I32(lhs), IValue::I32(rhs)) => lhs.cmp(rhs),
+        (IValue::I64(lhs), IValue::I64(rhs)) => lhs.cmp(rhs),
+        (IValue::Record(lhs), IValue::Record(rhs)) => cmp_slices(lhs, rhs),
+        (IValue::Optional(lhs), IValue::Optional(rhs)) => match (lhs, rhs) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(lhs), Some(rhs)) => cmp_values(lhs, rhs),
+        },
+        (IValue::Variant(lhs_case, lhs), IValue::Variant(rhs_case, rhs)) => {
+            lhs_case.cmp(rhs_case).then_with(|| match (lhs, rhs) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(lhs), Some(rhs)) => cmp_values(lhs, rhs),
+            })
+        }
+        (IValue::Result(lhs), IValue::Result(rhs)) => match (lhs, rhs) {
+            (Ok(lhs), Ok(rhs)) | (Err(lhs), Err(rhs)) => cmp_values(lhs, rhs),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+        },
+        (IValue::Map(lhs), IValue::Map(rhs)) => {
+            for ((lhs_key, lhs_value), (rhs_key, rhs_value)) in lhs.iter().zip(rhs) {
+                let ordering =
+                    cmp_values(lhs_key, rhs_key).then_with(|| cmp_values(lhs_value, rhs_value));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+
+            lhs.len().cmp(&rhs.len())
+        }
+        (IValue::Flags(lhs), IValue::Flags(rhs)) => lhs.cmp(rhs),
+        (IValue::Enum(lhs), IValue::Enum(rhs)) => lhs.cmp(rhs),
+        (IValue::Char(lhs), IValue::Char(rhs)) => lhs.cmp(rhs),
+        (IValue::U128(lhs), IValue::U128(rhs)) => lhs.cmp(rhs),
+        (IValue::S128(lhs), IValue::S128(rhs)) => lhs.cmp(rhs),
+        (IValue::Handle(lhs), IValue::Handle(rhs)) => lhs.cmp(rhs),
+        _ => rank(lhs).cmp(&rank(rhs)),
+    }
+}
+
+fn cmp_slices(lhs: &[IValue], rhs: &[IValue]) -> Ordering {
+    for (lhs, rhs) in lhs.iter().zip(rhs) {
+        let ordering = cmp_values(lhs, rhs);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    lhs.len().cmp(&rhs.len())
+}
+
+/// Compares a byte array with an array as if it were an array of `IValue::U8`.
+fn cmp_bytes_with_values(bytes: &[u8], values: &[IValue]) -> Ordering {
+    for (byte, value) in bytes.iter().zip(values) {
+        let ordering = cmp_values(&IValue::U8(*byte), value);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    bytes.len().cmp(&values.len())
+}
+
+fn hash_value<H: Hasher>(value: &IValue, state: &mut H) {
+    rank(value).hash(state);
+
+    match value {
+        IValue::Boolean(value) => value.hash(state),
+        IValue::S8(value) => value.hash(state),
+        IValue::S16(value) => value.hash(state),
+        IValue::S32(value) => value.hash(state),
+        IValue::S64(value) => value.hash(state),
+        IValue::U8(value) => value.hash(state),
+        IValue::U16(value) => value.hash(state),
+        IValue::U32(value) => value.hash(state),
+        IValue::U64(value) => value.hash(state),
+        IValue::F32(value) => float_key(*value as f64).hash(state),
+        IValue::F64(value) => float_key(*value).hash(state),
+        IValue::String(value) => value.hash(state),
+        IValue::ByteArray(bytes) => {
+            bytes.len().hash(state);
+            for byte in bytes {
+                hash_value(&IValue::U8(*byte), state);
+            }
+        }
+        IValue::Array(values) => {
+            values.len().hash(state);
+            for value in values {
+                hash_value(value, state);
+            }
+        }
+        IValue::I32(value) => value.hash(state),
+        IValue::I64(value) => value.hash(state),
+        IValue::Record(fields) => {
+            fields.len().hash(state);
+            for field in fields.iter() {
+                hash_value(field, state);
+            }
+        }
+        IValue::Optional(value) => hash_option(value.as_deref(), state),
+        IValue::Variant(case, payload) => {
+            case.hash(state);
+            hash_option(payload.as_deref(), state);
+        }
+        IValue::Result(result) => match result {
+            Ok(value) => {
+                0u8.hash(state);
+                hash_value(value, state);
+            }
+            Err(value) => {
+                1u8.hash(state);
+                hash_value(value, state);
+            }
+        },
+        IValue::Map(entries) => {
+            entries.len().hash(state);
+            for (key, value) in entries {
+                hash_value(key, state);
+                hash_value(value, state);
+            }
+        }
+        IValue::Flags(value) => value.hash(state),
+        IValue::Enum(value) => value.hash(state),
+        IValue::Char(value) => value.hash(state),
+        IValue::U128(value) => value.hash(state),
+        IValue::S128(value) => value.hash(state),
+        IValue::Handle(value) => value.hash(state),
+    }
+}
+
+fn hash_option<H: Hasher>(value: Option<&IValue>, state: &mut H) {
+    match value {
+        None => 0u8.hash(state),
+        Some(value) => {
+            1u8.hash(state);
+            hash_value(value, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ne_vec::NEVec;
+
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash_of(value: IValue) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        CanonicalIValue(value).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn canonical_cmp(lhs: IValue, rhs: IValue) -> Ordering {
+        CanonicalIValue(lhs).cmp(&CanonicalIValue(rhs))
+    }
+
+    /// A value of every kind, some kinds twice.
+    fn values() -> Vec<IValue> {
+        vec![
+            IValue::Boolean(true),
+            IValue::S8(-1),
+            IValue::S16(-1),
+            IValue::S32(-1),
+            IValue::S64(-1),
+            IValue::U8(1),
+            IValue::U16(1),
+            IValue::U32(1),
+            IValue::U64(1),
+            IValue::F32(f32::NAN),
+            IValue::F32(-0.0),
+            IValue::F64(0.0),
+            IValue::F64(f64::NEG_INFINITY),
+            IValue::String(String::from("a")),
+            IValue::ByteArray(vec![1, 2]),
+            IValue::Array(vec![IValue::U8(1), IValue::U8(3)]),
+            IValue::Array(vec![IValue::String(String::from("a"))]),
+            IValue::I32(1),
+            IValue::I64(1),
+            IValue::Record(NEVec::new(vec![IValue::U8(1)]).unwrap()),
+            IValue::Optional(None),
+            IValue::Optional(Some(Box::new(IValue::U8(1)))),
+            IValue::Variant(0, None),
+            IValue::Variant(0, Some(Box::new(IValue::U8(1)))),
+            IValue::Result(Ok(Box::new(IValue::U8(1)))),
+            IValue::Result(Err(Box::new(IValue::U8(1)))),
+            IValue::Map(vec![(IValue::U8(1), IValue::U8(2))]),
+            IValue::Flags(1),
+            IValue::Enum(1),
+            IValue::Char('a'),
+            IValue::U128(1),
+            IValue::S128(-1),
+            IValue::Handle(1),
+        ]
+    }
+
+    #[test]
+    fn test_nan_equals_nan() {
+        let quiet_nan = f64::from_bits(0x7ff8_0000_0000_0001);
+
+        assert_eq!(
+            CanonicalIValue(IValue::F64(f64::NAN)),
+            CanonicalIValue(IValue::F64(-quiet_nan))
+        );
+        assert_eq!(
+            CanonicalIValue(IValue::F32(f32::NAN)),
+            CanonicalIValue(IValue::F32(-f32::NAN))
+        );
+        assert_eq!(
+            hash_of(IValue::F64(f64::NAN)),
+            hash_of(IValue::F64(-quiet_nan))
+        );
+        assert_eq!(
+            canonical_cmp(IValue::F64(f64::INFINITY), IValue::F64(f64::NAN)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_negative_zero_is_less_than_zero() {
+        assert_ne!(
+            CanonicalIValue(IValue::F64(-0.0)),
+            CanonicalIValue(IValue::F64(0.0))
+        );
+        assert_eq!(
+            canonical_cmp(IValue::F64(-0.0), IValue::F64(0.0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            canonical_cmp(IValue::F32(-0.0), IValue::F32(0.0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            canonical_cmp(IValue::F64(-1.0), IValue::F64(-0.0)),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_byte_array_equals_array_of_u8() {
+        let bytes = IValue::ByteArray(vec![1, 2, 3]);
+        let array = IValue::Array(vec![IValue::U8(1), IValue::U8(2), IValue::U8(3)]);
+
+        assert_eq!(
+            CanonicalIValue(bytes.clone()),
+            CanonicalIValue(array.clone())
+        );
+        assert_eq!(hash_of(bytes.clone()), hash_of(array));
+
+        let shorter = IValue::Array(vec![IValue::U8(1), IValue::U8(2)]);
+        assert_eq!(
+            canonical_cmp(shorter.clone(), bytes.clone()),
+            Ordering::Less
+        );
+        assert_eq!(canonical_cmp(bytes, shorter), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_ord_is_consistent() {
+        let values = values();
+
+        for lhs in &values {
+            for rhs in &values {
+                let ordering = canonical_cmp(lhs.clone(), rhs.clone());
+
+                assert_eq!(
+                    ordering,
+                    canonical_cmp(rhs.clone(), lhs.clone()).reverse(),
+                    "{:?} and {:?} are not antisymmetric",
+                    lhs,
+                    rhs
+                );
+                assert_eq!(
+                    ordering == Ordering::Equal,
+                    CanonicalIValue(lhs.clone()) == CanonicalIValue(rhs.clone())
+                );
+                if ordering == Ordering::Equal {
+                    assert_eq!(hash_of(lhs.clone()), hash_of(rhs.clone()));
+                }
+
+                for third in &values {
+                    if ordering == Ordering::Less
+                        && canonical_cmp(rhs.clone(), third.clone()) == Ordering::Less
+                    {
+                        assert_eq!(
+                            canonical_cmp(lhs.clone(), third.clone()),
+                            Ordering::Less,
+                            "{:?} < {:?} < {:?} is not transitive",
+                            lhs,
+                            rhs,
+                            third
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_variants_are_ordered_by_rank() {
+        let mut values = values();
+        values.sort_by(|lhs, rhs| canonical_cmp(lhs.clone(), rhs.clone()));
+
+        for pair in values.windows(2) {
+            assert!(rank(&pair[0]) <= rank(&pair[1]));
+        }
+    }
+}
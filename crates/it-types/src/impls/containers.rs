@@ -0,0 +1,338 @@
+//! Conversions between `IValue` and Rust containers of convertible values.
+
+use crate::ne_vec::NEVec;
+use crate::IRecord;
+use crate::IRecordFieldType;
+use crate::IRecordType;
+use crate::IType;
+use crate::IValue;
+use crate::InterfaceType;
+use crate::PathSegment;
+use crate::RecordRegistry;
+use crate::WasmValueNativeCastError;
+
+use std::convert::TryFrom;
+
+/// A Rust type convertible to and from `IValue`, including containers
+/// of convertible values like `Vec<Vec<String>>` or `Option<Box<T>>`.
+///
+/// `From` and `TryFrom` are implemented along with this trait where the coherence
+/// rules allow it, e.g. `TryFrom<IValue>` can't be implemented for any `Option<T>`,
+/// because `Option<IValue>` already implements it.
+///
+/// Elements of `Vec`s and arrays are converted one by one into an `IValue::Array`,
+/// except `u8` that are gathered into an `IValue::ByteArray`.
+pub trait IValueConvertible: InterfaceType + Sized {
+    /// Converts the value into an `IValue`.
+    fn into_ivalue(self) -> IValue;
+
+    /// Converts an `IValue` into a value, the error points to the element that can't be converted.
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError>;
+
+    /// Converts elements into an array value.
+    fn into_array_value(elements: Vec<Self>) -> IValue {
+        IValue::Array(elements.into_iter().map(Self::into_ivalue).collect())
+    }
+
+    /// Converts an array value into elements.
+    fn try_from_array_value(value: IValue) -> Result<Vec<Self>, WasmValueNativeCastError> {
+        match value {
+            IValue::Array(values) => try_from_elements(values),
+            _ => Err(WasmValueNativeCastError::new(
+                value,
+                Vec::<Self>::interface_type(&mut RecordRegistry::new()),
+            )),
+        }
+    }
+}
+
+fn try_from_elements<T>(values: Vec<IValue>) -> Result<Vec<T>, WasmValueNativeCastError>
+where
+    T: IValueConvertible,
+{
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(position, value)| {
+            T::try_from_ivalue(value).map_err(|e| e.at(PathSegment::Index(position)))
+        })
+        .collect()
+}
+
+macro_rules! convertible {
+    ($($native_type:ty),+) => {
+        $(
+            impl IValueConvertible for $native_type {
+                fn into_ivalue(self) -> IValue {
+                    self.into()
+                }
+
+                fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+                    Self::try_from(value)
+                }
+            }
+
+            impl TryFrom<IValue> for Option<$native_type> {
+                type Error = WasmValueNativeCastError;
+
+                fn try_from(value: IValue) -> Result<Self, Self::Error> {
+                    Self::try_from_ivalue(value)
+                }
+            }
+        )+
+    };
+}
+
+convertible!(bool, i8, i16, i32, i64, u16, u32, u64, u128, i128, f32, f64, char, String);
+
+impl IValueConvertible for u8 {
+    fn into_ivalue(self) -> IValue {
+        self.into()
+    }
+
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+        Self::try_from(value)
+    }
+
+    fn into_array_value(elements: Vec<Self>) -> IValue {
+        IValue::ByteArray(elements)
+    }
+
+    fn try_from_array_value(value: IValue) -> Result<Vec<Self>, WasmValueNativeCastError> {
+        match value {
+            IValue::ByteArray(bytes) => Ok(bytes),
+            IValue::Array(values) => try_from_elements(values),
+            _ => Err(WasmValueNativeCastError::new(value, IType::ByteArray)),
+        }
+    }
+}
+
+impl TryFrom<IValue> for Option<u8> {
+    type Error = WasmValueNativeCastError;
+
+    fn try_from(value: IValue) -> Result<Self, Self::Error> {
+        Self::try_from_ivalue(value)
+    }
+}
+
+/// Arrays of `u8` are byte arrays.
+fn array_type<T: InterfaceType>(registry: &mut RecordRegistry) -> IType {
+    match T::interface_type(registry) {
+        IType::U8 => IType::ByteArray,
+        element_type => IType::Array(Box::new(element_type)),
+    }
+}
+
+impl<T: InterfaceType> InterfaceType for Vec<T> {
+    fn interface_type(registry: &mut RecordRegistry) -> IType {
+        array_type::<T>(registry)
+    }
+}
+
+impl<T: IValueConvertible> IValueConvertible for Vec<T> {
+    fn into_ivalue(self) -> IValue {
+        T::into_array_value(self)
+    }
+
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+        T::try_from_array_value(value)
+    }
+}
+
+impl<T: IValueConvertible> From<Vec<T>> for IValue {
+    fn from(elements: Vec<T>) -> Self {
+        elements.into_ivalue()
+    }
+}
+
+impl<T: IValueConvertible> TryFrom<IValue> for Vec<T> {
+    type Error = WasmValueNativeCastError;
+
+    fn try_from(value: IValue) -> Result<Self, Self::Error> {
+        Self::try_from_ivalue(value)
+    }
+}
+
+impl<T: IValueConvertible> TryFrom<IValue> for Option<Vec<T>> {
+    type Error = WasmValueNativeCastError;
+
+    fn try_from(value: IValue) -> Result<Self, Self::Error> {
+        Self::try_from_ivalue(value)
+    }
+}
+
+impl<T: InterfaceType, const N: usize> InterfaceType for [T; N] {
+    fn interface_type(registry: &mut RecordRegistry) -> IType {
+        array_type::<T>(registry)
+    }
+}
+
+impl<T: IValueConvertible, const N: usize> IValueConvertible for [T; N] {
+    fn into_ivalue(self) -> IValue {
+        T::into_array_value(Vec::from(self))
+    }
+
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+        let elements = T::try_from_array_value(value)?;
+
+        <[T; N]>::try_from(elements).map_err(|elements| {
+            WasmValueNativeCastError::new(
+                T::into_array_value(elements),
+                Self::interface_type(&mut RecordRegistry::new()),
+            )
+        })
+    }
+}
+
+impl<T: IValueConvertible, const N: usize> From<[T; N]> for IValue {
+    fn from(elements: [T; N]) -> Self {
+        elements.into_ivalue()
+    }
+}
+
+impl<T: IValueConvertible, const N: usize> TryFrom<IValue> for [T; N] {
+    type Error = WasmValueNativeCastError;
+
+    fn try_from(value: IValue) -> Result<Self, Self::Error> {
+        Self::try_from_ivalue(value)
+    }
+}
+
+impl<T: InterfaceType> InterfaceType for Option<T> {
+    fn interface_type(registry: &mut RecordRegistry) -> IType {
+        IType::Optional(Box::new(T::interface_type(registry)))
+    }
+}
+
+impl<T: IValueConvertible> IValueConvertible for Option<T> {
+    fn into_ivalue(self) -> IValue {
+        IValue::Optional(self.map(|value| Box::new(value.into_ivalue())))
+    }
+
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+        match value {
+            IValue::Optional(None) => Ok(None),
+            IValue::Optional(Some(value)) => T::try_from_ivalue(*value).map(Some),
+            _ => Err(WasmValueNativeCastError::new(
+                value,
+                Self::interface_type(&mut RecordRegistry::new()),
+            )),
+        }
+    }
+}
+
+impl<T: IValueConvertible> From<Option<T>> for IValue {
+    fn from(value: Option<T>) -> Self {
+        value.into_ivalue()
+    }
+}
+
+impl<T: InterfaceType + ?Sized> InterfaceType for Box<T> {
+    fn interface_type(registry: &mut RecordRegistry) -> IType {
+        T::interface_type(registry)
+    }
+}
+
+impl<T: IValueConvertible> IValueConvertible for Box<T> {
+    fn into_ivalue(self) -> IValue {
+        (*self).into_ivalue()
+    }
+
+    fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+        T::try_from_ivalue(value).map(Box::new)
+    }
+}
+
+impl<T: IValueConvertible> From<Box<T>> for IValue {
+    fn from(value: Box<T>) -> Self {
+        value.into_ivalue()
+    }
+}
+
+impl InterfaceType for &str {
+    fn interface_type(_registry: &mut RecordRegistry) -> IType {
+        IType::String
+    }
+}
+
+impl From<&str> for IValue {
+    fn from(value: &str) -> Self {
+        IValue::String(value.to_string())
+    }
+}
+
+/// Tuples are records with anonymous fields.
+macro_rules! tuple {
+    ($count:literal => $($position:tt $element:ident),+) => {
+        impl<$($element: InterfaceType + 'static),+> InterfaceType for ($($element,)+) {
+            fn interface_type(registry: &mut RecordRegistry) -> IType {
+                IType::Record(registry.register::<Self>())
+            }
+        }
+
+        impl<$($element: InterfaceType + 'static),+> IRecord for ($($element,)+) {
+            fn record_type(registry: &mut RecordRegistry) -> IRecordType {
+                let fields = vec![$(IRecordFieldType {
+                    name: None,
+                    ty: $element::interface_type(registry),
+                }),+];
+
+                IRecordType {
+                    name: concat!("Tuple", $count).to_string(),
+                    // this `unwrap` is safe because a tuple has at least one element
+                    fields: NEVec::new(fields).unwrap(),
+                }
+            }
+        }
+
+        impl<$($element: IValueConvertible + 'static),+> IValueConvertible for ($($element,)+) {
+            fn into_ivalue(self) -> IValue {
+                let fields = vec![$(self.$position.into_ivalue()),+];
+
+                // this `unwrap` is safe because a tuple has at least one element
+                IValue::Record(NEVec::new(fields).unwrap())
+            }
+
+            fn try_from_ivalue(value: IValue) -> Result<Self, WasmValueNativeCastError> {
+                let fields = match value {
+                    IValue::Record(fields) if fields.len() == $count => fields.into_vec(),
+                    _ => {
+                        return Err(WasmValueNativeCastError::new(
+                            value,
+                            Self::interface_type(&mut RecordRegistry::new()),
+                        ))
+                    }
+                };
+                let mut fields = fields.into_iter();
+
+                // these `unwrap`s are safe because the number of fields is checked above
+                Ok(($($element::try_from_ivalue(fields.next().unwrap()).map_err(|e| {
+                    e.at(PathSegment::Field(stringify!($position).to_string()))
+                })?,)+))
+            }
+        }
+
+        impl<$($element: IValueConvertible + 'static),+> From<($($element,)+)> for IValue {
+            fn from(value: ($($element,)+)) -> Self {
+                value.into_ivalue()
+            }
+        }
+
+        impl<$($element: IValueConvertible + 'static),+> TryFrom<IValue> for ($($element,)+) {
+            type Error = WasmValueNativeCastError;
+
+            fn try_from(value: IValue) -> Result<Self, Self::Error> {
+                Self::try_from_ivalue(value)
+            }
+        }
+    };
+}
+
+tuple!(1 => 0 A);
+tuple!(2 => 0 A, 1 B);
+tuple!(3 => 0 A, 1 B, 2 C);
+tuple!(4 => 0 A, 1 B, 2 C, 3 D);
+tuple!(5 => 0 A, 1 B, 2 C, 3 D, 4 E);
+tuple!(6 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+tuple!(7 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+tuple!(8 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
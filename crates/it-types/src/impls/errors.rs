@@ -1,5 +1,7 @@
 use crate::IType;
 use crate::IValue;
+use crate::PathSegment;
+use crate::ValuePath;
 
 use std::error::Error;
 use std::fmt::Display;
@@ -18,12 +20,43 @@ pub struct WasmValueNativeCastError {
     /// `IType` is used to represent the native type by
     /// associativity.
     pub to: IType,
+
+    /// The path from the converted value to the one that can't be cast,
+    /// e.g. `points[2].x` when a `Vec` of records is converted.
+    pub path: ValuePath,
+}
+
+impl WasmValueNativeCastError {
+    /// Creates an error of casting `from` itself.
+    pub fn new(from: IValue, to: IType) -> Self {
+        Self {
+            from,
+            to,
+            path: ValuePath::default(),
+        }
+    }
+
+    /// Prepends a path segment, the path is built up while the error
+    /// bubbles up from an element to its container.
+    pub fn at(mut self, segment: PathSegment) -> Self {
+        self.path.0.insert(0, segment);
+        self
+    }
 }
 
 impl Error for WasmValueNativeCastError {}
 
 impl Display for WasmValueNativeCastError {
     fn fmt(&self, formatter: &mut Formatter) -> Result {
-        write!(formatter, "{:?}", self)
+        if !self.path.is_empty() {
+            write!(formatter, "{}: ", self.path)?;
+        }
+
+        write!(
+            formatter,
+            "{:?} can't be converted to a value of type '{}'",
+            self.from,
+            (&self.to).to_string()
+        )
     }
 }
@@ -1,6 +1,8 @@
+mod containers;
 mod errors;
 mod types;
 mod values;
 
+pub use containers::IValueConvertible;
 pub use errors::WasmValueNativeCastError;
 pub use values::NativeType;
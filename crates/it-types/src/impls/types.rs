@@ -1,7 +1,11 @@
 use crate::ne_vec::NEVec;
+use crate::IEnumType;
+use crate::IFlagsType;
 use crate::IRecordFieldType;
 use crate::IRecordType;
 use crate::IType;
+use crate::IVariantCaseType;
+use crate::IVariantType;
 
 use it_to_bytes::ToBytes;
 use wast::parser::Parse;
@@ -41,17 +45,54 @@ where
                 0x0e_u8.to_bytes(writer)?;
                 record_id.to_bytes(writer)
             }
+            IType::Optional(ty) => {
+                0x0f_u8.to_bytes(writer)?;
+                ty.to_bytes(writer)
+            }
+            IType::Variant(variant_id) => {
+                0x10_u8.to_bytes(writer)?;
+                variant_id.to_bytes(writer)
+            }
+            IType::Result(ok_ty, err_ty) => {
+                0x11_u8.to_bytes(writer)?;
+                ok_ty.to_bytes(writer)?;
+                err_ty.to_bytes(writer)
+            }
+            IType::Map(key_ty, value_ty) => {
+                0x12_u8.to_bytes(writer)?;
+                key_ty.to_bytes(writer)?;
+                value_ty.to_bytes(writer)
+            }
+            IType::Flags(flags_id) => {
+                0x13_u8.to_bytes(writer)?;
+                flags_id.to_bytes(writer)
+            }
+            IType::Enum(enum_id) => {
+                0x14_u8.to_bytes(writer)?;
+                enum_id.to_bytes(writer)
+            }
+            IType::Char => 0x15_u8.to_bytes(writer),
+            IType::U128 => 0x16_u8.to_bytes(writer),
+            IType::S128 => 0x17_u8.to_bytes(writer),
+            IType::Handle(resource_id) => {
+                0x18_u8.to_bytes(writer)?;
+                resource_id.to_bytes(writer)
+            }
+            IType::Alias(alias_id) => {
+                0x19_u8.to_bytes(writer)?;
+                alias_id.to_bytes(writer)
+            }
         }
     }
 }
 
-/// Encode a `RecordType` into bytes.
+/// Encode a `RecordFieldType` into bytes, an anonymous field is encoded with an empty name.
 impl<W> ToBytes<W> for IRecordFieldType
 where
     W: Write,
 {
     fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
-        self.name.as_str().to_bytes(writer)?;
+        self.name.as_deref().unwrap_or_default().to_bytes(writer)?;
         self.ty.to_bytes(writer)
     }
 }
@@ -67,6 +108,57 @@ where
     }
 }
 
+/// Encode a `VariantCaseType` into bytes.
+impl<W> ToBytes<W> for IVariantCaseType
+where
+    W: Write,
+{
+    fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
+        self.name.as_str().to_bytes(writer)?;
+
+        match &self.ty {
+            Some(ty) => {
+                0x01_u8.to_bytes(writer)?;
+                ty.to_bytes(writer)
+            }
+            None => 0x00_u8.to_bytes(writer),
+        }
+    }
+}
+
+/// Encode a `VariantType` into bytes.
+impl<W> ToBytes<W> for IVariantType
+where
+    W: Write,
+{
+    fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
+        self.name.as_str().to_bytes(writer)?;
+        self.cases.to_bytes(writer)
+    }
+}
+
+/// Encode a `FlagsType` into bytes.
+impl<W> ToBytes<W> for IFlagsType
+where
+    W: Write,
+{
+    fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
+        self.name.as_str().to_bytes(writer)?;
+        self.flags.to_bytes(writer)
+    }
+}
+
+/// Encode an `EnumType` into bytes.
+impl<W> ToBytes<W> for IEnumType
+where
+    W: Write,
+{
+    fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
+        self.name.as_str().to_bytes(writer)?;
+        self.members.to_bytes(writer)
+    }
+}
+
 mod keyword {
     pub use wast::{
         custom_keyword,
@@ -76,6 +168,13 @@ mod keyword {
     // New keywords.
     custom_keyword!(record);
     custom_keyword!(field);
+    custom_keyword!(variant);
+    custom_keyword!(case);
+    custom_keyword!(flags);
+    custom_keyword!(handle);
+    custom_keyword!(alias);
+    custom_keyword!(flag);
+    custom_keyword!(enum_ = "enum");
 
     // New types.
     custom_keyword!(boolean);
@@ -87,8 +186,13 @@ mod keyword {
     custom_keyword!(u16);
     custom_keyword!(u32);
     custom_keyword!(u64);
+    custom_keyword!(u128);
+    custom_keyword!(s128);
+    custom_keyword!(char);
     custom_keyword!(string);
     custom_keyword!(array);
+    custom_keyword!(option);
+    custom_keyword!(map);
 }
 
 impl Parse<'_> for IType {
@@ -130,6 +234,18 @@ impl Parse<'_> for IType {
             parser.parse::<keyword::u64>()?;
 
             Ok(IType::U64)
+        } else if lookahead.peek::<keyword::u128>() {
+            parser.parse::<keyword::u128>()?;
+
+            Ok(IType::U128)
+        } else if lookahead.peek::<keyword::s128>() {
+            parser.parse::<keyword::s128>()?;
+
+            Ok(IType::S128)
+        } else if lookahead.peek::<keyword::char>() {
+            parser.parse::<keyword::char>()?;
+
+            Ok(IType::Char)
         } else if lookahead.peek::<keyword::f32>() {
             parser.parse::<keyword::f32>()?;
 
@@ -160,6 +276,46 @@ impl Parse<'_> for IType {
             parser.parse::<keyword::record>()?;
 
             Ok(IType::Record(parser.parse()?))
+        } else if lookahead.peek::<keyword::option>() {
+            parser.parse::<keyword::option>()?;
+
+            let optional_type = parser.parens(|p| p.parse())?;
+
+            Ok(IType::Optional(Box::new(optional_type)))
+        } else if lookahead.peek::<keyword::variant>() {
+            parser.parse::<keyword::variant>()?;
+
+            Ok(IType::Variant(parser.parse()?))
+        } else if lookahead.peek::<keyword::result>() {
+            parser.parse::<keyword::result>()?;
+
+            let ok_type = parser.parens(|p| p.parse())?;
+            let err_type = parser.parens(|p| p.parse())?;
+
+            Ok(IType::Result(Box::new(ok_type), Box::new(err_type)))
+        } else if lookahead.peek::<keyword::map>() {
+            parser.parse::<keyword::map>()?;
+
+            let key_type = parser.parens(|p| p.parse())?;
+            let value_type = parser.parens(|p| p.parse())?;
+
+            Ok(IType::Map(Box::new(key_type), Box::new(value_type)))
+        } else if lookahead.peek::<keyword::alias>() {
+            parser.parse::<keyword::alias>()?;
+
+            Ok(IType::Alias(parser.parse()?))
+        } else if lookahead.peek::<keyword::handle>() {
+            parser.parse::<keyword::handle>()?;
+
+            Ok(IType::Handle(parser.parse()?))
+        } else if lookahead.peek::<keyword::flags>() {
+            parser.parse::<keyword::flags>()?;
+
+            Ok(IType::Flags(parser.parse()?))
+        } else if lookahead.peek::<keyword::enum_>() {
+            parser.parse::<keyword::enum_>()?;
+
+            Ok(IType::Enum(parser.parse()?))
         } else {
             Err(lookahead.error())
         }
@@ -184,26 +340,26 @@ impl Parse<'_> for IRecordType {
             while !parser.is_empty() {
                 parser.parse::<keyword::field>()?;
 
-                let name = parser
-                    .step(|cursor| {
-                        cursor
-                            .id()
-                            .ok_or_else(|| cursor.error("expecting argument identifier"))
-                    })?
-                    .to_string();
-
-                if !name.ends_with(':') {
-                    parser.step(|cursor| {
-                        if let Some((":", rest)) = cursor.reserved() {
-                            return Ok(("", rest));
-                        }
-                        Err(cursor.error("expected : between an argument and a type"))
-                    })?;
+                // an anonymous field consists only of its type
+                let name = parser.step(|cursor| match cursor.id() {
+                    Some((name, rest)) => Ok((Some(name), rest)),
+                    None => Ok((None, cursor)),
+                })?;
+
+                if let Some(name) = name {
+                    if !name.ends_with(':') {
+                        parser.step(|cursor| {
+                            if let Some((":", rest)) = cursor.reserved() {
+                                return Ok(("", rest));
+                            }
+                            Err(cursor.error("expected : between an argument and a type"))
+                        })?;
+                    }
                 }
 
                 let ty = parser.parse()?;
                 let record_field_type = IRecordFieldType {
-                    name: name.trim_end_matches(':').to_string(),
+                    name: name.map(|name| name.trim_end_matches(':').to_string()),
                     ty,
                 };
 
@@ -220,3 +376,149 @@ impl Parse<'_> for IRecordType {
         Ok(record_type)
     }
 }
+
+impl Parse<'_> for IVariantType {
+    fn parse(parser: Parser<'_>) -> Result<Self, ParseError> {
+        parser.parse::<keyword::variant>()?;
+
+        let variant_name = parser
+            .step(|cursor| {
+                cursor
+                    .id()
+                    .ok_or_else(|| cursor.error("expecting argument identifier"))
+            })?
+            .to_string();
+
+        let mut cases = vec![];
+
+        parser.parens(|parser| {
+            while !parser.is_empty() {
+                parser.parse::<keyword::case>()?;
+
+                let name = parser
+                    .step(|cursor| {
+                        cursor
+                            .id()
+                            .ok_or_else(|| cursor.error("expecting argument identifier"))
+                    })?
+                    .to_string();
+
+                // a case without payload is followed either by the next case or by the end
+                let has_payload = name.ends_with(':')
+                    || parser.step(|cursor| match cursor.reserved() {
+                        Some((":", rest)) => Ok((true, rest)),
+                        _ => Ok((false, cursor)),
+                    })?;
+
+                let ty = if has_payload {
+                    Some(parser.parse()?)
+                } else {
+                    None
+                };
+
+                let name = name.trim_end_matches(':').to_string();
+                if cases
+                    .iter()
+                    .any(|case: &IVariantCaseType| case.name == name)
+                {
+                    return Err(parser.error(format!("duplicate name `{}`", name)));
+                }
+
+                let variant_case_type = IVariantCaseType { name, ty };
+
+                cases.push(variant_case_type);
+            }
+            Ok(())
+        })?;
+
+        let variant_type = IVariantType {
+            name: variant_name,
+            cases: NEVec::new(cases)
+                .map_err(|_| parser.error("variant must have at least one case"))?,
+        };
+
+        Ok(variant_type)
+    }
+}
+
+/// Parses a list of `(<keyword> $name)*` items used by flags and enums.
+fn parse_names<'a, K: Parse<'a>>(parser: Parser<'a>) -> Result<Vec<String>, ParseError> {
+    let mut names = vec![];
+
+    parser.parens(|parser| {
+        while !parser.is_empty() {
+            parser.parse::<K>()?;
+
+            let name = parser
+                .step(|cursor| {
+                    cursor
+                        .id()
+                        .ok_or_else(|| cursor.error("expecting argument identifier"))
+                })?
+                .to_string();
+
+            if names.contains(&name) {
+                return Err(parser.error(format!("duplicate name `{}`", name)));
+            }
+
+            names.push(name);
+        }
+        Ok(())
+    })?;
+
+    Ok(names)
+}
+
+impl Parse<'_> for IFlagsType {
+    fn parse(parser: Parser<'_>) -> Result<Self, ParseError> {
+        parser.parse::<keyword::flags>()?;
+
+        let flags_name = parser
+            .step(|cursor| {
+                cursor
+                    .id()
+                    .ok_or_else(|| cursor.error("expecting argument identifier"))
+            })?
+            .to_string();
+
+        let flags = parse_names::<keyword::flag>(parser)?;
+        if flags.len() > IFlagsType::MAX_FLAGS_COUNT {
+            return Err(parser.error(format!(
+                "flags can't have more than {} members",
+                IFlagsType::MAX_FLAGS_COUNT
+            )));
+        }
+
+        let flags_type = IFlagsType {
+            name: flags_name,
+            flags: NEVec::new(flags)
+                .map_err(|_| parser.error("flags must have at least one flag"))?,
+        };
+
+        Ok(flags_type)
+    }
+}
+
+impl Parse<'_> for IEnumType {
+    fn parse(parser: Parser<'_>) -> Result<Self, ParseError> {
+        parser.parse::<keyword::enum_>()?;
+
+        let enum_name = parser
+            .step(|cursor| {
+                cursor
+                    .id()
+                    .ok_or_else(|| cursor.error("expecting argument identifier"))
+            })?
+            .to_string();
+
+        let members = parse_names::<keyword::case>(parser)?;
+
+        let enum_type = IEnumType {
+            name: enum_name,
+            members: NEVec::new(members)
+                .map_err(|_| parser.error("enum must have at least one member"))?,
+        };
+
+        Ok(enum_type)
+    }
+}
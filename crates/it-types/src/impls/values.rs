@@ -1,8 +1,15 @@
+use crate::canonical::cmp_values;
 use crate::IType;
 use crate::IValue;
+use crate::InterfaceType;
+use crate::RecordRegistry;
 use crate::WasmValueNativeCastError;
 
+use it_to_bytes::ToBytes;
+
 use std::convert::TryFrom;
+use std::io;
+use std::io::Write;
 
 /// Represents a native type supported by WIT.
 pub trait NativeType {
@@ -16,6 +23,12 @@ macro_rules! native {
             const INTERFACE_TYPE: IType = IType::$variant;
         }
 
+        impl InterfaceType for $native_type {
+            fn interface_type(_registry: &mut RecordRegistry) -> IType {
+                Self::INTERFACE_TYPE
+            }
+        }
+
         impl From<$native_type> for IValue {
             fn from(n: $native_type) -> Self {
                 IValue::$variant(n)
@@ -28,16 +41,17 @@ macro_rules! native {
             fn try_from(w: IValue) -> Result<Self, Self::Error> {
                 match w {
                     IValue::$variant(n) => Ok(n),
-                    _ => Err(WasmValueNativeCastError {
-                        from: w,
-                        to: <$native_type>::INTERFACE_TYPE,
-                    }),
+                    _ => Err(WasmValueNativeCastError::new(
+                        w,
+                        <$native_type>::INTERFACE_TYPE,
+                    )),
                 }
             }
         }
     };
 }
 
+native!(bool, Boolean);
 native!(i8, S8);
 native!(i16, S16);
 native!(i32, I32);
@@ -46,49 +60,183 @@ native!(u8, U8);
 native!(u16, U16);
 native!(u32, U32);
 native!(u64, U64);
+native!(u128, U128);
+native!(i128, S128);
 native!(f32, F32);
 native!(f64, F64);
+native!(char, Char);
 native!(String, String);
 
+// conversions of `Vec<u8>` are implemented along with other `Vec`s, see `IValueConvertible`
 impl NativeType for Vec<u8> {
     const INTERFACE_TYPE: IType = IType::ByteArray;
 }
 
-impl From<Vec<u8>> for IValue {
-    fn from(n: Vec<u8>) -> Self {
-        IValue::ByteArray(n)
+/// Encode an `IValue` into bytes.
+///
+/// A value is encoded as the type code of its kind (the same one as in
+/// `IType` encoding) followed by a payload: numbers are little-endian,
+/// lengths and variant cases are LEB128, strings are UTF-8. The encoding is
+/// deterministic, values equal as `CanonicalIValue`s are encoded identically:
+/// NaNs are canonicalized and arrays of `U8` are encoded as byte arrays.
+///
+/// Decoder is `decoders::binary::ivalue`.
+impl<W> ToBytes<W> for IValue
+where
+    W: Write,
+{
+    fn to_bytes(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            IValue::Boolean(value) => {
+                0x0b_u8.to_bytes(writer)?;
+                (*value as u8).to_bytes(writer)
+            }
+            IValue::S8(value) => fixed(writer, 0x00, &value.to_le_bytes()),
+            IValue::S16(value) => fixed(writer, 0x01, &value.to_le_bytes()),
+            IValue::S32(value) => fixed(writer, 0x02, &value.to_le_bytes()),
+            IValue::S64(value) => fixed(writer, 0x03, &value.to_le_bytes()),
+            IValue::U8(value) => fixed(writer, 0x04, &value.to_le_bytes()),
+            IValue::U16(value) => fixed(writer, 0x05, &value.to_le_bytes()),
+            IValue::U32(value) => fixed(writer, 0x06, &value.to_le_bytes()),
+            IValue::U64(value) => fixed(writer, 0x07, &value.to_le_bytes()),
+            IValue::F32(value) => {
+                let value = if value.is_nan() { f32::NAN } else { *value };
+                fixed(writer, 0x08, &value.to_le_bytes())
+            }
+            IValue::F64(value) => {
+                let value = if value.is_nan() { f64::NAN } else { *value };
+                fixed(writer, 0x09, &value.to_le_bytes())
+            }
+            IValue::String(value) => {
+                0x0a_u8.to_bytes(writer)?;
+                (value.len() as u64).to_bytes(writer)?;
+                writer.write_all(value.as_bytes())
+            }
+            IValue::ByteArray(bytes) => byte_array_to_bytes(writer, bytes),
+            IValue::Array(values) => {
+                let bytes = values
+                    .iter()
+                    .map(|value| match value {
+                        IValue::U8(byte) => Some(*byte),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+
+                if let Some(bytes) = bytes {
+                    return byte_array_to_bytes(writer, &bytes);
+                }
+
+                0x36_u8.to_bytes(writer)?;
+                values_to_bytes(writer, values)
+            }
+            IValue::I32(value) => fixed(writer, 0x0c, &value.to_le_bytes()),
+            IValue::I64(value) => fixed(writer, 0x0d, &value.to_le_bytes()),
+            IValue::Record(fields) => {
+                0x0e_u8.to_bytes(writer)?;
+                values_to_bytes(writer, fields)
+            }
+            IValue::Optional(value) => {
+                0x0f_u8.to_bytes(writer)?;
+                option_to_bytes(writer, value.as_deref())
+            }
+            IValue::Variant(case, payload) => {
+                0x10_u8.to_bytes(writer)?;
+                (*case as u64).to_bytes(writer)?;
+                option_to_bytes(writer, payload.as_deref())
+            }
+            IValue::Result(result) => {
+                0x11_u8.to_bytes(writer)?;
+                match result {
+                    Ok(value) => {
+                        0x00_u8.to_bytes(writer)?;
+                        value.to_bytes(writer)
+                    }
+                    Err(value) => {
+                        0x01_u8.to_bytes(writer)?;
+                        value.to_bytes(writer)
+                    }
+                }
+            }
+            IValue::Map(entries) => {
+                0x12_u8.to_bytes(writer)?;
+                (entries.len() as u64).to_bytes(writer)?;
+
+                // entries are written in the canonical order of keys, so equal maps have equal bytes
+                let mut entries = entries.iter().collect::<Vec<_>>();
+                entries.sort_by(|(lhs_key, lhs_value), (rhs_key, rhs_value)| {
+                    cmp_values(lhs_key, rhs_key).then_with(|| cmp_values(lhs_value, rhs_value))
+                });
+                for (key, value) in entries {
+                    key.to_bytes(writer)?;
+                    value.to_bytes(writer)?;
+                }
+
+                Ok(())
+            }
+            IValue::Flags(bits) => fixed(writer, 0x13, &bits.to_le_bytes()),
+            IValue::Enum(member) => fixed(writer, 0x14, &member.to_le_bytes()),
+            IValue::Char(value) => fixed(writer, 0x15, &(*value as u32).to_le_bytes()),
+            IValue::U128(value) => fixed(writer, 0x16, &value.to_le_bytes()),
+            IValue::S128(value) => fixed(writer, 0x17, &value.to_le_bytes()),
+            IValue::Handle(handle) => fixed(writer, 0x18, &handle.to_le_bytes()),
+        }
+    }
+}
+
+fn fixed<W: Write>(writer: &mut W, code: u8, bytes: &[u8]) -> io::Result<()> {
+    code.to_bytes(writer)?;
+    writer.write_all(bytes)
+}
+
+fn byte_array_to_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    0x3c_u8.to_bytes(writer)?;
+    (bytes.len() as u64).to_bytes(writer)?;
+    writer.write_all(bytes)
+}
+
+fn values_to_bytes<W: Write>(writer: &mut W, values: &[IValue]) -> io::Result<()> {
+    (values.len() as u64).to_bytes(writer)?;
+    for value in values {
+        value.to_bytes(writer)?;
     }
+
+    Ok(())
 }
 
-impl TryFrom<IValue> for Vec<u8> {
-    type Error = WasmValueNativeCastError;
-
-    fn try_from(w: IValue) -> Result<Self, Self::Error> {
-        match w {
-            IValue::ByteArray(n) => Ok(n),
-            IValue::Array(ivalues) => try_to_byte_array(ivalues),
-            _ => Err(WasmValueNativeCastError {
-                from: w,
-                to: Vec::<u8>::INTERFACE_TYPE,
-            }),
+fn option_to_bytes<W: Write>(writer: &mut W, value: Option<&IValue>) -> io::Result<()> {
+    match value {
+        None => 0x00_u8.to_bytes(writer),
+        Some(value) => {
+            0x01_u8.to_bytes(writer)?;
+            value.to_bytes(writer)
         }
     }
 }
 
-fn try_to_byte_array(ivalues: Vec<IValue>) -> Result<Vec<u8>, WasmValueNativeCastError> {
-    let mut result = Vec::with_capacity(ivalues.len());
-
-    for value in &ivalues {
-        match value {
-            IValue::U8(byte) => result.push(*byte),
-            _ => {
-                return Err(WasmValueNativeCastError {
-                    from: IValue::Array(ivalues),
-                    to: Vec::<u8>::INTERFACE_TYPE,
-                })
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: &IValue) -> Vec<u8> {
+        let mut bytes = vec![];
+        value.to_bytes(&mut bytes).unwrap();
+
+        bytes
     }
 
-    Ok(result)
+    #[test]
+    fn test_map_bytes_do_not_depend_on_insertion_order() {
+        let entries = vec![
+            (IValue::String("b".to_string()), IValue::U32(2)),
+            (IValue::String("a".to_string()), IValue::U32(1)),
+            (IValue::S32(-1), IValue::Boolean(true)),
+        ];
+        let mut reversed = entries.clone();
+        reversed.reverse();
+
+        assert_eq!(
+            encode(&IValue::Map(entries)),
+            encode(&IValue::Map(reversed))
+        );
+    }
 }
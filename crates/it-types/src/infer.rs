@@ -0,0 +1,420 @@
+//! Inference of the interface type of a value, e.g. a value received from a dynamic source.
+
+use crate::IRecordType;
+use crate::IType;
+use crate::IValue;
+use crate::PathSegment;
+use crate::ValuePath;
+
+use std::error::Error;
+use std::fmt;
+
+/// Describes why the type of a value can't be inferred.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferTypeErrorKind {
+    /// The value doesn't contain enough information, e.g. it's an empty array, `None`
+    /// or a variant, enum, flags or handle value that refers to a type definition.
+    NotEnoughInformation,
+
+    /// No record type has the shape of the record value.
+    NoMatchingRecord,
+
+    /// Several record types have the shape of the record value.
+    AmbiguousRecord {
+        /// Ids of the matching record types.
+        record_type_ids: Vec<u64>,
+    },
+
+    /// An element of an array or of a map doesn't have the type of the others.
+    ElementTypeMismatch {
+        /// The type inferred for the other elements.
+        expected_type: IType,
+    },
+}
+
+/// An inference error along with the path to the offending value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferTypeError {
+    /// The path from the value to the one whose type can't be inferred.
+    pub path: ValuePath,
+
+    /// The error kind.
+    pub kind: InferTypeErrorKind,
+}
+
+impl fmt::Display for InferTypeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughInformation => write!(f, "the value doesn't determine its type"),
+            Self::NoMatchingRecord => write!(f, "no record type matches the record value"),
+            Self::AmbiguousRecord { record_type_ids } => write!(
+                f,
+                "record types with ids {:?} match the record value",
+                record_type_ids
+            ),
+            Self::ElementTypeMismatch { expected_type } => write!(
+                f,
+                "the element doesn't have type '{}' of the other elements",
+                expected_type.to_string()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for InferTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+impl Error for InferTypeError {}
+
+impl IValue {
+    /// Infers the type of the value, record values are matched by shape against
+    /// `records`, e.g. `RecordRegistry::records()`.
+    ///
+    /// The type of an array or of a map is the type of its first element whose type
+    /// can be inferred, other elements must be of this type. Types of record fields
+    /// are taken from the matched record type, so they may be `None` or empty arrays.
+    ///
+    /// ```
+    /// use fluence_it_types::IType;
+    /// use fluence_it_types::IValue;
+    ///
+    /// let value = IValue::Array(vec![
+    ///     IValue::Optional(None),
+    ///     IValue::Optional(Some(Box::new(IValue::String("a".to_string())))),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     value.infer_type(std::iter::empty()),
+    ///     Ok(IType::Array(Box::new(IType::Optional(Box::new(IType::String)))))
+    /// );
+    /// ```
+    pub fn infer_type<'r>(
+        &self,
+        records: impl IntoIterator<Item = (u64, &'r IRecordType)>,
+    ) -> Result<IType, InferTypeError> {
+        let inferrer = Inferrer {
+            records: records.into_iter().collect(),
+        };
+
+        inferrer.infer(self).map_err(|(mut path, kind)| {
+            path.reverse();
+            InferTypeError {
+                path: ValuePath(path),
+                kind,
+            }
+        })
+    }
+}
+
+/// A path is built in the reverse order while an error bubbles up.
+type InferResult<T> = Result<T, (Vec<PathSegment>, InferTypeErrorKind)>;
+
+struct Inferrer<'r> {
+    records: Vec<(u64, &'r IRecordType)>,
+}
+
+impl Inferrer<'_> {
+    fn infer(&self, value: &IValue) -> InferResult<IType> {
+        let ty = match value {
+            IValue::Boolean(_) => IType::Boolean,
+            IValue::S8(_) => IType::S8,
+            IValue::S16(_) => IType::S16,
+            IValue::S32(_) => IType::S32,
+            IValue::S64(_) => IType::S64,
+            IValue::U8(_) => IType::U8,
+            IValue::U16(_) => IType::U16,
+            IValue::U32(_) => IType::U32,
+            IValue::U64(_) => IType::U64,
+            IValue::F32(_) => IType::F32,
+            IValue::F64(_) => IType::F64,
+            IValue::I32(_) => IType::I32,
+            IValue::I64(_) => IType::I64,
+            IValue::Char(_) => IType::Char,
+            IValue::U128(_) => IType::U128,
+            IValue::S128(_) => IType::S128,
+            IValue::String(_) => IType::String,
+            IValue::ByteArray(_) => IType::ByteArray,
+            IValue::Array(values) => {
+                let ty = self.infer_elements(values.iter().map(|value| (None, value)))?;
+                IType::Array(Box::new(ty))
+            }
+            IValue::Optional(Some(value)) => IType::Optional(Box::new(self.infer(value)?)),
+            IValue::Map(entries) => {
+                let keys = entries.iter().map(|(key, _)| (Some("key"), key));
+                let values = entries.iter().map(|(_, value)| (Some("value"), value));
+
+                IType::Map(
+                    Box::new(self.infer_elements(keys)?),
+                    Box::new(self.infer_elements(values)?),
+                )
+            }
+            IValue::Record(fields) => IType::Record(self.match_record(fields)?),
+            IValue::Optional(None)
+            | IValue::Result(_)
+            | IValue::Variant(..)
+            | IValue::Enum(_)
+            | IValue::Flags(_)
+            | IValue::Handle(_) => {
+                return Err((vec![], InferTypeErrorKind::NotEnoughInformation));
+            }
+        };
+
+        Ok(ty)
+    }
+
+    /// Infers the type of elements, `field` is set for keys and values of map entries.
+    fn infer_elements<'v>(
+        &self,
+        elements: impl Iterator<Item = (Option<&'static str>, &'v IValue)> + Clone,
+    ) -> InferResult<IType> {
+        let path = |position: usize, field: Option<&str>| {
+            let mut path = vec![PathSegment::Index(position)];
+            if let Some(field) = field {
+                path.insert(0, PathSegment::Field(field.to_string()));
+            }
+            path
+        };
+
+        let mut first_error = None;
+        let mut inferred = None;
+        for (position, (field, value)) in elements.clone().enumerate() {
+            match self.infer(value) {
+                Ok(ty) => {
+                    inferred = Some(ty);
+                    break;
+                }
+                Err((mut error_path, kind)) if first_error.is_none() => {
+                    error_path.extend(path(position, field));
+                    first_error = Some((error_path, kind));
+                }
+                Err(_) => {}
+            }
+        }
+
+        let ty = match (inferred, first_error) {
+            (Some(ty), _) => ty,
+            (None, Some(error)) => return Err(error),
+            (None, None) => return Err((vec![], InferTypeErrorKind::NotEnoughInformation)),
+        };
+
+        for (position, (field, value)) in elements.enumerate() {
+            if !self.matches(&ty, value) {
+                return Err((
+                    path(position, field),
+                    InferTypeErrorKind::ElementTypeMismatch { expected_type: ty },
+                ));
+            }
+        }
+
+        Ok(ty)
+    }
+
+    fn match_record(&self, fields: &[IValue]) -> InferResult<u64> {
+        let record_type_ids = self
+            .records
+            .iter()
+            .filter(|(_, record_type)| self.matches_record(record_type, fields))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        match record_type_ids.as_slice() {
+            [id] => Ok(*id),
+            [] => Err((vec![], InferTypeErrorKind::NoMatchingRecord)),
+            _ => Err((
+                vec![],
+                InferTypeErrorKind::AmbiguousRecord { record_type_ids },
+            )),
+        }
+    }
+
+    fn matches_record(&self, record_type: &IRecordType, fields: &[IValue]) -> bool {
+        record_type.fields.len() == fields.len()
+            && record_type
+                .fields
+                .iter()
+                .zip(fields)
+                .all(|(field_type, field)| self.matches(&field_type.ty, field))
+    }
+
+    /// Checks the shape of a value, variants, enums, flags and handles are
+    /// matched only by kind, aliases never match because they can't be resolved.
+    fn matches(&self, ty: &IType, value: &IValue) -> bool {
+        match (ty, value) {
+            (IType::Boolean, IValue::Boolean(_))
+            | (IType::S8, IValue::S8(_))
+            | (IType::S16, IValue::S16(_))
+            | (IType::S32, IValue::S32(_))
+            | (IType::S64, IValue::S64(_))
+            | (IType::U8, IValue::U8(_))
+            | (IType::U16, IValue::U16(_))
+            | (IType::U32, IValue::U32(_))
+            | (IType::U64, IValue::U64(_))
+            | (IType::I32, IValue::I32(_))
+            | (IType::I64, IValue::I64(_))
+            | (IType::F32, IValue::F32(_))
+            | (IType::F64, IValue::F64(_))
+            | (IType::Char, IValue::Char(_))
+            | (IType::U128, IValue::U128(_))
+            | (IType::S128, IValue::S128(_))
+            | (IType::String, IValue::String(_))
+            | (IType::ByteArray, IValue::ByteArray(_))
+            | (IType::Optional(_), IValue::Optional(None))
+            | (IType::Variant(_), IValue::Variant(..))
+            | (IType::Enum(_), IValue::Enum(_))
+            | (IType::Flags(_), IValue::Flags(_))
+            | (IType::Handle(_), IValue::Handle(_)) => true,
+            (IType::Array(ty), IValue::ByteArray(_)) => ty.as_ref() == &IType::U8,
+            (IType::Array(ty), IValue::Array(values)) => {
+                values.iter().all(|value| self.matches(ty, value))
+            }
+            (IType::ByteArray, IValue::Array(values)) => {
+                values.iter().all(|value| matches!(value, IValue::U8(_)))
+            }
+            (IType::Optional(ty), IValue::Optional(Some(value))) => self.matches(ty, value),
+            (IType::Result(ok_ty, _), IValue::Result(Ok(value))) => self.matches(ok_ty, value),
+            (IType::Result(_, err_ty), IValue::Result(Err(value))) => self.matches(err_ty, value),
+            (IType::Map(key_ty, value_ty), IValue::Map(entries)) => entries
+                .iter()
+                .all(|(key, value)| self.matches(key_ty, key) && self.matches(value_ty, value)),
+            (IType::Record(record_type_id), IValue::Record(fields)) => self
+                .records
+                .iter()
+                .find(|(id, _)| id == record_type_id)
+                .map_or(false, |(_, record_type)| {
+                    self.matches_record(record_type, fields)
+                }),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ne_vec::NEVec;
+    use crate::IRecordFieldType;
+
+    fn record_type(name: &str, fields: Vec<(Option<&str>, IType)>) -> IRecordType {
+        let fields = fields
+            .into_iter()
+            .map(|(name, ty)| IRecordFieldType {
+                name: name.map(str::to_string),
+                ty,
+            })
+            .collect();
+
+        IRecordType {
+            name: name.to_string(),
+            fields: NEVec::new(fields).unwrap(),
+        }
+    }
+
+    /// Record types as they'd be registered for `Line`, `Point`, `(i32, i32)` and `Tree`.
+    fn records() -> Vec<(u64, IRecordType)> {
+        let array = |ty| IType::Array(Box::new(ty));
+
+        vec![
+            (
+                0,
+                record_type(
+                    "Line",
+                    vec![
+                        (Some("start"), IType::Record(1)),
+                        (Some("end"), IType::Record(1)),
+                        (Some("label"), IType::String),
+                        (Some("visible"), IType::Boolean),
+                        (Some("data"), IType::ByteArray),
+                    ],
+                ),
+            ),
+            (
+                1,
+                record_type("Point", vec![(None, IType::I32), (None, IType::I32)]),
+            ),
+            (
+                2,
+                record_type("Pair", vec![(None, IType::I32), (None, IType::I32)]),
+            ),
+            (
+                3,
+                record_type(
+                    "Tree",
+                    vec![
+                        (Some("labels"), array(array(IType::String))),
+                        (Some("points"), array(IType::Record(2))),
+                        (Some("children"), array(IType::Record(3))),
+                        (Some("parent"), IType::Optional(Box::new(IType::Record(3)))),
+                    ],
+                ),
+            ),
+        ]
+    }
+
+    fn record(fields: Vec<IValue>) -> IValue {
+        IValue::Record(NEVec::new(fields).unwrap())
+    }
+
+    fn point(x: i32, y: i32) -> IValue {
+        record(vec![IValue::I32(x), IValue::I32(y)])
+    }
+
+    #[test]
+    fn test_infer_type() {
+        let records = records();
+        let infer = |value: IValue| value.infer_type(records.iter().map(|(id, ty)| (*id, ty)));
+
+        let leaf = record(vec![
+            IValue::Array(vec![]),
+            IValue::Array(vec![]),
+            IValue::Array(vec![]),
+            IValue::Optional(None),
+        ]);
+        assert_eq!(
+            infer(IValue::Array(vec![leaf])),
+            Ok(IType::Array(Box::new(IType::Record(3))))
+        );
+
+        let line = record(vec![
+            point(1, 2),
+            point(3, 4),
+            IValue::String("diagonal".to_string()),
+            IValue::Boolean(true),
+            IValue::ByteArray(vec![42]),
+        ]);
+        assert_eq!(infer(line), Ok(IType::Record(0)));
+
+        // both `Point` and `Pair` have this shape
+        let error = infer(IValue::Array(vec![point(1, 2)])).unwrap_err();
+        assert_eq!(error.path.to_string(), "[0]");
+        assert_eq!(
+            error.kind,
+            InferTypeErrorKind::AmbiguousRecord {
+                record_type_ids: vec![1, 2]
+            }
+        );
+
+        let error = infer(IValue::Map(vec![
+            (IValue::U8(1), IValue::Optional(None)),
+            (IValue::U8(2), IValue::Optional(None)),
+        ]))
+        .unwrap_err();
+        assert_eq!(error.path.to_string(), "[0].value");
+        assert_eq!(error.kind, InferTypeErrorKind::NotEnoughInformation);
+
+        let error = infer(IValue::Array(vec![IValue::U8(1), IValue::S8(2)])).unwrap_err();
+        assert_eq!(error.path.to_string(), "[1]");
+        assert_eq!(
+            error.kind,
+            InferTypeErrorKind::ElementTypeMismatch {
+                expected_type: IType::U8
+            }
+        );
+    }
+}
@@ -1,20 +1,43 @@
+mod canonical;
 #[cfg(feature = "impls")]
 mod impls;
+mod infer;
 pub mod ne_vec;
+mod path;
+mod registry;
 mod types;
 mod values;
 
 // types
+pub use types::EnumType as IEnumType;
+pub use types::FlagsType as IFlagsType;
 pub use types::IType;
 pub use types::RecordFieldType as IRecordFieldType;
 pub use types::RecordType as IRecordType;
+pub use types::VariantCaseType as IVariantCaseType;
+pub use types::VariantType as IVariantType;
 
+#[cfg(feature = "impls")]
+pub use impls::IValueConvertible;
 #[cfg(feature = "impls")]
 pub use impls::NativeType;
+pub use registry::IRecord;
+pub use registry::InterfaceType;
+pub use registry::RecordRegistry;
+
+#[cfg(feature = "derive")]
+pub use it_derive::IRecord;
 
 // values
+pub use canonical::CanonicalIValue;
 pub use values::IValue;
 
+// paths
+pub use path::PathSegment;
+pub use path::ValuePath;
+
 // errors
 #[cfg(feature = "impls")]
 pub use impls::WasmValueNativeCastError;
+pub use infer::InferTypeError;
+pub use infer::InferTypeErrorKind;
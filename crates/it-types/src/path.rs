@@ -0,0 +1,38 @@
+//! Paths from a value to its inner values, used to point to offending values in errors.
+
+use std::fmt;
+
+/// A step from a value to one of its inner values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A record field, a variant case or a result arm, anonymous fields are named by their position.
+    Field(String),
+
+    /// An element of an array or of a map.
+    Index(usize),
+}
+
+/// A path from the checked value to the offending one, e.g. `user.addresses[2].zip`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValuePath(pub Vec<PathSegment>);
+
+impl ValuePath {
+    /// Returns `true` if the path points to the checked value itself.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (position, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if position == 0 => write!(f, "{}", name)?,
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Interface types of Rust types, records are registered to be referred by ids.
+
+use crate::IRecordType;
+use crate::IType;
+
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// A Rust type that has an interface type.
+///
+/// Unlike [`NativeType`](crate::NativeType), the interface type may refer to records,
+/// so they are registered in `registry` to get their ids.
+pub trait InterfaceType {
+    /// Returns the interface type, registering the records it refers to.
+    fn interface_type(registry: &mut RecordRegistry) -> IType;
+}
+
+/// A Rust type that is represented as a record, usually implemented by `#[derive(IRecord)]`.
+pub trait IRecord: InterfaceType {
+    /// Returns the record type, registering the records its fields refer to.
+    fn record_type(registry: &mut RecordRegistry) -> IRecordType;
+}
+
+/// Assigns ids to record types of Rust types, each type is registered once.
+#[derive(Debug, Default, Clone)]
+pub struct RecordRegistry {
+    ids: HashMap<TypeId, u64>,
+    records: BTreeMap<u64, IRecordType>,
+}
+
+impl RecordRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the record type of `T` and the records it refers to,
+    /// returns the id of `T`'s record type.
+    ///
+    /// Types are told apart by their `TypeId`, so `T` must be `'static`.
+    pub fn register<T: IRecord + ?Sized + 'static>(&mut self) -> u64 {
+        let key = TypeId::of::<T>();
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        // the id is assigned beforehand, so a record can refer to itself
+        let id = self.ids.len() as u64;
+        self.ids.insert(key, id);
+
+        let record_type = T::record_type(self);
+        self.records.insert(id, record_type);
+
+        id
+    }
+
+    /// Returns the record type with the given id.
+    pub fn record_type(&self, id: u64) -> Option<&IRecordType> {
+        self.records.get(&id)
+    }
+
+    /// Returns all registered record types along with their ids.
+    pub fn records(&self) -> impl Iterator<Item = (u64, &IRecordType)> {
+        self.records
+            .iter()
+            .map(|(id, record_type)| (*id, record_type))
+    }
+}
@@ -58,14 +58,46 @@ pub enum IType {
 
     /// A record contains record index from interfaces AST.
     Record(u64),
+
+    /// An optional value of the given type.
+    Optional(Box<IType>),
+
+    /// A variant contains variant index from interfaces AST.
+    Variant(u64),
+
+    /// A result of a fallible operation: the success type and the error type.
+    Result(Box<IType>, Box<IType>),
+
+    /// A map from keys of the first type to values of the second one.
+    Map(Box<IType>, Box<IType>),
+
+    /// A set of flags contains flags index from interfaces AST.
+    Flags(u64),
+
+    /// An enumeration contains enum index from interfaces AST.
+    Enum(u64),
+
+    /// A Unicode scalar value.
+    Char,
+
+    /// A 128-bits unsigned integer.
+    U128,
+
+    /// A 128-bits signed integer.
+    S128,
+
+    /// A handle to a host object of the given resource type.
+    Handle(u64),
+
+    /// An alias contains alias index from interfaces AST.
+    Alias(u64),
 }
 
 /// Represents a record field type.
 #[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct RecordFieldType {
-    // TODO: make name optional to support structures with anonymous fields in Rust
-    /// A field name.
-    pub name: String,
+    /// A field name, anonymous fields (e.g. of tuples and tuple structs) don't have it.
+    pub name: Option<String>,
 
     /// A field type.
     pub ty: IType,
@@ -83,12 +115,63 @@ pub struct RecordType {
     pub fields: NEVec<RecordFieldType>,
 }
 
+/// Represents a variant case type.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct VariantCaseType {
+    /// A case name.
+    pub name: String,
+
+    /// A payload type, cases without payload don't have it.
+    pub ty: Option<IType>,
+}
+
+/// Represents a variant type, i.e. a tagged union of named cases.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct VariantType {
+    /// A variant name.
+    pub name: String,
+
+    /// Cases of the variant, a case index is used as a discriminant.
+    /// A variant must have at least one case, hence the
+    /// [`NEVec`][crate::ne_vec::NEVec].
+    pub cases: NEVec<VariantCaseType>,
+}
+
+/// Represents a flags type, i.e. a set of named boolean flags packed into a bitset.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct FlagsType {
+    /// A flags name.
+    pub name: String,
+
+    /// Names of the flags, a flag index is used as a bit index.
+    /// There must be at least one flag, hence the
+    /// [`NEVec`][crate::ne_vec::NEVec].
+    pub flags: NEVec<String>,
+}
+
+impl FlagsType {
+    /// The maximum number of flags, they are packed into `u64`.
+    pub const MAX_FLAGS_COUNT: usize = 64;
+}
+
+/// Represents an enumeration type, i.e. a set of named members without payload.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct EnumType {
+    /// An enum name.
+    pub name: String,
+
+    /// Names of the members, a member index is used as a discriminant.
+    /// An enum must have at least one member, hence the
+    /// [`NEVec`][crate::ne_vec::NEVec].
+    pub members: NEVec<String>,
+}
+
 impl Default for RecordType {
     fn default() -> Self {
         Self {
             name: String::new(),
             fields: NEVec::new(vec![RecordFieldType {
-                name: String::new(),
+                name: None,
                 ty: IType::S8,
             }])
             .unwrap(),
@@ -118,6 +201,25 @@ impl ToString for &IType {
             IType::I32 => "i32".to_string(),
             IType::I64 => "i64".to_string(),
             IType::Record(record_type_id) => format!("record {}", record_type_id),
+            IType::Optional(ty) => format!("option ({})", ty.as_ref().to_string()),
+            IType::Variant(variant_type_id) => format!("variant {}", variant_type_id),
+            IType::Result(ok_ty, err_ty) => format!(
+                "result ({}) ({})",
+                ok_ty.as_ref().to_string(),
+                err_ty.as_ref().to_string()
+            ),
+            IType::Map(key_ty, value_ty) => format!(
+                "map ({}) ({})",
+                key_ty.as_ref().to_string(),
+                value_ty.as_ref().to_string()
+            ),
+            IType::Flags(flags_type_id) => format!("flags {}", flags_type_id),
+            IType::Enum(enum_type_id) => format!("enum {}", enum_type_id),
+            IType::Char => "char".to_string(),
+            IType::U128 => "u128".to_string(),
+            IType::S128 => "s128".to_string(),
+            IType::Handle(resource_id) => format!("handle {}", resource_id),
+            IType::Alias(alias_type_id) => format!("alias {}", alias_type_id),
         }
     }
 }
@@ -132,11 +234,71 @@ impl ToString for &RecordType {
                 .iter()
                 .fold(String::new(), |mut accumulator, field_type| {
                     accumulator.push(' ');
-                    accumulator.push_str(&format!(
-                        "field ${}: {}\n",
-                        field_type.name,
-                        (&field_type.ty).to_string()
-                    ));
+                    match &field_type.name {
+                        Some(name) => accumulator.push_str(&format!(
+                            "field ${}: {}\n",
+                            name,
+                            (&field_type.ty).to_string()
+                        )),
+                        None => accumulator
+                            .push_str(&format!("field {}\n", (&field_type.ty).to_string())),
+                    }
+                    accumulator
+                }),
+        )
+    }
+}
+
+impl ToString for &VariantType {
+    fn to_string(&self) -> String {
+        format!(
+            "variant ${} (\n{cases})",
+            self.name,
+            cases = self
+                .cases
+                .iter()
+                .fold(String::new(), |mut accumulator, case_type| {
+                    accumulator.push(' ');
+                    match &case_type.ty {
+                        Some(ty) => accumulator.push_str(&format!(
+                            "case ${}: {}\n",
+                            case_type.name,
+                            ty.to_string()
+                        )),
+                        None => accumulator.push_str(&format!("case ${}\n", case_type.name)),
+                    }
+                    accumulator
+                }),
+        )
+    }
+}
+
+impl ToString for &FlagsType {
+    fn to_string(&self) -> String {
+        format!(
+            "flags ${} (\n{flags})",
+            self.name,
+            flags = self
+                .flags
+                .iter()
+                .fold(String::new(), |mut accumulator, flag| {
+                    accumulator.push_str(&format!(" flag ${}\n", flag));
+                    accumulator
+                }),
+        )
+    }
+}
+
+impl ToString for &EnumType {
+    fn to_string(&self) -> String {
+        format!(
+            "enum ${} (\n{members})",
+            self.name,
+            members = self
+                .members
+                .iter()
+                .fold(String::new(), |mut accumulator, member| {
+                    accumulator.push_str(&format!(" case ${}\n", member));
                     accumulator
                 }),
         )
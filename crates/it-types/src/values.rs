@@ -57,6 +57,36 @@ pub enum IValue {
 
     /// A record.
     Record(NEVec<IValue>),
+
+    /// An optional value, `None` stands for the absence of a value.
+    Optional(Option<Box<IValue>>),
+
+    /// A variant value: a case index and a payload if the case has one.
+    Variant(u32, Option<Box<IValue>>),
+
+    /// A result value, either a success value or an error one.
+    Result(Result<Box<IValue>, Box<IValue>>),
+
+    /// A map, i.e. a list of key-value pairs.
+    Map(Vec<(IValue, IValue)>),
+
+    /// A set of flags, the i-th bit is set if the i-th flag is present.
+    Flags(u64),
+
+    /// An enumeration member index.
+    Enum(u32),
+
+    /// A Unicode scalar value.
+    Char(char),
+
+    /// A 128-bits unsigned integer.
+    U128(u128),
+
+    /// A 128-bits signed integer.
+    S128(i128),
+
+    /// An index into the host-side handle table.
+    Handle(u32),
 }
 
 impl Default for IValue {
@@ -1,7 +1,7 @@
 //! Represents the WIT language as a tree. This is the central
 //! representation of the language.
 
-use crate::{interpreter::Instruction, IRecordType, IType};
+use crate::{interpreter::Instruction, IEnumType, IFlagsType, IRecordType, IType, IVariantType};
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -16,6 +16,18 @@ pub enum TypeKind {
 
     /// A record type.
     Record,
+
+    /// A variant type.
+    Variant,
+
+    /// A flags type.
+    Flags,
+
+    /// An enum type.
+    Enum,
+
+    /// A type alias.
+    Alias,
 }
 
 /// Represents the function argument type.
@@ -50,6 +62,40 @@ pub enum Type {
     /// (@interface type (record string i32))
     /// ```
     Record(Arc<IRecordType>),
+
+    /// A variant type, like:
+    ///
+    /// ```wasm,ignore
+    /// (@interface type (variant $v (case $a: string case $b)))
+    /// ```
+    Variant(Arc<IVariantType>),
+
+    /// A flags type, like:
+    ///
+    /// ```wasm,ignore
+    /// (@interface type (flags $permissions (flag $read flag $write)))
+    /// ```
+    Flags(Arc<IFlagsType>),
+
+    /// An enum type, like:
+    ///
+    /// ```wasm,ignore
+    /// (@interface type (enum $status (case $ok case $failed)))
+    /// ```
+    Enum(Arc<IEnumType>),
+
+    /// A type alias, like:
+    ///
+    /// ```wasm,ignore
+    /// (@interface type (alias $PeerId string))
+    /// ```
+    Alias {
+        /// The alias name.
+        name: String,
+
+        /// The aliased type.
+        ty: IType,
+    },
 }
 
 /// Represents an imported function.
@@ -1,8 +1,13 @@
 //! Parse the WIT binary representation into an [AST](crate::ast).
 
+use crate::IEnumType;
+use crate::IFlagsType;
 use crate::IRecordFieldType;
 use crate::IRecordType;
 use crate::IType;
+use crate::IValue;
+use crate::IVariantCaseType;
+use crate::IVariantType;
 use crate::{ast::*, interpreter::Instruction};
 use nom::{
     error::{make_error, ErrorKind, ParseError},
@@ -18,6 +23,10 @@ impl TryFrom<u8> for TypeKind {
         Ok(match code {
             0x00 => Self::Function,
             0x01 => Self::Record,
+            0x02 => Self::Variant,
+            0x03 => Self::Flags,
+            0x04 => Self::Enum,
+            0x05 => Self::Alias,
             _ => return Err("Unknown type kind code."),
         })
     }
@@ -84,6 +93,9 @@ fn record_field<'input, E: ParseError<&'input [u8]>>(
     consume!((input, name) = owned_string(input)?);
     consume!((input, ty) = ty(input)?);
 
+    // an anonymous field is encoded with an empty name
+    let name = if name.is_empty() { None } else { Some(name) };
+
     Ok((input, IRecordFieldType { name, ty }))
 }
 
@@ -136,6 +148,51 @@ fn ty<'input, E: ParseError<&'input [u8]>>(
 
             IType::Record(record_id)
         }
+        0x0f => {
+            consume!((input, optional_value_type) = ty(input)?);
+
+            IType::Optional(Box::new(optional_value_type))
+        }
+        0x10 => {
+            consume!((input, variant_id) = uleb(input)?);
+
+            IType::Variant(variant_id)
+        }
+        0x11 => {
+            consume!((input, ok_type) = ty(input)?);
+            consume!((input, err_type) = ty(input)?);
+
+            IType::Result(Box::new(ok_type), Box::new(err_type))
+        }
+        0x12 => {
+            consume!((input, key_type) = ty(input)?);
+            consume!((input, value_type) = ty(input)?);
+
+            IType::Map(Box::new(key_type), Box::new(value_type))
+        }
+        0x13 => {
+            consume!((input, flags_id) = uleb(input)?);
+
+            IType::Flags(flags_id)
+        }
+        0x14 => {
+            consume!((input, enum_id) = uleb(input)?);
+
+            IType::Enum(enum_id)
+        }
+        0x15 => IType::Char,
+        0x16 => IType::U128,
+        0x17 => IType::S128,
+        0x18 => {
+            consume!((input, resource_id) = uleb(input)?);
+
+            IType::Handle(resource_id)
+        }
+        0x19 => {
+            consume!((input, alias_id) = uleb(input)?);
+
+            IType::Alias(alias_id)
+        }
         _ => return Err(Err::Error(make_error(input, ErrorKind::Alt))),
     };
 
@@ -160,6 +217,76 @@ fn record_type<'input, E: ParseError<&'input [u8]>>(
     ))
 }
 
+fn variant_case<'input, E: ParseError<&'input [u8]>>(
+    mut input: &'input [u8],
+) -> IResult<&'input [u8], IVariantCaseType, E> {
+    if input.is_empty() {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+
+    consume!((input, name) = owned_string(input)?);
+    consume!((input, has_payload) = byte(input)?);
+
+    let ty = match has_payload {
+        0x00 => None,
+        0x01 => {
+            consume!((input, ty) = ty(input)?);
+
+            Some(ty)
+        }
+        _ => return Err(Err::Error(make_error(input, ErrorKind::Alt))),
+    };
+
+    Ok((input, IVariantCaseType { name, ty }))
+}
+
+/// Parse a variant type, it must have at least one case.
+fn variant_type<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], IVariantType, E> {
+    use crate::NEVec;
+
+    let (output, name) = owned_string(input)?;
+    let (output, cases) = list(output, variant_case)?;
+
+    let cases = NEVec::new(cases).map_err(|_| Err::Error(make_error(input, ErrorKind::Verify)))?;
+
+    Ok((output, IVariantType { name, cases }))
+}
+
+/// Parse a flags type, it must have from 1 to 64 flags.
+fn flags_type<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], IFlagsType, E> {
+    use crate::NEVec;
+
+    let (output, name) = owned_string(input)?;
+    let (output, flags) = list(output, owned_string)?;
+
+    if flags.len() > IFlagsType::MAX_FLAGS_COUNT {
+        return Err(Err::Error(make_error(input, ErrorKind::TooLarge)));
+    }
+
+    let flags = NEVec::new(flags).map_err(|_| Err::Error(make_error(input, ErrorKind::Verify)))?;
+
+    Ok((output, IFlagsType { name, flags }))
+}
+
+/// Parse an enum type, it must have at least one member.
+fn enum_type<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], IEnumType, E> {
+    use crate::NEVec;
+
+    let (output, name) = owned_string(input)?;
+    let (output, members) = list(output, owned_string)?;
+
+    let members =
+        NEVec::new(members).map_err(|_| Err::Error(make_error(input, ErrorKind::Verify)))?;
+
+    Ok((output, IEnumType { name, members }))
+}
+
 /// Parse a UTF-8 string into &str.
 fn string<'input, E: ParseError<&'input [u8]>>(
     input: &'input [u8],
@@ -231,6 +358,217 @@ fn list<'input, I, E: ParseError<&'input [u8]>>(
     Ok((input, items))
 }
 
+/// Parse a fixed-size array of bytes, e.g. a little-endian number.
+fn fixed<'input, const N: usize, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], [u8; N], E> {
+    if input.len() < N {
+        return Err(Err::Error(make_error(input, ErrorKind::Eof)));
+    }
+
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&input[..N]);
+
+    Ok((&input[N..], bytes))
+}
+
+/// Parse a length of a sequence, each item of which takes at least
+/// `item_size` bytes, so it can't claim more items than the input has.
+fn length<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+    item_size: usize,
+) -> IResult<&'input [u8], usize, E> {
+    let (output, length) = uleb(input)?;
+
+    match usize::try_from(length) {
+        Ok(length) if length.saturating_mul(item_size) <= output.len() => Ok((output, length)),
+        _ => Err(Err::Error(make_error(input, ErrorKind::TooLarge))),
+    }
+}
+
+/// Parse a presence flag of an optional part of a value.
+fn presence<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], bool, E> {
+    match byte(input)? {
+        (output, 0x00) => Ok((output, false)),
+        (output, 0x01) => Ok((output, true)),
+        _ => Err(Err::Error(make_error(input, ErrorKind::Tag))),
+    }
+}
+
+/// Parse a sequence of values prefixed with its length.
+fn values<'input, E: ParseError<&'input [u8]>>(
+    mut input: &'input [u8],
+    depth_budget: usize,
+) -> IResult<&'input [u8], Vec<IValue>, E> {
+    consume!((input, length) = length(input, 1)?);
+
+    let mut values = Vec::with_capacity(length);
+    for _ in 0..length {
+        consume!((input, value) = nested_ivalue(input, depth_budget)?);
+        values.push(value);
+    }
+
+    Ok((input, values))
+}
+
+/// The maximal nesting depth of a value parsed by [`ivalue`], deeper values
+/// are rejected so that untrusted input can't overflow the stack.
+pub const MAX_VALUE_DEPTH: usize = 128;
+
+/// Parse a value encoded with `IValue::to_bytes`.
+///
+/// ```rust
+/// use wasmer_interface_types_fl::{decoders::binary::ivalue, IValue, ToBytes};
+///
+/// let value = IValue::Optional(Some(Box::new(IValue::String("abc".to_string()))));
+/// let mut bytes = vec![];
+/// value.to_bytes(&mut bytes).unwrap();
+///
+/// assert_eq!(ivalue::<()>(&bytes), Ok((&[][..], value)));
+/// ```
+///
+/// Values nested deeper than [`MAX_VALUE_DEPTH`] are rejected with `ErrorKind::TooLarge`.
+pub fn ivalue<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+) -> IResult<&'input [u8], IValue, E> {
+    ivalue_with_depth_budget(input, MAX_VALUE_DEPTH)
+}
+
+/// Parse a value nested into another one, the budget of the outer value is spent.
+fn nested_ivalue<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+    depth_budget: usize,
+) -> IResult<&'input [u8], IValue, E> {
+    if depth_budget <= 1 {
+        return Err(Err::Error(make_error(input, ErrorKind::TooLarge)));
+    }
+
+    ivalue_with_depth_budget(input, depth_budget - 1)
+}
+
+/// Parse a value that may contain values nested up to `depth_budget` levels, itself included.
+fn ivalue_with_depth_budget<'input, E: ParseError<&'input [u8]>>(
+    input: &'input [u8],
+    depth_budget: usize,
+) -> IResult<&'input [u8], IValue, E> {
+    use crate::NEVec;
+
+    macro_rules! number {
+        ($input:ident, $variant:ident, $native_type:ty) => {{
+            consume!(($input, bytes) = fixed($input)?);
+            IValue::$variant(<$native_type>::from_le_bytes(bytes))
+        }};
+    }
+
+    let (mut input, code) = byte(input)?;
+
+    let value = match code {
+        0x0b => {
+            consume!((input, value) = presence(input)?);
+            IValue::Boolean(value)
+        }
+        0x00 => number!(input, S8, i8),
+        0x01 => number!(input, S16, i16),
+        0x02 => number!(input, S32, i32),
+        0x03 => number!(input, S64, i64),
+        0x04 => number!(input, U8, u8),
+        0x05 => number!(input, U16, u16),
+        0x06 => number!(input, U32, u32),
+        0x07 => number!(input, U64, u64),
+        0x08 => number!(input, F32, f32),
+        0x09 => number!(input, F64, f64),
+        0x0a => {
+            consume!((input, length) = length(input, 1)?);
+            let string = str::from_utf8(&input[..length])
+                .map_err(|_| Err::Error(make_error(input, ErrorKind::Char)))?;
+            input = &input[length..];
+
+            IValue::String(string.to_string())
+        }
+        0x3c => {
+            consume!((input, length) = length(input, 1)?);
+            let bytes = input[..length].to_vec();
+            input = &input[length..];
+
+            IValue::ByteArray(bytes)
+        }
+        0x36 => {
+            consume!((input, values) = values(input, depth_budget)?);
+            IValue::Array(values)
+        }
+        0x0c => number!(input, I32, i32),
+        0x0d => number!(input, I64, i64),
+        0x0e => {
+            consume!((input, fields) = values(input, depth_budget)?);
+            let fields = NEVec::new(fields)
+                .map_err(|_| Err::Error(make_error(input, ErrorKind::NonEmpty)))?;
+
+            IValue::Record(fields)
+        }
+        0x0f => {
+            consume!((input, is_some) = presence(input)?);
+            if is_some {
+                consume!((input, value) = nested_ivalue(input, depth_budget)?);
+                IValue::Optional(Some(Box::new(value)))
+            } else {
+                IValue::Optional(None)
+            }
+        }
+        0x10 => {
+            consume!((input, case) = uleb(input)?);
+            let case = u32::try_from(case)
+                .map_err(|_| Err::Error(make_error(input, ErrorKind::TooLarge)))?;
+
+            consume!((input, has_payload) = presence(input)?);
+            if has_payload {
+                consume!((input, payload) = nested_ivalue(input, depth_budget)?);
+                IValue::Variant(case, Some(Box::new(payload)))
+            } else {
+                IValue::Variant(case, None)
+            }
+        }
+        0x11 => {
+            consume!((input, is_err) = presence(input)?);
+            consume!((input, value) = nested_ivalue(input, depth_budget)?);
+
+            if is_err {
+                IValue::Result(Err(Box::new(value)))
+            } else {
+                IValue::Result(Ok(Box::new(value)))
+            }
+        }
+        0x12 => {
+            consume!((input, length) = length(input, 2)?);
+
+            let mut entries = Vec::with_capacity(length);
+            for _ in 0..length {
+                consume!((input, key) = nested_ivalue(input, depth_budget)?);
+                consume!((input, value) = nested_ivalue(input, depth_budget)?);
+                entries.push((key, value));
+            }
+
+            IValue::Map(entries)
+        }
+        0x13 => number!(input, Flags, u64),
+        0x14 => number!(input, Enum, u32),
+        0x15 => {
+            consume!((input, bytes) = fixed(input)?);
+            let value = char::from_u32(u32::from_le_bytes(bytes))
+                .ok_or_else(|| Err::Error(make_error(input, ErrorKind::Char)))?;
+
+            IValue::Char(value)
+        }
+        0x16 => number!(input, U128, u128),
+        0x17 => number!(input, S128, i128),
+        0x18 => number!(input, Handle, u32),
+        _ => return Err(Err::Error(make_error(input, ErrorKind::Tag))),
+    };
+
+    Ok((input, value))
+}
+
 /// Parse an instruction with its arguments.
 fn instruction<'input, E: ParseError<&'input [u8]>>(
     input: &'input [u8],
@@ -292,24 +630,44 @@ fn instruction<'input, E: ParseError<&'input [u8]>>(
         0x1f => (input, Instruction::I64FromU16),
         0x20 => (input, Instruction::I64FromU32),
         0x21 => (input, Instruction::I64FromU64),
-
-        0x22 => (input, Instruction::StringLiftMemory),
-        0x23 => (input, Instruction::StringLowerMemory),
+        0x48 => (input, Instruction::CharFromI32),
+        0x49 => (input, Instruction::I32FromChar),
+        0x4A => (input, Instruction::U128FromI64),
+        0x4B => (input, Instruction::I64FromU128),
+        0x4C => (input, Instruction::S128FromI64),
+        0x4D => (input, Instruction::I64FromS128),
+
+        // the original opcodes operate on the default memory,
+        // the `0x51..=0x5A` ones carry a memory index as the last operand
+        0x22 => (input, Instruction::StringLiftMemory { memory_index: 0 }),
+        0x23 => (input, Instruction::StringLowerMemory { memory_index: 0 }),
         0x24 => (input, Instruction::StringSize),
 
-        0x43 => (input, Instruction::ByteArrayLiftMemory),
-        0x44 => (input, Instruction::ByteArrayLowerMemory),
+        0x43 => (input, Instruction::ByteArrayLiftMemory { memory_index: 0 }),
+        0x44 => (input, Instruction::ByteArrayLowerMemory { memory_index: 0 }),
         0x45 => (input, Instruction::ByteArraySize),
 
         0x37 => {
             consume!((input, value_type) = ty(input)?);
 
-            (input, Instruction::ArrayLiftMemory { value_type })
+            (
+                input,
+                Instruction::ArrayLiftMemory {
+                    value_type,
+                    memory_index: 0,
+                },
+            )
         }
         0x38 => {
             consume!((input, value_type) = ty(input)?);
 
-            (input, Instruction::ArrayLowerMemory { value_type })
+            (
+                input,
+                Instruction::ArrayLowerMemory {
+                    value_type,
+                    memory_index: 0,
+                },
+            )
         }
         0x3A => {
             consume!((input, record_type_id) = uleb(input)?);
@@ -318,6 +676,7 @@ fn instruction<'input, E: ParseError<&'input [u8]>>(
                 input,
                 Instruction::RecordLiftMemory {
                     record_type_id: record_type_id as u32,
+                    memory_index: 0,
                 },
             )
         }
@@ -328,6 +687,182 @@ fn instruction<'input, E: ParseError<&'input [u8]>>(
                 input,
                 Instruction::RecordLowerMemory {
                     record_type_id: record_type_id as u32,
+                    memory_index: 0,
+                },
+            )
+        }
+
+        0x46 => {
+            consume!((input, ok_type) = ty(input)?);
+            consume!((input, err_type) = ty(input)?);
+
+            (
+                input,
+                Instruction::ResultLiftMemory {
+                    ok_type,
+                    err_type,
+                    memory_index: 0,
+                },
+            )
+        }
+        0x47 => {
+            consume!((input, ok_type) = ty(input)?);
+            consume!((input, err_type) = ty(input)?);
+
+            (
+                input,
+                Instruction::ResultLowerMemory {
+                    ok_type,
+                    err_type,
+                    memory_index: 0,
+                },
+            )
+        }
+
+        0x51 => {
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::StringLiftMemory {
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x52 => {
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::StringLowerMemory {
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x53 => {
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ByteArrayLiftMemory {
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x54 => {
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ByteArrayLowerMemory {
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x55 => {
+            consume!((input, value_type) = ty(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ArrayLiftMemory {
+                    value_type,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x56 => {
+            consume!((input, value_type) = ty(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ArrayLowerMemory {
+                    value_type,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x57 => {
+            consume!((input, record_type_id) = uleb(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::RecordLiftMemory {
+                    record_type_id: record_type_id as u32,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x58 => {
+            consume!((input, record_type_id) = uleb(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::RecordLowerMemory {
+                    record_type_id: record_type_id as u32,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x59 => {
+            consume!((input, ok_type) = ty(input)?);
+            consume!((input, err_type) = ty(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ResultLiftMemory {
+                    ok_type,
+                    err_type,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+        0x5A => {
+            consume!((input, ok_type) = ty(input)?);
+            consume!((input, err_type) = ty(input)?);
+            consume!((input, memory_index) = uleb(input)?);
+
+            (
+                input,
+                Instruction::ResultLowerMemory {
+                    ok_type,
+                    err_type,
+                    memory_index: memory_index as u32,
+                },
+            )
+        }
+
+        0x4E => {
+            consume!((input, resource_id) = uleb(input)?);
+
+            (
+                input,
+                Instruction::HandleCreate {
+                    resource_id: resource_id as u32,
+                },
+            )
+        }
+        0x4F => {
+            consume!((input, resource_id) = uleb(input)?);
+
+            (
+                input,
+                Instruction::HandleBorrow {
+                    resource_id: resource_id as u32,
+                },
+            )
+        }
+        0x50 => {
+            consume!((input, resource_id) = uleb(input)?);
+
+            (
+                input,
+                Instruction::HandleDrop {
+                    resource_id: resource_id as u32,
                 },
             )
         }
@@ -395,6 +930,31 @@ fn types<'input, E: ParseError<&'input [u8]>>(
 
                 types.push(Type::Record(Arc::new(record_type)));
             }
+
+            TypeKind::Variant => {
+                consume!((input, variant_type) = variant_type(input)?);
+
+                types.push(Type::Variant(Arc::new(variant_type)));
+            }
+
+            TypeKind::Flags => {
+                consume!((input, flags_type) = flags_type(input)?);
+
+                types.push(Type::Flags(Arc::new(flags_type)));
+            }
+
+            TypeKind::Enum => {
+                consume!((input, enum_type) = enum_type(input)?);
+
+                types.push(Type::Enum(Arc::new(enum_type)));
+            }
+
+            TypeKind::Alias => {
+                consume!((input, name) = owned_string(input)?);
+                consume!((input, ty) = ty(input)?);
+
+                types.push(Type::Alias { name, ty });
+            }
         }
     }
 
@@ -576,18 +1136,25 @@ fn try_into_version(versions: Vec<&str>) -> Result<semver::Version, ErrorKind> {
 /// # Example
 ///
 /// ```rust
-/// use wasmer_interface_types::{
-///     ast::{Adapter, Export, Implementation, Import, Interfaces, Type},
+/// use wasmer_interface_types_fl::{
+///     ast::{Adapter, Export, FunctionArg, Implementation, Import, Interfaces, Type},
 ///     decoders::binary::parse,
 ///     interpreter::Instruction,
-///     types::IType,
+///     IType,
 /// };
+/// use std::sync::Arc;
 ///
 /// let input = &[
+///     0x05, // version section
+///     0x05, // string of 5 bytes
+///     0x30, 0x2e, 0x32, 0x2e, 0x30, // "0.2.0"
+///     //
 ///     0x00, // type section
 ///     0x01, // 1 type
 ///     0x00, // function type
 ///     0x01, // list of 1 item
+///     0x01, // string of 1 byte
+///     0x78, // "x"
 ///     0x00, // S8
 ///     0x01, // list of 1 item
 ///     0x01, // S16
@@ -620,9 +1187,13 @@ fn try_into_version(versions: Vec<&str>) -> Result<semver::Version, ErrorKind> {
 /// let output = Ok((
 ///     &[] as &[u8],
 ///     Interfaces {
+///         version: semver::Version::new(0, 2, 0),
 ///         types: vec![Type::Function {
-///             inputs: vec![IType::S8],
-///             outputs: vec![IType::S16],
+///             arguments: Arc::new(vec![FunctionArg {
+///                 name: String::from("x"),
+///                 ty: IType::S8,
+///             }]),
+///             output_types: Arc::new(vec![IType::S16]),
 ///         }],
 ///         imports: vec![Import {
 ///             namespace: "ab",
@@ -655,6 +1226,7 @@ pub fn parse<'input, E: ParseError<&'input [u8]>>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::NEVec;
     use nom::{error, Err};
 
     #[test]
@@ -730,10 +1302,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ivalue() {
+        let input = &[
+            0x0e, 0x03, // record of 3 fields
+            0x0a, 0x02, 0x61, 0x62, // "ab"
+            0x12, 0x01, // map of 1 entry
+            0x16, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, // U128
+            0x3c, 0x01, 0x07, // ByteArray
+            0x11, 0x00, // Ok
+            0x0f, 0x01, // Some
+            0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, // 1.0
+            0x01,
+        ];
+        let output = Ok((
+            &[0x01][..],
+            IValue::Record(
+                NEVec::new(vec![
+                    IValue::String("ab".to_string()),
+                    IValue::Map(vec![(IValue::U128(1), IValue::ByteArray(vec![7]))]),
+                    IValue::Result(Ok(Box::new(IValue::Optional(Some(Box::new(IValue::F64(
+                        1.0,
+                    ))))))),
+                ])
+                .unwrap(),
+            ),
+        ));
+
+        assert_eq!(ivalue::<()>(input), output);
+    }
+
+    #[test]
+    fn test_ivalue_invalid() {
+        macro_rules! assert_invalid {
+            ($input:expr, $error_kind:expr) => {
+                assert!(matches!(
+                    ivalue::<(&[u8], error::ErrorKind)>($input),
+                    Err(Err::Error((_, kind))) if kind == $error_kind
+                ));
+            };
+        }
+
+        assert_invalid!(&[0xff], error::ErrorKind::Tag);
+        assert_invalid!(&[0x0b, 0x02], error::ErrorKind::Tag);
+        assert_invalid!(&[0x0e, 0x00], error::ErrorKind::NonEmpty);
+        assert_invalid!(&[0x15, 0x00, 0xd8, 0x00, 0x00], error::ErrorKind::Char);
+        assert_invalid!(
+            &[0x36, 0xff, 0xff, 0xff, 0x0f, 0x04],
+            error::ErrorKind::TooLarge
+        );
+        assert_invalid!(&[0x06, 0x01, 0x00], error::ErrorKind::Eof);
+    }
+
+    #[test]
+    fn test_ivalue_depth() {
+        // `depth` nested optionals around a boolean
+        let nested = |depth: usize| {
+            let mut input = [0x0f, 0x01].repeat(depth - 1);
+            input.extend_from_slice(&[0x0b, 0x01]);
+            input
+        };
+
+        let input = nested(MAX_VALUE_DEPTH);
+        let (rest, _) = ivalue::<()>(&input).unwrap();
+        assert!(rest.is_empty());
+
+        let input = nested(MAX_VALUE_DEPTH + 1);
+        assert!(matches!(
+            ivalue::<(&[u8], error::ErrorKind)>(&input),
+            Err(Err::Error((_, error::ErrorKind::TooLarge)))
+        ));
+
+        // much deeper than the stack could handle without the limit
+        let input = nested(200_000);
+        assert!(matches!(
+            ivalue::<(&[u8], error::ErrorKind)>(&input),
+            Err(Err::Error((_, error::ErrorKind::TooLarge)))
+        ));
+
+        // arrays and maps spend the budget as well
+        let mut input = [0x36, 0x01].repeat(MAX_VALUE_DEPTH / 2);
+        input.extend([0x12, 0x01, 0x04, 0x00].repeat(MAX_VALUE_DEPTH / 2));
+        input.extend_from_slice(&[0x04, 0x00]);
+        assert!(matches!(
+            ivalue::<(&[u8], error::ErrorKind)>(&input),
+            Err(Err::Error((_, error::ErrorKind::TooLarge)))
+        ));
+    }
+
     #[test]
     fn test_ty() {
         let input = &[
-            0x0f, // list of 15 items
+            0x1c, // list of 28 items
+            0x0b, // Boolean
             0x00, // S8
             0x01, // S16
             0x02, // S32
@@ -745,15 +1407,28 @@ mod tests {
             0x08, // F32
             0x09, // F64
             0x0a, // String
-            0x0b, // Anyref
+            0x3c, // ByteArray
+            0x36, 0x0a, // Array(String)
             0x0c, // I32
             0x0d, // I64
-            0x0e, 0x01, 0x02, // Record
+            0x0e, 0x01, // Record(1)
+            0x0f, 0x0a, // Optional(String)
+            0x10, 0x02, // Variant(2)
+            0x11, 0x0a, 0x06, // Result(String, U32)
+            0x12, 0x0a, 0x06, // Map(String, U32)
+            0x13, 0x03, // Flags(3)
+            0x14, 0x04, // Enum(4)
+            0x15, // Char
+            0x16, // U128
+            0x17, // S128
+            0x18, 0x05, // Handle(5)
+            0x19, 0x06, // Alias(6)
             0x01,
         ];
         let output = Ok((
             &[0x01][..],
             vec![
+                IType::Boolean,
                 IType::S8,
                 IType::S16,
                 IType::S32,
@@ -765,12 +1440,22 @@ mod tests {
                 IType::F32,
                 IType::F64,
                 IType::String,
-                IType::Anyref,
+                IType::ByteArray,
+                IType::Array(Box::new(IType::String)),
                 IType::I32,
                 IType::I64,
-                IType::Record(RecordType {
-                    fields: vec1![IType::S32],
-                }),
+                IType::Record(1),
+                IType::Optional(Box::new(IType::String)),
+                IType::Variant(2),
+                IType::Result(Box::new(IType::String), Box::new(IType::U32)),
+                IType::Map(Box::new(IType::String), Box::new(IType::U32)),
+                IType::Flags(3),
+                IType::Enum(4),
+                IType::Char,
+                IType::U128,
+                IType::S128,
+                IType::Handle(5),
+                IType::Alias(6),
             ],
         ));
 
@@ -780,38 +1465,48 @@ mod tests {
     #[test]
     fn test_record_type() {
         let input = &[
-            0x03, // list of 3 items
+            0x02, // list of 2 items
+            0x01, // string of 1 byte
+            0x61, // "a"
             0x01, // 1 field
+            0x01, // string of 1 byte
+            0x78, // "x"
             0x0a, // String
+            0x01, // string of 1 byte
+            0x62, // "b"
             0x02, // 2 fields
-            0x0a, // String
-            0x0c, // I32
-            0x03, // 3 fields
-            0x0a, // String
-            0x0e, // Record
-            0x02, // 2 fields
-            0x0c, // I32
-            0x0c, // I32
+            0x01, // string of 1 byte
+            0x78, // "x"
+            0x0e, 0x00, // Record(0)
+            0x01, // string of 1 byte
+            0x79, // "y"
             0x09, // F64
             0x01,
         ];
         let output = Ok((
             &[0x01][..],
             vec![
-                RecordType {
-                    fields: vec1![IType::String],
-                },
-                RecordType {
-                    fields: vec1![IType::String, IType::I32],
+                IRecordType {
+                    name: String::from("a"),
+                    fields: NEVec::new(vec![IRecordFieldType {
+                        name: Some(String::from("x")),
+                        ty: IType::String,
+                    }])
+                    .unwrap(),
                 },
-                RecordType {
-                    fields: vec1![
-                        IType::String,
-                        IType::Record(RecordType {
-                            fields: vec1![IType::I32, IType::I32],
-                        }),
-                        IType::F64,
-                    ],
+                IRecordType {
+                    name: String::from("b"),
+                    fields: NEVec::new(vec![
+                        IRecordFieldType {
+                            name: Some(String::from("x")),
+                            ty: IType::Record(0),
+                        },
+                        IRecordFieldType {
+                            name: Some(String::from("y")),
+                            ty: IType::F64,
+                        },
+                    ])
+                    .unwrap(),
                 },
             ],
         ));
@@ -819,6 +1514,156 @@ mod tests {
         assert_eq!(list::<_, ()>(input, record_type), output);
     }
 
+    #[test]
+    fn test_record_type_anonymous_fields() {
+        let input = &[
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x61, // "a"
+            0x02, // 2 fields
+            0x00, // empty string
+            0x0a, // String
+            0x00, // empty string
+            0x0c, // I32
+            0x01,
+        ];
+        let output = Ok((
+            &[0x01][..],
+            vec![IRecordType {
+                name: String::from("a"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: None,
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: None,
+                        ty: IType::I32,
+                    },
+                ])
+                .unwrap(),
+            }],
+        ));
+
+        assert_eq!(list::<_, ()>(input, record_type), output);
+    }
+
+    #[test]
+    fn test_variant_type() {
+        let input = &[
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x61, // "a"
+            0x02, // 2 cases
+            0x01, // string of 1 byte
+            0x78, // "x"
+            0x01, // with payload
+            0x0a, // String
+            0x01, // string of 1 byte
+            0x79, // "y"
+            0x00, // without payload
+            0x01,
+        ];
+        let output = Ok((
+            &[0x01][..],
+            vec![IVariantType {
+                name: String::from("a"),
+                cases: NEVec::new(vec![
+                    IVariantCaseType {
+                        name: String::from("x"),
+                        ty: Some(IType::String),
+                    },
+                    IVariantCaseType {
+                        name: String::from("y"),
+                        ty: None,
+                    },
+                ])
+                .unwrap(),
+            }],
+        ));
+
+        assert_eq!(list::<_, ()>(input, variant_type), output);
+    }
+
+    #[test]
+    fn test_variant_type_without_cases() {
+        let input = &[
+            0x01, // string of 1 byte
+            0x61, // "a"
+            0x00, // 0 cases
+        ];
+
+        assert_eq!(
+            variant_type::<(&[u8], error::ErrorKind)>(input),
+            Err(Err::Error((&input[..], error::ErrorKind::Verify))),
+        );
+    }
+
+    #[test]
+    fn test_flags_type() {
+        let input = &[
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x70, // "p"
+            0x02, // 2 flags
+            0x01, // string of 1 byte
+            0x72, // "r"
+            0x01, // string of 1 byte
+            0x77, // "w"
+            0x01,
+        ];
+        let output = Ok((
+            &[0x01][..],
+            vec![IFlagsType {
+                name: String::from("p"),
+                flags: NEVec::new(vec![String::from("r"), String::from("w")]).unwrap(),
+            }],
+        ));
+
+        assert_eq!(list::<_, ()>(input, flags_type), output);
+    }
+
+    #[test]
+    fn test_flags_type_too_many_flags() {
+        let mut input = vec![
+            0x01, // string of 1 byte
+            0x70, // "p"
+            0x41, // 65 flags
+        ];
+        for _ in 0..65 {
+            input.extend_from_slice(&[0x01, 0x66]); // "f"
+        }
+
+        assert_eq!(
+            flags_type::<(&[u8], error::ErrorKind)>(&input),
+            Err(Err::Error((&input[..], error::ErrorKind::TooLarge))),
+        );
+    }
+
+    #[test]
+    fn test_enum_type() {
+        let input = &[
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x73, // "s"
+            0x02, // 2 members
+            0x01, // string of 1 byte
+            0x61, // "a"
+            0x01, // string of 1 byte
+            0x62, // "b"
+            0x01,
+        ];
+        let output = Ok((
+            &[0x01][..],
+            vec![IEnumType {
+                name: String::from("s"),
+                members: NEVec::new(vec![String::from("a"), String::from("b")]).unwrap(),
+            }],
+        ));
+
+        assert_eq!(list::<_, ()>(input, enum_type), output);
+    }
+
     #[test]
     fn test_string() {
         let input = &[
@@ -852,7 +1697,7 @@ mod tests {
     #[test]
     fn test_instructions() {
         let input = &[
-            0x27, // list of 39 items
+            0x32, // list of 50 items
             0x00, 0x01, // ArgumentGet { index: 1 }
             0x01, 0x01, // CallCore { function_index: 1 }
             0x02, // S8FromI32
@@ -887,11 +1732,24 @@ mod tests {
             0x1f, // I64FromU16
             0x20, // I64FromU32
             0x21, // I64FromU64
-            0x22, // StringLiftMemory
-            0x23, // StringLowerMemory
+            0x48, // CharFromI32
+            0x49, // I32FromChar
+            0x4a, // U128FromI64
+            0x4b, // I64FromU128
+            0x4c, // S128FromI64
+            0x4d, // I64FromS128
+            0x22, // StringLiftMemory { memory_index: 0 }
+            0x52, 0x01, // StringLowerMemory { memory_index: 1 }
             0x24, // StringSize
-            0x25, 0x01, // RecordLift { type_index: 1 },
-            0x26, 0x01, // RecordLower { type_index: 1 },
+            0x3a, 0x01, // RecordLiftMemory { record_type_id: 1, memory_index: 0 },
+            0x58, 0x01, 0x01, // RecordLowerMemory { record_type_id: 1, memory_index: 1 },
+            0x46, 0x0a,
+            0x06, // ResultLiftMemory { ok_type: String, err_type: U32, memory_index: 0 },
+            0x5a, 0x0a, 0x06,
+            0x01, // ResultLowerMemory { ok_type: String, err_type: U32, memory_index: 1 },
+            0x4e, 0x01, // HandleCreate { resource_id: 1 },
+            0x4f, 0x01, // HandleBorrow { resource_id: 1 },
+            0x50, 0x01, // HandleDrop { resource_id: 1 },
             0x0a,
         ];
         let output = Ok((
@@ -931,14 +1789,72 @@ mod tests {
                 Instruction::I64FromU16,
                 Instruction::I64FromU32,
                 Instruction::I64FromU64,
-                Instruction::StringLiftMemory,
-                Instruction::StringLowerMemory,
+                Instruction::CharFromI32,
+                Instruction::I32FromChar,
+                Instruction::U128FromI64,
+                Instruction::I64FromU128,
+                Instruction::S128FromI64,
+                Instruction::I64FromS128,
+                Instruction::StringLiftMemory { memory_index: 0 },
+                Instruction::StringLowerMemory { memory_index: 1 },
                 Instruction::StringSize,
-                /*
-                Instruction::RecordLift { type_index: 1 },
-                Instruction::RecordLower { type_index: 1 },
+                Instruction::RecordLiftMemory {
+                    record_type_id: 1,
+                    memory_index: 0,
+                },
+                Instruction::RecordLowerMemory {
+                    record_type_id: 1,
+                    memory_index: 1,
+                },
+                Instruction::ResultLiftMemory {
+                    ok_type: IType::String,
+                    err_type: IType::U32,
+                    memory_index: 0,
+                },
+                Instruction::ResultLowerMemory {
+                    ok_type: IType::String,
+                    err_type: IType::U32,
+                    memory_index: 1,
+                },
+                Instruction::HandleCreate { resource_id: 1 },
+                Instruction::HandleBorrow { resource_id: 1 },
+                Instruction::HandleDrop { resource_id: 1 },
+            ],
+        ));
 
-                 */
+        assert_eq!(list::<_, ()>(input, instruction), output);
+    }
+
+    #[test]
+    fn test_memory_instructions() {
+        let input = &[
+            0x06, // list of 6 items
+            0x43, // ByteArrayLiftMemory { memory_index: 0 }
+            0x53, 0x02, // ByteArrayLiftMemory { memory_index: 2 }
+            0x54, 0x00, // ByteArrayLowerMemory { memory_index: 0 }
+            0x38, 0x06, // ArrayLowerMemory { value_type: U32, memory_index: 0 }
+            0x55, 0x06, 0x02, // ArrayLiftMemory { value_type: U32, memory_index: 2 }
+            0x57, 0x01, 0x02, // RecordLiftMemory { record_type_id: 1, memory_index: 2 }
+            0x0a,
+        ];
+        let output = Ok((
+            &[0x0a][..],
+            vec![
+                Instruction::ByteArrayLiftMemory { memory_index: 0 },
+                Instruction::ByteArrayLiftMemory { memory_index: 2 },
+                Instruction::ByteArrayLowerMemory { memory_index: 0 },
+                Instruction::ArrayLowerMemory {
+                    value_type: IType::U32,
+                    memory_index: 0,
+                },
+                Instruction::ArrayLiftMemory {
+                    value_type: IType::U32,
+                    memory_index: 2,
+                },
+                Instruction::RecordLiftMemory {
+                    record_type_id: 1,
+                    memory_index: 2,
+                },
             ],
         ));
 
@@ -976,28 +1892,101 @@ mod tests {
     #[test]
     fn test_types() {
         let input = &[
-            0x02, // 2 type
+            0x06, // 6 type
             0x00, // function type
             0x02, // list of 2 items
+            0x01, // string of 1 byte
+            0x61, // "a"
             0x02, // S32
+            0x01, // string of 1 byte
+            0x62, // "b"
             0x02, // S32
-            0x01, // list of 2 items
+            0x01, // list of 1 items
             0x02, // S32
             0x01, // record type
+            0x01, // string of 1 byte
+            0x63, // "c"
             0x02, // list of 2 items
+            0x01, // string of 1 byte
+            0x78, // "x"
             0x02, // S32
+            0x01, // string of 1 byte
+            0x79, // "y"
             0x02, // S32
+            0x02, // variant type
+            0x01, // string of 1 byte
+            0x64, // "d"
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x7a, // "z"
+            0x00, // without payload
+            0x03, // flags type
+            0x01, // string of 1 byte
+            0x65, // "e"
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x72, // "r"
+            0x04, // enum type
+            0x01, // string of 1 byte
+            0x66, // "f"
+            0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x6f, // "o"
+            0x05, // alias type
+            0x01, // string of 1 byte
+            0x67, // "g"
+            0x0a, // String
         ];
         let output = Ok((
             &[] as &[u8],
             vec![
                 Type::Function {
-                    inputs: vec![IType::S32, IType::S32],
-                    outputs: vec![IType::S32],
+                    arguments: Arc::new(vec![
+                        FunctionArg {
+                            name: String::from("a"),
+                            ty: IType::S32,
+                        },
+                        FunctionArg {
+                            name: String::from("b"),
+                            ty: IType::S32,
+                        },
+                    ]),
+                    output_types: Arc::new(vec![IType::S32]),
+                },
+                Type::Record(Arc::new(IRecordType {
+                    name: String::from("c"),
+                    fields: NEVec::new(vec![
+                        IRecordFieldType {
+                            name: Some(String::from("x")),
+                            ty: IType::S32,
+                        },
+                        IRecordFieldType {
+                            name: Some(String::from("y")),
+                            ty: IType::S32,
+                        },
+                    ])
+                    .unwrap(),
+                })),
+                Type::Variant(Arc::new(IVariantType {
+                    name: String::from("d"),
+                    cases: NEVec::new(vec![IVariantCaseType {
+                        name: String::from("z"),
+                        ty: None,
+                    }])
+                    .unwrap(),
+                })),
+                Type::Flags(Arc::new(IFlagsType {
+                    name: String::from("e"),
+                    flags: NEVec::new(vec![String::from("r")]).unwrap(),
+                })),
+                Type::Enum(Arc::new(IEnumType {
+                    name: String::from("f"),
+                    members: NEVec::new(vec![String::from("o")]).unwrap(),
+                })),
+                Type::Alias {
+                    name: String::from("g"),
+                    ty: IType::String,
                 },
-                Type::Record(RecordType {
-                    fields: vec1![IType::S32, IType::S32],
-                }),
             ],
         ));
 
@@ -1060,10 +2049,16 @@ mod tests {
     #[test]
     fn test_parse() {
         let input = &[
+            0x05, // version section
+            0x05, // string of 5 bytes
+            0x30, 0x2e, 0x32, 0x2e, 0x30, // "0.2.0"
+            //
             0x00, // type section
             0x01, // 1 type
             0x00, // function type
             0x01, // list of 1 item
+            0x01, // string of 1 byte
+            0x78, // "x"
             0x00, // S8
             0x01, // list of 1 item
             0x01, // S16
@@ -1096,9 +2091,13 @@ mod tests {
         let output = Ok((
             &[] as &[u8],
             Interfaces {
+                version: semver::Version::new(0, 2, 0),
                 types: vec![Type::Function {
-                    inputs: vec![IType::S8],
-                    outputs: vec![IType::S16],
+                    arguments: Arc::new(vec![FunctionArg {
+                        name: String::from("x"),
+                        ty: IType::S8,
+                    }]),
+                    output_types: Arc::new(vec![IType::S16]),
                 }],
                 imports: vec![Import {
                     namespace: "ab",
@@ -20,6 +20,10 @@ mod keyword {
     custom_keyword!(r#type = "type");
     custom_keyword!(record);
     custom_keyword!(field);
+    custom_keyword!(variant);
+    custom_keyword!(flags);
+    custom_keyword!(enum_ = "enum");
+    custom_keyword!(alias);
     custom_keyword!(it_version);
 
     // Special symbols
@@ -77,6 +81,12 @@ mod keyword {
     custom_keyword!(i64_from_u16 = "i64.from_u16");
     custom_keyword!(i64_from_u32 = "i64.from_u32");
     custom_keyword!(i64_from_u64 = "i64.from_u64");
+    custom_keyword!(char_from_i32 = "char.from_i32");
+    custom_keyword!(i32_from_char = "i32.from_char");
+    custom_keyword!(u128_from_i64 = "u128.from_i64");
+    custom_keyword!(i64_from_u128 = "i64.from_u128");
+    custom_keyword!(s128_from_i64 = "s128.from_i64");
+    custom_keyword!(i64_from_s128 = "i64.from_s128");
     custom_keyword!(f32_push = "f32.push");
     custom_keyword!(f64_push = "f64.push");
     custom_keyword!(string_lift_memory = "string.lift_memory");
@@ -92,6 +102,11 @@ mod keyword {
     custom_keyword!(record_lower = "record.lower");
     custom_keyword!(record_lift_memory = "record.lift_memory");
     custom_keyword!(record_lower_memory = "record.lower_memory");
+    custom_keyword!(handle_create = "handle.create");
+    custom_keyword!(handle_borrow = "handle.borrow");
+    custom_keyword!(handle_drop = "handle.drop");
+    custom_keyword!(result_lift_memory = "result.lift_memory");
+    custom_keyword!(result_lower_memory = "result.lower_memory");
     custom_keyword!(dup = "dup");
     custom_keyword!(swap2 = "swap2");
 }
@@ -254,14 +269,42 @@ impl<'a> Parse<'a> for Instruction {
             parser.parse::<keyword::i64_from_u64>()?;
 
             Ok(Instruction::I64FromU64)
+        } else if lookahead.peek::<keyword::char_from_i32>() {
+            parser.parse::<keyword::char_from_i32>()?;
+
+            Ok(Instruction::CharFromI32)
+        } else if lookahead.peek::<keyword::i32_from_char>() {
+            parser.parse::<keyword::i32_from_char>()?;
+
+            Ok(Instruction::I32FromChar)
+        } else if lookahead.peek::<keyword::u128_from_i64>() {
+            parser.parse::<keyword::u128_from_i64>()?;
+
+            Ok(Instruction::U128FromI64)
+        } else if lookahead.peek::<keyword::i64_from_u128>() {
+            parser.parse::<keyword::i64_from_u128>()?;
+
+            Ok(Instruction::I64FromU128)
+        } else if lookahead.peek::<keyword::s128_from_i64>() {
+            parser.parse::<keyword::s128_from_i64>()?;
+
+            Ok(Instruction::S128FromI64)
+        } else if lookahead.peek::<keyword::i64_from_s128>() {
+            parser.parse::<keyword::i64_from_s128>()?;
+
+            Ok(Instruction::I64FromS128)
         } else if lookahead.peek::<keyword::string_lift_memory>() {
             parser.parse::<keyword::string_lift_memory>()?;
 
-            Ok(Instruction::StringLiftMemory)
+            Ok(Instruction::StringLiftMemory {
+                memory_index: memory_index(parser)?,
+            })
         } else if lookahead.peek::<keyword::string_lower_memory>() {
             parser.parse::<keyword::string_lower_memory>()?;
 
-            Ok(Instruction::StringLowerMemory)
+            Ok(Instruction::StringLowerMemory {
+                memory_index: memory_index(parser)?,
+            })
         } else if lookahead.peek::<keyword::string_size>() {
             parser.parse::<keyword::string_size>()?;
 
@@ -269,11 +312,15 @@ impl<'a> Parse<'a> for Instruction {
         } else if lookahead.peek::<keyword::byte_array_lift_memory>() {
             parser.parse::<keyword::byte_array_lift_memory>()?;
 
-            Ok(Instruction::ByteArrayLiftMemory)
+            Ok(Instruction::ByteArrayLiftMemory {
+                memory_index: memory_index(parser)?,
+            })
         } else if lookahead.peek::<keyword::byte_array_lower_memory>() {
             parser.parse::<keyword::byte_array_lower_memory>()?;
 
-            Ok(Instruction::ByteArrayLowerMemory)
+            Ok(Instruction::ByteArrayLowerMemory {
+                memory_index: memory_index(parser)?,
+            })
         } else if lookahead.peek::<keyword::byte_array_size>() {
             parser.parse::<keyword::byte_array_size>()?;
 
@@ -283,12 +330,14 @@ impl<'a> Parse<'a> for Instruction {
 
             Ok(Instruction::ArrayLiftMemory {
                 value_type: parser.parse()?,
+                memory_index: memory_index(parser)?,
             })
         } else if lookahead.peek::<keyword::array_lower_memory>() {
             parser.parse::<keyword::array_lower_memory>()?;
 
             Ok(Instruction::ArrayLowerMemory {
                 value_type: parser.parse()?,
+                memory_index: memory_index(parser)?,
             })
         }
         /*
@@ -311,12 +360,48 @@ impl<'a> Parse<'a> for Instruction {
 
             Ok(Instruction::RecordLiftMemory {
                 record_type_id: parser.parse()?,
+                memory_index: memory_index(parser)?,
             })
         } else if lookahead.peek::<keyword::record_lower_memory>() {
             parser.parse::<keyword::record_lower_memory>()?;
 
             Ok(Instruction::RecordLowerMemory {
                 record_type_id: parser.parse()?,
+                memory_index: memory_index(parser)?,
+            })
+        } else if lookahead.peek::<keyword::result_lift_memory>() {
+            parser.parse::<keyword::result_lift_memory>()?;
+
+            Ok(Instruction::ResultLiftMemory {
+                ok_type: parser.parse()?,
+                err_type: parser.parse()?,
+                memory_index: memory_index(parser)?,
+            })
+        } else if lookahead.peek::<keyword::result_lower_memory>() {
+            parser.parse::<keyword::result_lower_memory>()?;
+
+            Ok(Instruction::ResultLowerMemory {
+                ok_type: parser.parse()?,
+                err_type: parser.parse()?,
+                memory_index: memory_index(parser)?,
+            })
+        } else if lookahead.peek::<keyword::handle_create>() {
+            parser.parse::<keyword::handle_create>()?;
+
+            Ok(Instruction::HandleCreate {
+                resource_id: parser.parse()?,
+            })
+        } else if lookahead.peek::<keyword::handle_borrow>() {
+            parser.parse::<keyword::handle_borrow>()?;
+
+            Ok(Instruction::HandleBorrow {
+                resource_id: parser.parse()?,
+            })
+        } else if lookahead.peek::<keyword::handle_drop>() {
+            parser.parse::<keyword::handle_drop>()?;
+
+            Ok(Instruction::HandleDrop {
+                resource_id: parser.parse()?,
             })
         } else if lookahead.peek::<keyword::dup>() {
             parser.parse::<keyword::dup>()?;
@@ -332,6 +417,16 @@ impl<'a> Parse<'a> for Instruction {
     }
 }
 
+/// Parses the optional memory index that ends memory instructions,
+/// the default memory is used when it's omitted.
+fn memory_index(parser: Parser<'_>) -> Result<u32> {
+    if parser.peek::<u32>() {
+        parser.parse()
+    } else {
+        Ok(it_lilo::traits::DEFAULT_MEMORY_INDEX as u32)
+    }
+}
+
 struct AtInterface;
 
 impl Peek for AtInterface {
@@ -498,6 +593,27 @@ impl<'a> Parse<'a> for Type {
                 })
             } else if lookahead.peek::<keyword::record>() {
                 Ok(Type::Record(Arc::new(parser.parse()?)))
+            } else if lookahead.peek::<keyword::variant>() {
+                Ok(Type::Variant(Arc::new(parser.parse()?)))
+            } else if lookahead.peek::<keyword::flags>() {
+                Ok(Type::Flags(Arc::new(parser.parse()?)))
+            } else if lookahead.peek::<keyword::enum_>() {
+                Ok(Type::Enum(Arc::new(parser.parse()?)))
+            } else if lookahead.peek::<keyword::alias>() {
+                parser.parse::<keyword::alias>()?;
+
+                let name = parser
+                    .step(|cursor| {
+                        cursor
+                            .id()
+                            .ok_or_else(|| cursor.error("expecting alias identifier"))
+                    })?
+                    .to_string();
+
+                Ok(Type::Alias {
+                    name,
+                    ty: parser.parse()?,
+                })
             } else {
                 Err(lookahead.error())
             }
@@ -666,15 +782,18 @@ fn try_handle_version(
 /// # Examples
 ///
 /// ```rust
-/// use wasmer_interface_types::{
-///     ast::{Adapter, Export, Implementation, Import, Interfaces, Type},
+/// use wasmer_interface_types_fl::{
+///     ast::{Adapter, Export, FunctionArg, Implementation, Import, Interfaces, Type},
 ///     decoders::wat::{parse, Buffer},
 ///     interpreter::Instruction,
-///     types::IType,
+///     IType,
 /// };
+/// use std::sync::Arc;
 ///
 /// let input = Buffer::new(
-///     r#"(@interface type (func (param i32) (result s8)))
+///     r#"(@interface it_version "0.2.0")
+///
+/// (@interface type (func (param $x: i32) (result s8)))
 ///
 /// (@interface import "ns" "foo" (func (type 0)))
 ///
@@ -686,9 +805,13 @@ fn try_handle_version(
 /// )
 /// .unwrap();
 /// let output = Interfaces {
+///     version: semver::Version::new(0, 2, 0),
 ///     types: vec![Type::Function {
-///         inputs: vec![IType::I32],
-///         outputs: vec![IType::S8],
+///         arguments: Arc::new(vec![FunctionArg {
+///             name: String::from("x"),
+///             ty: IType::I32,
+///         }]),
+///         output_types: Arc::new(vec![IType::S8]),
 ///     }],
 ///     imports: vec![Import {
 ///         namespace: "ns",
@@ -718,6 +841,13 @@ pub fn parse<'input>(input: &'input Buffer) -> Result<Interfaces<'input>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IEnumType;
+    use crate::IFlagsType;
+    use crate::IRecordFieldType;
+    use crate::IRecordType;
+    use crate::IVariantCaseType;
+    use crate::IVariantType;
+    use crate::NEVec;
     use wast::parser;
 
     fn buffer(input: &str) -> Buffer {
@@ -727,6 +857,7 @@ mod tests {
     #[test]
     fn test_interface_type() {
         let inputs = vec![
+            "boolean",
             "s8",
             "s16",
             "s32",
@@ -738,12 +869,24 @@ mod tests {
             "f32",
             "f64",
             "string",
-            "anyref",
+            "array (string)",
             "i32",
             "i64",
-            "record (field string)",
+            "record 1",
+            "option (string)",
+            "variant 1",
+            "result (string) (u32)",
+            "map (string) (u32)",
+            "flags 1",
+            "enum 2",
+            "char",
+            "u128",
+            "s128",
+            "handle 3",
+            "alias 4",
         ];
         let outputs = vec![
+            IType::Boolean,
             IType::S8,
             IType::S16,
             IType::S32,
@@ -755,12 +898,21 @@ mod tests {
             IType::F32,
             IType::F64,
             IType::String,
-            IType::Anyref,
+            IType::Array(Box::new(IType::String)),
             IType::I32,
             IType::I64,
-            IType::Record(RecordType {
-                fields: vec1![IType::String],
-            }),
+            IType::Record(1),
+            IType::Optional(Box::new(IType::String)),
+            IType::Variant(1),
+            IType::Result(Box::new(IType::String), Box::new(IType::U32)),
+            IType::Map(Box::new(IType::String), Box::new(IType::U32)),
+            IType::Flags(1),
+            IType::Enum(2),
+            IType::Char,
+            IType::U128,
+            IType::S128,
+            IType::Handle(3),
+            IType::Alias(4),
         ];
 
         assert_eq!(inputs.len(), outputs.len());
@@ -773,25 +925,65 @@ mod tests {
     #[test]
     fn test_record_type() {
         let inputs = vec![
-            "record (field string)",
-            "record (field string) (field i32)",
-            "record (field string) (field record (field i32) (field i32)) (field f64)",
+            "record $a (field $x: string)",
+            "record $b (field $x: string field $y: i32)",
+            "record $c (field $x: string field $y: record 0 field $z: f64)",
+            "record $d (field string field $y: i32)",
         ];
         let outputs = vec![
-            RecordType {
-                fields: vec1![IType::String],
+            IRecordType {
+                name: String::from("a"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::String,
+                }])
+                .unwrap(),
+            },
+            IRecordType {
+                name: String::from("b"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: Some(String::from("x")),
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("y")),
+                        ty: IType::I32,
+                    },
+                ])
+                .unwrap(),
             },
-            RecordType {
-                fields: vec1![IType::String, IType::I32],
+            IRecordType {
+                name: String::from("c"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: Some(String::from("x")),
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("y")),
+                        ty: IType::Record(0),
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("z")),
+                        ty: IType::F64,
+                    },
+                ])
+                .unwrap(),
             },
-            RecordType {
-                fields: vec1![
-                    IType::String,
-                    IType::Record(RecordType {
-                        fields: vec1![IType::I32, IType::I32],
-                    }),
-                    IType::F64,
-                ],
+            IRecordType {
+                name: String::from("d"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: None,
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("y")),
+                        ty: IType::I32,
+                    },
+                ])
+                .unwrap(),
             },
         ];
 
@@ -799,12 +991,111 @@ mod tests {
 
         for (input, output) in inputs.iter().zip(outputs.iter()) {
             assert_eq!(
-                &parser::parse::<RecordType>(&buffer(input)).unwrap(),
+                &parser::parse::<IRecordType>(&buffer(input)).unwrap(),
                 output
             );
         }
     }
 
+    #[test]
+    fn test_variant_type() {
+        let inputs = [
+            "variant $a (case $x)",
+            "variant $b (case $x: string case $y case $z: record 0)",
+        ];
+        let outputs = vec![
+            IVariantType {
+                name: String::from("a"),
+                cases: NEVec::new(vec![IVariantCaseType {
+                    name: String::from("x"),
+                    ty: None,
+                }])
+                .unwrap(),
+            },
+            IVariantType {
+                name: String::from("b"),
+                cases: NEVec::new(vec![
+                    IVariantCaseType {
+                        name: String::from("x"),
+                        ty: Some(IType::String),
+                    },
+                    IVariantCaseType {
+                        name: String::from("y"),
+                        ty: None,
+                    },
+                    IVariantCaseType {
+                        name: String::from("z"),
+                        ty: Some(IType::Record(0)),
+                    },
+                ])
+                .unwrap(),
+            },
+        ];
+
+        assert_eq!(inputs.len(), outputs.len());
+
+        for (input, output) in inputs.iter().zip(outputs.iter()) {
+            assert_eq!(
+                &parser::parse::<IVariantType>(&buffer(input)).unwrap(),
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_variant_type_invalid() {
+        let inputs = ["variant $a ()", "variant $a (case $x case $x: string)"];
+
+        for input in inputs.iter() {
+            assert!(parser::parse::<IVariantType>(&buffer(input)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_flags_type() {
+        let input = "flags $p (flag $read flag $write flag $exec)";
+        let output = IFlagsType {
+            name: String::from("p"),
+            flags: NEVec::new(vec![
+                String::from("read"),
+                String::from("write"),
+                String::from("exec"),
+            ])
+            .unwrap(),
+        };
+
+        assert_eq!(parser::parse::<IFlagsType>(&buffer(input)).unwrap(), output);
+    }
+
+    #[test]
+    fn test_flags_type_invalid() {
+        let too_many_flags = (0..65).fold(String::from("flags $p ("), |mut input, i| {
+            input.push_str(&format!("flag $f{} ", i));
+            input
+        }) + ")";
+        let inputs = [
+            "flags $p ()",
+            "flags $p (flag $read flag $read)",
+            too_many_flags.as_str(),
+        ];
+
+        for input in inputs.iter() {
+            assert!(parser::parse::<IFlagsType>(&buffer(input)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_enum_type() {
+        let input = "enum $s (case $ok case $failed)";
+        let output = IEnumType {
+            name: String::from("s"),
+            members: NEVec::new(vec![String::from("ok"), String::from("failed")]).unwrap(),
+        };
+
+        assert_eq!(parser::parse::<IEnumType>(&buffer(input)).unwrap(), output);
+        assert!(parser::parse::<IEnumType>(&buffer("enum $s ()")).is_err());
+    }
+
     #[test]
     fn test_instructions() {
         let inputs = vec![
@@ -842,11 +1133,22 @@ mod tests {
             "i64.from_u16",
             "i64.from_u32",
             "i64.from_u64",
+            "char.from_i32",
+            "i32.from_char",
+            "u128.from_i64",
+            "i64.from_u128",
+            "s128.from_i64",
+            "i64.from_s128",
             "string.lift_memory",
-            "string.lower_memory",
+            "string.lower_memory 1",
             "string.size",
-            "record.lift 42",
-            "record.lower 42",
+            "record.lift_memory 42",
+            "record.lower_memory 42 1",
+            "result.lift_memory string u32",
+            "result.lower_memory string u32 1",
+            "handle.create 42",
+            "handle.borrow 42",
+            "handle.drop 42",
         ];
         let outputs = vec![
             Instruction::ArgumentGet { index: 7 },
@@ -883,13 +1185,36 @@ mod tests {
             Instruction::I64FromU16,
             Instruction::I64FromU32,
             Instruction::I64FromU64,
-            Instruction::StringLiftMemory,
-            Instruction::StringLowerMemory,
+            Instruction::CharFromI32,
+            Instruction::I32FromChar,
+            Instruction::U128FromI64,
+            Instruction::I64FromU128,
+            Instruction::S128FromI64,
+            Instruction::I64FromS128,
+            Instruction::StringLiftMemory { memory_index: 0 },
+            Instruction::StringLowerMemory { memory_index: 1 },
             Instruction::StringSize,
-            /*
-            Instruction::RecordLift { type_index: 42 },
-            Instruction::RecordLower { type_index: 42 },
-             */
+            Instruction::RecordLiftMemory {
+                record_type_id: 42,
+                memory_index: 0,
+            },
+            Instruction::RecordLowerMemory {
+                record_type_id: 42,
+                memory_index: 1,
+            },
+            Instruction::ResultLiftMemory {
+                ok_type: IType::String,
+                err_type: IType::U32,
+                memory_index: 0,
+            },
+            Instruction::ResultLowerMemory {
+                ok_type: IType::String,
+                err_type: IType::U32,
+                memory_index: 1,
+            },
+            Instruction::HandleCreate { resource_id: 42 },
+            Instruction::HandleBorrow { resource_id: 42 },
+            Instruction::HandleDrop { resource_id: 42 },
         ];
 
         assert_eq!(inputs.len(), outputs.len());
@@ -905,15 +1230,24 @@ mod tests {
     #[test]
     fn test_param_empty() {
         let input = buffer("(param)");
-        let output = FunctionType::InputTypes(vec![]);
+        let output = FunctionType::Header(vec![]);
 
         assert_eq!(parser::parse::<FunctionType>(&input).unwrap(), output);
     }
 
     #[test]
     fn test_param() {
-        let input = buffer("(param i32 string)");
-        let output = FunctionType::InputTypes(vec![IType::I32, IType::String]);
+        let input = buffer("(param $x: i32 $y: string)");
+        let output = FunctionType::Header(vec![
+            FunctionArg {
+                name: String::from("x"),
+                ty: IType::I32,
+            },
+            FunctionArg {
+                name: String::from("y"),
+                ty: IType::String,
+            },
+        ]);
 
         assert_eq!(parser::parse::<FunctionType>(&input).unwrap(), output);
     }
@@ -936,10 +1270,19 @@ mod tests {
 
     #[test]
     fn test_type_function() {
-        let input = buffer(r#"(@interface type (func (param i32 i32) (result i32)))"#);
+        let input = buffer(r#"(@interface type (func (param $x: i32 $y: i32) (result i32)))"#);
         let output = Interface::Type(Type::Function {
-            inputs: vec![IType::I32, IType::I32],
-            outputs: vec![IType::I32],
+            arguments: Arc::new(vec![
+                FunctionArg {
+                    name: String::from("x"),
+                    ty: IType::I32,
+                },
+                FunctionArg {
+                    name: String::from("y"),
+                    ty: IType::I32,
+                },
+            ]),
+            output_types: Arc::new(vec![IType::I32]),
         });
 
         assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
@@ -947,10 +1290,75 @@ mod tests {
 
     #[test]
     fn test_type_record() {
-        let input = buffer(r#"(@interface type (record (field string) (field i32)))"#);
-        let output = Interface::Type(Type::Record(RecordType {
-            fields: vec1![IType::String, IType::I32],
-        }));
+        let input = buffer(r#"(@interface type (record $r (field $x: string field $y: i32)))"#);
+        let output = Interface::Type(Type::Record(Arc::new(IRecordType {
+            name: String::from("r"),
+            fields: NEVec::new(vec![
+                IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::String,
+                },
+                IRecordFieldType {
+                    name: Some(String::from("y")),
+                    ty: IType::I32,
+                },
+            ])
+            .unwrap(),
+        })));
+
+        assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
+    }
+
+    #[test]
+    fn test_type_variant() {
+        let input = buffer(r#"(@interface type (variant $v (case $x: string case $y)))"#);
+        let output = Interface::Type(Type::Variant(Arc::new(IVariantType {
+            name: String::from("v"),
+            cases: NEVec::new(vec![
+                IVariantCaseType {
+                    name: String::from("x"),
+                    ty: Some(IType::String),
+                },
+                IVariantCaseType {
+                    name: String::from("y"),
+                    ty: None,
+                },
+            ])
+            .unwrap(),
+        })));
+
+        assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
+    }
+
+    #[test]
+    fn test_type_flags() {
+        let input = buffer(r#"(@interface type (flags $p (flag $read flag $write)))"#);
+        let output = Interface::Type(Type::Flags(Arc::new(IFlagsType {
+            name: String::from("p"),
+            flags: NEVec::new(vec![String::from("read"), String::from("write")]).unwrap(),
+        })));
+
+        assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
+    }
+
+    #[test]
+    fn test_type_enum() {
+        let input = buffer(r#"(@interface type (enum $s (case $ok case $failed)))"#);
+        let output = Interface::Type(Type::Enum(Arc::new(IEnumType {
+            name: String::from("s"),
+            members: NEVec::new(vec![String::from("ok"), String::from("failed")]).unwrap(),
+        })));
+
+        assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
+    }
+
+    #[test]
+    fn test_type_alias() {
+        let input = buffer(r#"(@interface type (alias $PeerId string))"#);
+        let output = Interface::Type(Type::Alias {
+            name: String::from("PeerId"),
+            ty: IType::String,
+        });
 
         assert_eq!(parser::parse::<Interface>(&input).unwrap(), output);
     }
@@ -1014,7 +1422,9 @@ mod tests {
     #[test]
     fn test_interfaces() {
         let input = buffer(
-            r#"(@interface type (func (param i32) (result s8)))
+            r#"(@interface it_version "0.2.0")
+
+(@interface type (func (param $x: i32) (result s8)))
 
 (@interface import "ns" "foo" (func (type 0)))
 
@@ -1025,9 +1435,13 @@ mod tests {
 (@interface implement (func 0) (func 1))"#,
         );
         let output = Interfaces {
+            version: semver::Version::new(0, 2, 0),
             types: vec![Type::Function {
-                inputs: vec![IType::I32],
-                outputs: vec![IType::S8],
+                arguments: Arc::new(vec![FunctionArg {
+                    name: String::from("x"),
+                    ty: IType::I32,
+                }]),
+                output_types: Arc::new(vec![IType::S8]),
             }],
             imports: vec![Import {
                 namespace: "ns",
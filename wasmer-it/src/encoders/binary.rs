@@ -17,6 +17,10 @@ where
         match self {
             TypeKind::Function => 0x00_u8.to_bytes(writer),
             TypeKind::Record => 0x01_u8.to_bytes(writer),
+            TypeKind::Variant => 0x02_u8.to_bytes(writer),
+            TypeKind::Flags => 0x03_u8.to_bytes(writer),
+            TypeKind::Enum => 0x04_u8.to_bytes(writer),
+            TypeKind::Alias => 0x05_u8.to_bytes(writer),
         }
     }
 }
@@ -70,6 +74,27 @@ where
                 TypeKind::Record.to_bytes(writer)?;
                 record_type.to_bytes(writer)?;
             }
+
+            Type::Variant(variant_type) => {
+                TypeKind::Variant.to_bytes(writer)?;
+                variant_type.to_bytes(writer)?;
+            }
+
+            Type::Flags(flags_type) => {
+                TypeKind::Flags.to_bytes(writer)?;
+                flags_type.to_bytes(writer)?;
+            }
+
+            Type::Enum(enum_type) => {
+                TypeKind::Enum.to_bytes(writer)?;
+                enum_type.to_bytes(writer)?;
+            }
+
+            Type::Alias { name, ty } => {
+                TypeKind::Alias.to_bytes(writer)?;
+                name.to_bytes(writer)?;
+                ty.to_bytes(writer)?;
+            }
         }
 
         Ok(())
@@ -230,35 +255,150 @@ where
             Instruction::I64FromU16 => 0x1f_u8.to_bytes(writer)?,
             Instruction::I64FromU32 => 0x20_u8.to_bytes(writer)?,
             Instruction::I64FromU64 => 0x21_u8.to_bytes(writer)?,
+            Instruction::CharFromI32 => 0x48_u8.to_bytes(writer)?,
+            Instruction::I32FromChar => 0x49_u8.to_bytes(writer)?,
+            Instruction::U128FromI64 => 0x4A_u8.to_bytes(writer)?,
+            Instruction::I64FromU128 => 0x4B_u8.to_bytes(writer)?,
+            Instruction::S128FromI64 => 0x4C_u8.to_bytes(writer)?,
+            Instruction::I64FromS128 => 0x4D_u8.to_bytes(writer)?,
 
-            Instruction::StringLiftMemory => 0x22_u8.to_bytes(writer)?,
-            Instruction::StringLowerMemory => 0x23_u8.to_bytes(writer)?,
+            // Instructions on the default memory keep their original opcodes without
+            // a memory index, so that binaries using one memory stay readable by older
+            // decoders; instructions on other memories use the `0x51..=0x5A` opcodes.
+            Instruction::StringLiftMemory { memory_index: 0 } => 0x22_u8.to_bytes(writer)?,
+            Instruction::StringLiftMemory { memory_index } => {
+                0x51_u8.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::StringLowerMemory { memory_index: 0 } => 0x23_u8.to_bytes(writer)?,
+            Instruction::StringLowerMemory { memory_index } => {
+                0x52_u8.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
             Instruction::StringSize => 0x24_u8.to_bytes(writer)?,
 
-            Instruction::ByteArrayLiftMemory => 0x43_u8.to_bytes(writer)?,
-            Instruction::ByteArrayLowerMemory => 0x44_u8.to_bytes(writer)?,
+            Instruction::ByteArrayLiftMemory { memory_index: 0 } => 0x43_u8.to_bytes(writer)?,
+            Instruction::ByteArrayLiftMemory { memory_index } => {
+                0x53_u8.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::ByteArrayLowerMemory { memory_index: 0 } => 0x44_u8.to_bytes(writer)?,
+            Instruction::ByteArrayLowerMemory { memory_index } => {
+                0x54_u8.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
             Instruction::ByteArraySize => 0x45_u8.to_bytes(writer)?,
 
-            Instruction::ArrayLiftMemory { value_type } => {
+            Instruction::ArrayLiftMemory {
+                value_type,
+                memory_index: 0,
+            } => {
                 0x37_u8.to_bytes(writer)?;
                 value_type.to_bytes(writer)?
             }
-            Instruction::ArrayLowerMemory { value_type } => {
+            Instruction::ArrayLiftMemory {
+                value_type,
+                memory_index,
+            } => {
+                0x55_u8.to_bytes(writer)?;
+                value_type.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::ArrayLowerMemory {
+                value_type,
+                memory_index: 0,
+            } => {
                 0x38_u8.to_bytes(writer)?;
                 value_type.to_bytes(writer)?
             }
+            Instruction::ArrayLowerMemory {
+                value_type,
+                memory_index,
+            } => {
+                0x56_u8.to_bytes(writer)?;
+                value_type.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
             Instruction::RecordLiftMemory {
                 record_type_id: type_index,
+                memory_index: 0,
             } => {
                 0x3A_u8.to_bytes(writer)?;
                 (*type_index as u64).to_bytes(writer)?
             }
+            Instruction::RecordLiftMemory {
+                record_type_id: type_index,
+                memory_index,
+            } => {
+                0x57_u8.to_bytes(writer)?;
+                (*type_index as u64).to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
             Instruction::RecordLowerMemory {
                 record_type_id: type_index,
+                memory_index: 0,
             } => {
                 0x3B_u8.to_bytes(writer)?;
                 (*type_index as u64).to_bytes(writer)?
             }
+            Instruction::RecordLowerMemory {
+                record_type_id: type_index,
+                memory_index,
+            } => {
+                0x58_u8.to_bytes(writer)?;
+                (*type_index as u64).to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::ResultLiftMemory {
+                ok_type,
+                err_type,
+                memory_index: 0,
+            } => {
+                0x46_u8.to_bytes(writer)?;
+                ok_type.to_bytes(writer)?;
+                err_type.to_bytes(writer)?
+            }
+            Instruction::ResultLiftMemory {
+                ok_type,
+                err_type,
+                memory_index,
+            } => {
+                0x59_u8.to_bytes(writer)?;
+                ok_type.to_bytes(writer)?;
+                err_type.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::ResultLowerMemory {
+                ok_type,
+                err_type,
+                memory_index: 0,
+            } => {
+                0x47_u8.to_bytes(writer)?;
+                ok_type.to_bytes(writer)?;
+                err_type.to_bytes(writer)?
+            }
+            Instruction::ResultLowerMemory {
+                ok_type,
+                err_type,
+                memory_index,
+            } => {
+                0x5A_u8.to_bytes(writer)?;
+                ok_type.to_bytes(writer)?;
+                err_type.to_bytes(writer)?;
+                (*memory_index as u64).to_bytes(writer)?
+            }
+            Instruction::HandleCreate { resource_id } => {
+                0x4E_u8.to_bytes(writer)?;
+                (*resource_id as u64).to_bytes(writer)?
+            }
+            Instruction::HandleBorrow { resource_id } => {
+                0x4F_u8.to_bytes(writer)?;
+                (*resource_id as u64).to_bytes(writer)?
+            }
+            Instruction::HandleDrop { resource_id } => {
+                0x50_u8.to_bytes(writer)?;
+                (*resource_id as u64).to_bytes(writer)?
+            }
             Instruction::Dup => 0x34_u8.to_bytes(writer)?,
             Instruction::Swap2 => 0x35_u8.to_bytes(writer)?,
             Instruction::PushI32 { value } => {
@@ -278,6 +418,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IEnumType;
+    use crate::IFlagsType;
+    use crate::IRecordFieldType;
+    use crate::IRecordType;
+    use crate::IType;
+    use crate::IValue;
+    use crate::IVariantCaseType;
+    use crate::IVariantType;
+    use crate::NEVec;
+
+    use std::sync::Arc;
 
     macro_rules! assert_to_bytes {
         ($expr:expr, $expected_output:expr) => {{
@@ -353,6 +504,7 @@ mod tests {
 
     #[test]
     fn test_interface_type() {
+        assert_to_bytes!(IType::Boolean, &[0x0b]);
         assert_to_bytes!(IType::S8, &[0x00]);
         assert_to_bytes!(IType::S16, &[0x01]);
         assert_to_bytes!(IType::S32, &[0x02]);
@@ -364,56 +516,206 @@ mod tests {
         assert_to_bytes!(IType::F32, &[0x08]);
         assert_to_bytes!(IType::F64, &[0x09]);
         assert_to_bytes!(IType::String, &[0x0a]);
-        assert_to_bytes!(IType::Anyref, &[0x0b]);
+        assert_to_bytes!(IType::ByteArray, &[0x3c]);
+        assert_to_bytes!(IType::Array(Box::new(IType::String)), &[0x36, 0x0a]);
         assert_to_bytes!(IType::I32, &[0x0c]);
         assert_to_bytes!(IType::I64, &[0x0d]);
+        assert_to_bytes!(IType::Record(1), &[0x0e, 0x01]);
+        assert_to_bytes!(IType::Optional(Box::new(IType::String)), &[0x0f, 0x0a]);
+        assert_to_bytes!(IType::Variant(1), &[0x10, 0x01]);
+        assert_to_bytes!(
+            IType::Result(Box::new(IType::String), Box::new(IType::U32)),
+            &[0x11, 0x0a, 0x06]
+        );
+        assert_to_bytes!(
+            IType::Map(Box::new(IType::String), Box::new(IType::U32)),
+            &[0x12, 0x0a, 0x06]
+        );
+        assert_to_bytes!(IType::Flags(1), &[0x13, 0x01]);
+        assert_to_bytes!(IType::Enum(1), &[0x14, 0x01]);
+        assert_to_bytes!(IType::Char, &[0x15]);
+        assert_to_bytes!(IType::U128, &[0x16]);
+        assert_to_bytes!(IType::S128, &[0x17]);
+        assert_to_bytes!(IType::Handle(1), &[0x18, 0x01]);
+        assert_to_bytes!(IType::Alias(1), &[0x19, 0x01]);
+    }
+
+    #[test]
+    fn test_ivalue() {
+        assert_to_bytes!(IValue::Boolean(true), &[0x0b, 0x01]);
+        assert_to_bytes!(IValue::S16(-2), &[0x01, 0xfe, 0xff]);
+        assert_to_bytes!(IValue::U32(1), &[0x06, 0x01, 0x00, 0x00, 0x00]);
+        assert_to_bytes!(IValue::String("ab".to_string()), &[0x0a, 0x02, 0x61, 0x62]);
+        assert_to_bytes!(IValue::ByteArray(vec![1, 2]), &[0x3c, 0x02, 0x01, 0x02]);
+        assert_to_bytes!(
+            IValue::Array(vec![IValue::U8(1), IValue::U8(2)]),
+            &[0x3c, 0x02, 0x01, 0x02]
+        );
+        assert_to_bytes!(
+            IValue::Array(vec![IValue::Char('a')]),
+            &[0x36, 0x01, 0x15, 0x61, 0x00, 0x00, 0x00]
+        );
+        assert_to_bytes!(
+            IValue::Record(NEVec::new(vec![IValue::Optional(None), IValue::Enum(2)]).unwrap()),
+            &[0x0e, 0x02, 0x0f, 0x00, 0x14, 0x02, 0x00, 0x00, 0x00]
+        );
+        assert_to_bytes!(
+            IValue::Variant(1, Some(Box::new(IValue::Handle(3)))),
+            &[0x10, 0x01, 0x01, 0x18, 0x03, 0x00, 0x00, 0x00]
+        );
         assert_to_bytes!(
-            IType::Record(RecordType {
-                fields: vec1![IType::String]
-            }),
-            &[0x0e, 0x01, 0x0a]
+            IValue::Result(Err(Box::new(IValue::S8(-1)))),
+            &[0x11, 0x01, 0x00, 0xff]
         );
+        assert_to_bytes!(
+            IValue::Map(vec![(IValue::U8(1), IValue::Boolean(false))]),
+            &[0x12, 0x01, 0x04, 0x01, 0x0b, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_ivalue_canonical_nan() {
+        let mut lhs = vec![];
+        let mut rhs = vec![];
+
+        IValue::F64(f64::NAN).to_bytes(&mut lhs).unwrap();
+        IValue::F64(-f64::NAN).to_bytes(&mut rhs).unwrap();
+
+        assert_eq!(lhs, rhs);
     }
 
     #[test]
     fn test_record_type() {
         assert_to_bytes!(
-            RecordType {
-                fields: vec1![IType::String]
+            IRecordType {
+                name: String::from("a"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::String,
+                }])
+                .unwrap(),
             },
             &[
+                0x01, // string of 1 byte
+                0x61, // "a"
                 0x01, // 1 field
+                0x01, // string of 1 byte
+                0x78, // "x"
                 0x0a, // String
             ]
         );
         assert_to_bytes!(
-            RecordType {
-                fields: vec1![IType::String, IType::I32]
+            IRecordType {
+                name: String::from("b"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: Some(String::from("x")),
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("y")),
+                        ty: IType::Record(1),
+                    },
+                ])
+                .unwrap(),
             },
             &[
+                0x01, // string of 1 byte
+                0x62, // "b"
                 0x02, // 2 fields
+                0x01, // string of 1 byte
+                0x78, // "x"
                 0x0a, // String
-                0x0c, // I32
+                0x01, // string of 1 byte
+                0x79, // "y"
+                0x0e, 0x01, // Record(1)
             ]
         );
         assert_to_bytes!(
-            RecordType {
-                fields: vec1![
-                    IType::String,
-                    IType::Record(RecordType {
-                        fields: vec1![IType::I32, IType::I32],
-                    }),
-                    IType::F64,
-                ],
+            IRecordType {
+                name: String::from("c"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: None,
+                    ty: IType::String,
+                }])
+                .unwrap(),
             },
             &[
-                0x03, // 3 fields
+                0x01, // string of 1 byte
+                0x63, // "c"
+                0x01, // 1 field
+                0x00, // empty string
                 0x0a, // String
-                0x0e, // Record
-                0x02, // 2 fields
-                0x0c, // I32
-                0x0c, // I32
-                0x09, // F64
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variant_type() {
+        assert_to_bytes!(
+            IVariantType {
+                name: String::from("a"),
+                cases: NEVec::new(vec![
+                    IVariantCaseType {
+                        name: String::from("x"),
+                        ty: Some(IType::String),
+                    },
+                    IVariantCaseType {
+                        name: String::from("y"),
+                        ty: None,
+                    },
+                ])
+                .unwrap(),
+            },
+            &[
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x02, // 2 cases
+                0x01, // string of 1 byte
+                0x78, // "x"
+                0x01, // with payload
+                0x0a, // String
+                0x01, // string of 1 byte
+                0x79, // "y"
+                0x00, // without payload
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flags_type() {
+        assert_to_bytes!(
+            IFlagsType {
+                name: String::from("p"),
+                flags: NEVec::new(vec![String::from("r"), String::from("w")]).unwrap(),
+            },
+            &[
+                0x01, // string of 1 byte
+                0x70, // "p"
+                0x02, // 2 flags
+                0x01, // string of 1 byte
+                0x72, // "r"
+                0x01, // string of 1 byte
+                0x77, // "w"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_type() {
+        assert_to_bytes!(
+            IEnumType {
+                name: String::from("s"),
+                members: NEVec::new(vec![String::from("a"), String::from("b")]).unwrap(),
+            },
+            &[
+                0x01, // string of 1 byte
+                0x73, // "s"
+                0x02, // 2 members
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x01, // string of 1 byte
+                0x62, // "b"
             ]
         );
     }
@@ -425,6 +727,7 @@ mod tests {
         assert_to_bytes!(InterfaceKind::Adapter, &[0x02]);
         assert_to_bytes!(InterfaceKind::Export, &[0x03]);
         assert_to_bytes!(InterfaceKind::Implementation, &[0x04]);
+        assert_to_bytes!(InterfaceKind::Version, &[0x05]);
     }
 
     #[test]
@@ -448,16 +751,20 @@ mod tests {
     fn test_type_function() {
         assert_to_bytes!(
             Type::Function {
-                inputs: vec![IType::I32, IType::I64],
-                outputs: vec![IType::S32],
+                arguments: Arc::new(vec![FunctionArg {
+                    name: String::from("a"),
+                    ty: IType::I32,
+                }]),
+                output_types: Arc::new(vec![IType::S32]),
             },
             &[
                 0x00, // function type
-                0x02, // list of 2 items
+                0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x61, // "a"
                 0x0c, // I32
-                0x0d, // I64
-                0x01, // list of 1 items
-                0x02, // I64
+                0x01, // list of 1 item
+                0x02, // S32
             ]
         );
     }
@@ -465,14 +772,98 @@ mod tests {
     #[test]
     fn test_type_record() {
         assert_to_bytes!(
-            Type::Record(RecordType {
-                fields: vec1![IType::I32, IType::I64],
-            }),
+            Type::Record(Arc::new(IRecordType {
+                name: String::from("a"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::I32,
+                }])
+                .unwrap(),
+            })),
             &[
                 0x01, // record type
-                0x02, // list of 2 items
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x78, // "x"
                 0x0c, // I32
-                0x0d, // I64
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_variant() {
+        assert_to_bytes!(
+            Type::Variant(Arc::new(IVariantType {
+                name: String::from("a"),
+                cases: NEVec::new(vec![IVariantCaseType {
+                    name: String::from("x"),
+                    ty: Some(IType::I32),
+                }])
+                .unwrap(),
+            })),
+            &[
+                0x02, // variant type
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x78, // "x"
+                0x01, // with payload
+                0x0c, // I32
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_flags() {
+        assert_to_bytes!(
+            Type::Flags(Arc::new(IFlagsType {
+                name: String::from("a"),
+                flags: NEVec::new(vec![String::from("x")]).unwrap(),
+            })),
+            &[
+                0x03, // flags type
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x78, // "x"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_enum() {
+        assert_to_bytes!(
+            Type::Enum(Arc::new(IEnumType {
+                name: String::from("a"),
+                members: NEVec::new(vec![String::from("x")]).unwrap(),
+            })),
+            &[
+                0x04, // enum type
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x78, // "x"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_alias() {
+        assert_to_bytes!(
+            Type::Alias {
+                name: String::from("a"),
+                ty: IType::String,
+            },
+            &[
+                0x05, // alias type
+                0x01, // string of 1 byte
+                0x61, // "a"
+                0x0a, // String
             ]
         );
     }
@@ -514,9 +905,13 @@ mod tests {
     fn test_interfaces() {
         assert_to_bytes!(
             Interfaces {
+                version: semver::Version::new(0, 2, 0),
                 types: vec![Type::Function {
-                    inputs: vec![IType::S8],
-                    outputs: vec![IType::S16],
+                    arguments: Arc::new(vec![FunctionArg {
+                        name: String::from("a"),
+                        ty: IType::S8,
+                    }]),
+                    output_types: Arc::new(vec![IType::S16]),
                 }],
                 imports: vec![Import {
                     namespace: "ab",
@@ -537,10 +932,16 @@ mod tests {
                 }],
             },
             &[
+                0x05, // version section
+                0x05, // string of 5 bytes
+                0x30, 0x2e, 0x32, 0x2e, 0x30, // "0.2.0"
+                //
                 0x00, // type section
                 0x01, // 1 type
                 0x00, // function type
                 0x01, // list of 1 item
+                0x01, // string of 1 byte
+                0x61, // "a"
                 0x00, // S8
                 0x01, // list of 1 item
                 0x01, // S16
@@ -611,14 +1012,39 @@ mod tests {
                 Instruction::I64FromU16,
                 Instruction::I64FromU32,
                 Instruction::I64FromU64,
-                Instruction::StringLiftMemory,
-                Instruction::StringLowerMemory,
+                Instruction::CharFromI32,
+                Instruction::I32FromChar,
+                Instruction::U128FromI64,
+                Instruction::I64FromU128,
+                Instruction::S128FromI64,
+                Instruction::I64FromS128,
+                Instruction::StringLiftMemory { memory_index: 0 },
+                Instruction::StringLowerMemory { memory_index: 1 },
                 Instruction::StringSize,
-                Instruction::RecordLift { type_index: 1 },
-                Instruction::RecordLower { type_index: 1 },
+                Instruction::RecordLiftMemory {
+                    record_type_id: 1,
+                    memory_index: 0,
+                },
+                Instruction::RecordLowerMemory {
+                    record_type_id: 1,
+                    memory_index: 1,
+                },
+                Instruction::ResultLiftMemory {
+                    ok_type: IType::String,
+                    err_type: IType::U32,
+                    memory_index: 0,
+                },
+                Instruction::ResultLowerMemory {
+                    ok_type: IType::String,
+                    err_type: IType::U32,
+                    memory_index: 1,
+                },
+                Instruction::HandleCreate { resource_id: 1 },
+                Instruction::HandleBorrow { resource_id: 1 },
+                Instruction::HandleDrop { resource_id: 1 },
             ],
             &[
-                0x27, // list of 39 items
+                0x32, // list of 50 items
                 0x00, 0x01, // ArgumentGet { index: 1 }
                 0x01, 0x01, // CallCore { function_index: 1 }
                 0x02, // S8FromI32
@@ -653,11 +1079,24 @@ mod tests {
                 0x1f, // I64FromU16
                 0x20, // I64FromU32
                 0x21, // I64FromU64
-                0x22, // StringLiftMemory
-                0x23, // StringLowerMemory
+                0x48, // CharFromI32
+                0x49, // I32FromChar
+                0x4a, // U128FromI64
+                0x4b, // I64FromU128
+                0x4c, // S128FromI64
+                0x4d, // I64FromS128
+                0x22, // StringLiftMemory { memory_index: 0 }
+                0x52, 0x01, // StringLowerMemory { memory_index: 1 }
                 0x24, // StringSize
-                0x025, 0x01, // RecordLift { type_index: 1 }
-                0x026, 0x01, // RecordLower { type_index: 1 }
+                0x3a, 0x01, // RecordLiftMemory { record_type_id: 1, memory_index: 0 }
+                0x58, 0x01, 0x01, // RecordLowerMemory { record_type_id: 1, memory_index: 1 }
+                0x46, 0x0a,
+                0x06, // ResultLiftMemory { ok_type: String, err_type: U32, memory_index: 0 }
+                0x5a, 0x0a, 0x06,
+                0x01, // ResultLowerMemory { ok_type: String, err_type: U32, memory_index: 1 }
+                0x4e, 0x01, // HandleCreate { resource_id: 1 }
+                0x4f, 0x01, // HandleBorrow { resource_id: 1 }
+                0x50, 0x01, // HandleDrop { resource_id: 1 }
             ]
         );
     }
@@ -3,17 +3,22 @@
 //! # Example
 //!
 //! ```rust
-//! use wasmer_interface_types::{
-//!     ast::{Adapter, Export, Implementation, Import, Interfaces, Type},
+//! use wasmer_interface_types_fl::{
+//!     ast::{Adapter, Export, FunctionArg, Implementation, Import, Interfaces, Type},
 //!     encoders::wat::*,
 //!     interpreter::Instruction,
-//!     types::IType,
+//!     IType,
 //! };
+//! use std::sync::Arc;
 //!
 //! let input: String = (&Interfaces {
+//!     version: semver::Version::new(0, 2, 0),
 //!     types: vec![Type::Function {
-//!         inputs: vec![IType::I32],
-//!         outputs: vec![IType::S8],
+//!         arguments: Arc::new(vec![FunctionArg {
+//!             name: String::from("x"),
+//!             ty: IType::I32,
+//!         }]),
+//!         output_types: Arc::new(vec![IType::S8]),
 //!     }],
 //!     imports: vec![Import {
 //!         namespace: "ns",
@@ -34,23 +39,23 @@
 //!     }],
 //! })
 //!     .to_string();
-//! let output = r#";; Types
-//! (@interface type (func
-//!   (param i32)
-//!   (result s8)))
+//! let output = "(@interface it_version \"0.2.0\")
+//!
+//! ;; Types
+//! (@interface type (func \n  (param $x: i32) \n  (result s8)))   ;; 0
 //!
 //! ;; Imports
-//! (@interface import "ns" "foo" (func (type 0)))
+//! (@interface import \"ns\" \"foo\" (func (type 0)))
 //!
 //! ;; Adapters
 //! (@interface func (type 0)
 //!   arg.get 42)
 //!
 //! ;; Exports
-//! (@interface export "bar" (func 0))
+//! (@interface export \"bar\" (func 0))
 //!
 //! ;; Implementations
-//! (@interface implement (func 0) (func 1))"#;
+//! (@interface implement (func 0) (func 1))";
 //!
 //! assert_eq!(input, output);
 //! ```
@@ -99,26 +104,93 @@ impl ToString for &Instruction {
             Instruction::I64FromU16 => "i64.from_u16".into(),
             Instruction::I64FromU32 => "i64.from_u32".into(),
             Instruction::I64FromU64 => "i64.from_u64".into(),
-            Instruction::StringLiftMemory => "string.lift_memory".into(),
-            Instruction::StringLowerMemory => "string.lower_memory".into(),
+            Instruction::CharFromI32 => "char.from_i32".into(),
+            Instruction::I32FromChar => "i32.from_char".into(),
+            Instruction::U128FromI64 => "u128.from_i64".into(),
+            Instruction::I64FromU128 => "i64.from_u128".into(),
+            Instruction::S128FromI64 => "s128.from_i64".into(),
+            Instruction::I64FromS128 => "i64.from_s128".into(),
+            Instruction::StringLiftMemory { memory_index } => {
+                format!("string.lift_memory{}", encode_memory_index(*memory_index))
+            }
+            Instruction::StringLowerMemory { memory_index } => {
+                format!("string.lower_memory{}", encode_memory_index(*memory_index))
+            }
             Instruction::StringSize => "string.size".into(),
 
-            Instruction::ByteArrayLiftMemory => "byte_array.lift_memory".into(),
-            Instruction::ByteArrayLowerMemory => "byte_array.lower_memory".into(),
-            Instruction::ByteArraySize => "byte_array.size".into(),
-
-            Instruction::ArrayLiftMemory { value_type } => {
-                format!("array.lift_memory {}", value_type.to_string())
+            Instruction::ByteArrayLiftMemory { memory_index } => {
+                format!(
+                    "byte_array.lift_memory{}",
+                    encode_memory_index(*memory_index)
+                )
             }
-            Instruction::ArrayLowerMemory { value_type } => {
-                format!("array.lower_memory {}", value_type.to_string())
+            Instruction::ByteArrayLowerMemory { memory_index } => {
+                format!(
+                    "byte_array.lower_memory{}",
+                    encode_memory_index(*memory_index)
+                )
             }
+            Instruction::ByteArraySize => "byte_array.size".into(),
+
+            Instruction::ArrayLiftMemory {
+                value_type,
+                memory_index,
+            } => format!(
+                "array.lift_memory {}{}",
+                value_type.to_string(),
+                encode_memory_index(*memory_index)
+            ),
+            Instruction::ArrayLowerMemory {
+                value_type,
+                memory_index,
+            } => format!(
+                "array.lower_memory {}{}",
+                value_type.to_string(),
+                encode_memory_index(*memory_index)
+            ),
             Instruction::RecordLiftMemory {
                 record_type_id: type_index,
-            } => format!("record.lift_memory {}", type_index),
+                memory_index,
+            } => format!(
+                "record.lift_memory {}{}",
+                type_index,
+                encode_memory_index(*memory_index)
+            ),
             Instruction::RecordLowerMemory {
                 record_type_id: type_index,
-            } => format!("record.lower_memory {}", type_index),
+                memory_index,
+            } => format!(
+                "record.lower_memory {}{}",
+                type_index,
+                encode_memory_index(*memory_index)
+            ),
+            Instruction::ResultLiftMemory {
+                ok_type,
+                err_type,
+                memory_index,
+            } => format!(
+                "result.lift_memory {} {}{}",
+                ok_type.to_string(),
+                err_type.to_string(),
+                encode_memory_index(*memory_index)
+            ),
+            Instruction::ResultLowerMemory {
+                ok_type,
+                err_type,
+                memory_index,
+            } => format!(
+                "result.lower_memory {} {}{}",
+                ok_type.to_string(),
+                err_type.to_string(),
+                encode_memory_index(*memory_index)
+            ),
+            Instruction::HandleCreate { resource_id } => {
+                format!("handle.create {}", resource_id)
+            }
+            Instruction::HandleBorrow { resource_id } => {
+                format!("handle.borrow {}", resource_id)
+            }
+            Instruction::HandleDrop { resource_id } => format!("handle.drop {}", resource_id),
             Instruction::Dup => "dup".into(),
             Instruction::Swap2 => "swap2".into(),
             Instruction::PushI32 { value } => format!("i32.push {}", value),
@@ -127,6 +199,15 @@ impl ToString for &Instruction {
     }
 }
 
+/// Encode the memory index of a memory instruction, it's omitted for the default memory.
+fn encode_memory_index(memory_index: u32) -> String {
+    if memory_index as usize == it_lilo::traits::DEFAULT_MEMORY_INDEX {
+        String::new()
+    } else {
+        format!(" {}", memory_index)
+    }
+}
+
 /// Encode a list of `IType` representing inputs into a
 /// string.
 fn encode_function_arguments(arguments: &[FunctionArg]) -> String {
@@ -186,6 +267,27 @@ impl<'input> ToString for &Type {
                 r#"(@interface type ({record_type}))"#,
                 record_type = record_type.as_ref().to_string(),
             ),
+
+            Type::Variant(variant_type) => format!(
+                r#"(@interface type ({variant_type}))"#,
+                variant_type = variant_type.as_ref().to_string(),
+            ),
+
+            Type::Flags(flags_type) => format!(
+                r#"(@interface type ({flags_type}))"#,
+                flags_type = flags_type.as_ref().to_string(),
+            ),
+
+            Type::Enum(enum_type) => format!(
+                r#"(@interface type ({enum_type}))"#,
+                enum_type = enum_type.as_ref().to_string(),
+            ),
+
+            Type::Alias { name, ty } => format!(
+                r#"(@interface type (alias ${name} {ty}))"#,
+                name = name,
+                ty = ty.to_string(),
+            ),
         }
     }
 }
@@ -343,10 +445,20 @@ impl<'input> ToString for &Interfaces<'input> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IEnumType;
+    use crate::IFlagsType;
+    use crate::IRecordFieldType;
+    use crate::IRecordType;
+    use crate::IVariantCaseType;
+    use crate::IVariantType;
+    use crate::NEVec;
+
+    use std::sync::Arc;
 
     #[test]
     fn test_interface_types() {
         let inputs: Vec<String> = vec![
+            (&IType::Boolean).to_string(),
             (&IType::S8).to_string(),
             (&IType::S16).to_string(),
             (&IType::S32).to_string(),
@@ -358,14 +470,25 @@ mod tests {
             (&IType::F32).to_string(),
             (&IType::F64).to_string(),
             (&IType::String).to_string(),
+            (&IType::ByteArray).to_string(),
+            (&IType::Array(Box::new(IType::String))).to_string(),
             (&IType::I32).to_string(),
             (&IType::I64).to_string(),
-            (&IType::Record(RecordType {
-                fields: vec1![IType::String],
-            }))
-                .to_string(),
+            (&IType::Record(1)).to_string(),
+            (&IType::Optional(Box::new(IType::String))).to_string(),
+            (&IType::Variant(1)).to_string(),
+            (&IType::Result(Box::new(IType::String), Box::new(IType::U32))).to_string(),
+            (&IType::Map(Box::new(IType::String), Box::new(IType::U32))).to_string(),
+            (&IType::Flags(1)).to_string(),
+            (&IType::Enum(2)).to_string(),
+            (&IType::Char).to_string(),
+            (&IType::U128).to_string(),
+            (&IType::S128).to_string(),
+            (&IType::Handle(3)).to_string(),
+            (&IType::Alias(4)).to_string(),
         ];
         let outputs = vec![
+            "bool",
             "s8",
             "s16",
             "s32",
@@ -377,10 +500,22 @@ mod tests {
             "f32",
             "f64",
             "string",
-            "anyref",
+            "array (u8)",
+            "array (string)",
             "i32",
             "i64",
-            "record (field string)",
+            "record 1",
+            "option (string)",
+            "variant 1",
+            "result (string) (u32)",
+            "map (string) (u32)",
+            "flags 1",
+            "enum 2",
+            "char",
+            "u128",
+            "s128",
+            "handle 3",
+            "alias 4",
         ];
 
         assert_eq!(inputs, outputs);
@@ -389,34 +524,115 @@ mod tests {
     #[test]
     fn test_record_type() {
         let inputs = vec![
-            (&RecordType {
-                fields: vec1![IType::String],
+            (&IRecordType {
+                name: String::from("a"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::String,
+                }])
+                .unwrap(),
             })
                 .to_string(),
-            (&RecordType {
-                fields: vec1![IType::String, IType::I32],
+            (&IRecordType {
+                name: String::from("b"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: Some(String::from("x")),
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: Some(String::from("y")),
+                        ty: IType::Record(0),
+                    },
+                ])
+                .unwrap(),
             })
                 .to_string(),
-            (&RecordType {
-                fields: vec1![
-                    IType::String,
-                    IType::Record(RecordType {
-                        fields: vec1![IType::I32, IType::I32],
-                    }),
-                    IType::F64,
-                ],
+            (&IRecordType {
+                name: String::from("c"),
+                fields: NEVec::new(vec![
+                    IRecordFieldType {
+                        name: None,
+                        ty: IType::String,
+                    },
+                    IRecordFieldType {
+                        name: None,
+                        ty: IType::I32,
+                    },
+                ])
+                .unwrap(),
             })
                 .to_string(),
         ];
         let outputs = vec![
-            "record (field string)",
-            "record (field string) (field i32)",
-            "record (field string) (field record (field i32) (field i32)) (field f64)",
+            "record $a (\n field $x: string\n)",
+            "record $b (\n field $x: string\n field $y: record 0\n)",
+            "record $c (\n field string\n field i32\n)",
         ];
 
         assert_eq!(inputs, outputs);
     }
 
+    #[test]
+    fn test_variant_type() {
+        let inputs = vec![
+            (&IVariantType {
+                name: String::from("a"),
+                cases: NEVec::new(vec![IVariantCaseType {
+                    name: String::from("x"),
+                    ty: None,
+                }])
+                .unwrap(),
+            })
+                .to_string(),
+            (&IVariantType {
+                name: String::from("b"),
+                cases: NEVec::new(vec![
+                    IVariantCaseType {
+                        name: String::from("x"),
+                        ty: Some(IType::String),
+                    },
+                    IVariantCaseType {
+                        name: String::from("y"),
+                        ty: None,
+                    },
+                ])
+                .unwrap(),
+            })
+                .to_string(),
+        ];
+        let outputs = vec![
+            "variant $a (\n case $x\n)",
+            "variant $b (\n case $x: string\n case $y\n)",
+        ];
+
+        assert_eq!(inputs, outputs);
+    }
+
+    #[test]
+    fn test_flags_type() {
+        let input = (&IFlagsType {
+            name: String::from("p"),
+            flags: NEVec::new(vec![String::from("read"), String::from("write")]).unwrap(),
+        })
+            .to_string();
+        let output = "flags $p (\n flag $read\n flag $write\n)";
+
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn test_enum_type() {
+        let input = (&IEnumType {
+            name: String::from("s"),
+            members: NEVec::new(vec![String::from("ok"), String::from("failed")]).unwrap(),
+        })
+            .to_string();
+        let output = "enum $s (\n case $ok\n case $failed\n)";
+
+        assert_eq!(input, output);
+    }
+
     #[test]
     fn test_instructions() {
         let inputs: Vec<String> = vec![
@@ -454,13 +670,40 @@ mod tests {
             (&Instruction::I64FromU16).to_string(),
             (&Instruction::I64FromU32).to_string(),
             (&Instruction::I64FromU64).to_string(),
-            (&Instruction::StringLiftMemory).to_string(),
-            (&Instruction::StringLowerMemory).to_string(),
+            (&Instruction::CharFromI32).to_string(),
+            (&Instruction::I32FromChar).to_string(),
+            (&Instruction::U128FromI64).to_string(),
+            (&Instruction::I64FromU128).to_string(),
+            (&Instruction::S128FromI64).to_string(),
+            (&Instruction::I64FromS128).to_string(),
+            (&Instruction::StringLiftMemory { memory_index: 0 }).to_string(),
+            (&Instruction::StringLowerMemory { memory_index: 1 }).to_string(),
             (&Instruction::StringSize).to_string(),
-            /*
-            (&Instruction::RecordLift { type_index: 42 }).to_string(),
-            (&Instruction::RecordLower { type_index: 42 }).to_string(),
-             */
+            (&Instruction::RecordLiftMemory {
+                record_type_id: 42,
+                memory_index: 0,
+            })
+                .to_string(),
+            (&Instruction::RecordLowerMemory {
+                record_type_id: 42,
+                memory_index: 1,
+            })
+                .to_string(),
+            (&Instruction::ResultLiftMemory {
+                ok_type: IType::String,
+                err_type: IType::U32,
+                memory_index: 0,
+            })
+                .to_string(),
+            (&Instruction::ResultLowerMemory {
+                ok_type: IType::String,
+                err_type: IType::U32,
+                memory_index: 1,
+            })
+                .to_string(),
+            (&Instruction::HandleCreate { resource_id: 42 }).to_string(),
+            (&Instruction::HandleBorrow { resource_id: 42 }).to_string(),
+            (&Instruction::HandleDrop { resource_id: 42 }).to_string(),
         ];
         let outputs = vec![
             "arg.get 7",
@@ -497,11 +740,22 @@ mod tests {
             "i64.from_u16",
             "i64.from_u32",
             "i64.from_u64",
+            "char.from_i32",
+            "i32.from_char",
+            "u128.from_i64",
+            "i64.from_u128",
+            "s128.from_i64",
+            "i64.from_s128",
             "string.lift_memory",
-            "string.lower_memory",
+            "string.lower_memory 1",
             "string.size",
-            "record.lift 42",
-            "record.lower 42",
+            "record.lift_memory 42",
+            "record.lower_memory 42 1",
+            "result.lift_memory string u32",
+            "result.lower_memory string u32 1",
+            "handle.create 42",
+            "handle.borrow 42",
+            "handle.drop 42",
         ];
 
         assert_eq!(inputs, outputs);
@@ -511,40 +765,60 @@ mod tests {
     fn test_types() {
         let inputs: Vec<String> = vec![
             (&Type::Function {
-                inputs: vec![IType::I32, IType::F32],
-                outputs: vec![IType::I32],
+                arguments: Arc::new(vec![FunctionArg {
+                    name: String::from("x"),
+                    ty: IType::I32,
+                }]),
+                output_types: Arc::new(vec![IType::I32]),
             })
                 .to_string(),
             (&Type::Function {
-                inputs: vec![IType::I32],
-                outputs: vec![],
+                arguments: Arc::new(vec![]),
+                output_types: Arc::new(vec![]),
             })
                 .to_string(),
-            (&Type::Function {
-                inputs: vec![],
-                outputs: vec![IType::I32],
-            })
+            (&Type::Record(Arc::new(IRecordType {
+                name: String::from("r"),
+                fields: NEVec::new(vec![IRecordFieldType {
+                    name: Some(String::from("x")),
+                    ty: IType::String,
+                }])
+                .unwrap(),
+            })))
                 .to_string(),
-            (&Type::Function {
-                inputs: vec![],
-                outputs: vec![],
-            })
+            (&Type::Variant(Arc::new(IVariantType {
+                name: String::from("v"),
+                cases: NEVec::new(vec![IVariantCaseType {
+                    name: String::from("x"),
+                    ty: Some(IType::String),
+                }])
+                .unwrap(),
+            })))
                 .to_string(),
-            (&Type::Record(RecordType {
-                fields: vec1![IType::String, IType::I32],
-            }))
+            (&Type::Flags(Arc::new(IFlagsType {
+                name: String::from("p"),
+                flags: NEVec::new(vec![String::from("read")]).unwrap(),
+            })))
+                .to_string(),
+            (&Type::Enum(Arc::new(IEnumType {
+                name: String::from("s"),
+                members: NEVec::new(vec![String::from("ok")]).unwrap(),
+            })))
+                .to_string(),
+            (&Type::Alias {
+                name: String::from("PeerId"),
+                ty: IType::Optional(Box::new(IType::String)),
+            })
                 .to_string(),
         ];
         let outputs = vec![
-            r#"(@interface type (func
-  (param i32 f32)
-  (result i32)))"#,
-            r#"(@interface type (func
-  (param i32)))"#,
-            r#"(@interface type (func
-  (result i32)))"#,
-            r#"(@interface type (func))"#,
-            r#"(@interface type (record (field string) (field i32)))"#,
+            "(@interface type (func \n  (param $x: i32) \n  (result i32)))",
+            "(@interface type (func  ))",
+            "(@interface type (record $r (\n field $x: string\n)))",
+            "(@interface type (variant $v (\n case $x: string\n)))",
+            "(@interface type (flags $p (\n flag $read\n)))",
+            "(@interface type (enum $s (\n case $ok\n)))",
+            "(@interface type (alias $PeerId option (string)))",
         ];
 
         assert_eq!(inputs, outputs);
@@ -591,9 +865,13 @@ mod tests {
     #[test]
     fn test_interfaces() {
         let input: String = (&Interfaces {
+            version: semver::Version::new(0, 2, 0),
             types: vec![Type::Function {
-                inputs: vec![IType::I32],
-                outputs: vec![IType::S8],
+                arguments: Arc::new(vec![FunctionArg {
+                    name: String::from("x"),
+                    ty: IType::I32,
+                }]),
+                output_types: Arc::new(vec![IType::S8]),
             }],
             imports: vec![Import {
                 namespace: "ns",
@@ -614,23 +892,23 @@ mod tests {
             }],
         })
             .to_string();
-        let output = r#";; Types
-(@interface type (func
-  (param i32)
-  (result s8)))
+        let output = "(@interface it_version \"0.2.0\")
+
+;; Types
+(@interface type (func \n  (param $x: i32) \n  (result s8)))   ;; 0
 
 ;; Imports
-(@interface import "ns" "foo" (func (type 0)))
+(@interface import \"ns\" \"foo\" (func (type 0)))
 
 ;; Adapters
 (@interface func (type 0)
   arg.get 42)
 
 ;; Exports
-(@interface export "bar" (func 0))
+(@interface export \"bar\" (func 0))
 
 ;; Implementations
-(@interface implement (func 0) (func 1))"#;
+(@interface implement (func 0) (func 1))";
 
         assert_eq!(input, output);
     }
@@ -3,7 +3,7 @@
 
 use crate::IType;
 use crate::IValue;
-use crate::{ast::TypeKind, interpreter::Instruction};
+use crate::{ast::TypeKind, interpreter::handles::HandleError, interpreter::Instruction};
 use std::{
     error::Error,
     fmt::{self, Display, Formatter},
@@ -14,6 +14,7 @@ use std::{
 
 use it_lilo::lifter::LiError;
 use it_lilo::lowerer::LoError;
+use it_lilo::type_checker::TypeCheckError;
 use thiserror::Error as ThisError;
 
 pub use fluence_it_types::WasmValueNativeCastError;
@@ -68,7 +69,7 @@ impl InstructionError {
             } => Self::from_error_kind(
                 instruction.clone(),
                 InstructionErrorKind::MemoryOutOfBoundsAccess {
-                    index: offset + size,
+                    index: offset.saturating_add(size),
                     length: memory_size,
                 },
             ),
@@ -111,7 +112,7 @@ pub enum InstructionErrorKind {
     },
 
     /// Failed to cast from a WIT value to a native value.
-    #[error("failed to cast the WIT value `{0}` to its native type")]
+    #[error("failed to cast a WIT value to its native type: {0}")]
     ToNative(#[from] WasmValueNativeCastError),
 
     /// Failed to cast from `from` to `to`.
@@ -193,10 +194,10 @@ pub enum InstructionErrorKind {
     #[error("read out of the memory bounds (index {index} > memory length {length})")]
     MemoryOutOfBoundsAccess {
         /// The access index.
-        index: u32,
+        index: u64,
 
         /// The memory length.
-        length: u32,
+        length: u64,
     },
 
     /// The string contains invalid UTF-8 encoding.
@@ -224,6 +225,35 @@ pub enum InstructionErrorKind {
         record_type_id: u64,
     },
 
+    /// The searched by id variant type doesn't exist.
+    #[error("variant type with `{variant_type_id}` is missing in a Wasm binary")]
+    VariantTypeByNameIsMissing {
+        /// The variant type id.
+        variant_type_id: u64,
+    },
+
+    /// The searched by id flags type doesn't exist.
+    #[error("flags type with `{flags_type_id}` is missing in a Wasm binary")]
+    FlagsTypeByNameIsMissing {
+        /// The flags type id.
+        flags_type_id: u64,
+    },
+
+    /// The searched by id enum type doesn't exist.
+    #[error("enum type with `{enum_type_id}` is missing in a Wasm binary")]
+    EnumTypeByNameIsMissing {
+        /// The enum type id.
+        enum_type_id: u64,
+    },
+
+    /// The instance doesn't provide a handle table.
+    #[error("handle table is missing")]
+    HandleTableIsMissing,
+
+    /// Errors related to handles of host objects.
+    #[error("{0}")]
+    HandleError(#[from] HandleError),
+
     /// Corrupted array's been popped from the stack.
     #[error("{0}")]
     CorruptedArray(String),
@@ -249,6 +279,10 @@ pub enum InstructionErrorKind {
     #[error("serde error: {0}")]
     SerdeError(String),
 
+    /// A value doesn't match its interface type.
+    #[error("{0}")]
+    TypeCheck(#[from] TypeCheckError),
+
     /// Errors related to lifting/lowering records.
     #[error("{0}")]
     LiError(#[from] LiError),
@@ -0,0 +1,191 @@
+//! A host-side table of handles to opaque host objects.
+//!
+//! Guests never see host objects directly, they only get handles — indices
+//! into this table. Every handle is checked against the table before use,
+//! so a guest can't forge a handle or keep using a dropped one.
+
+use std::collections::HashMap;
+use thiserror::Error as ThisError;
+
+/// Errors that could occur while operating on a [`HandleTable`].
+#[derive(ThisError, Debug, Clone, PartialEq, Eq)]
+pub enum HandleError {
+    /// The handle has never been created or has been already dropped.
+    #[error("handle {handle} doesn't exist, it's either forged or has been already dropped")]
+    UnknownHandle {
+        /// The handle that was looked up.
+        handle: u32,
+    },
+
+    /// The handle refers to an object of another resource type.
+    #[error("handle {handle} refers to resource {actual_resource_id}, but resource {expected_resource_id} was expected")]
+    ResourceMismatch {
+        /// The handle that was looked up.
+        handle: u32,
+
+        /// The resource type the handle was expected to have.
+        expected_resource_id: u64,
+
+        /// The resource type the handle actually has.
+        actual_resource_id: u64,
+    },
+
+    /// All handle indices have been used up.
+    #[error("handle table is full")]
+    TableIsFull,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HandleEntry {
+    resource_id: u64,
+    rep: u32,
+}
+
+/// A table mapping handles to host object representations.
+///
+/// A representation (`rep`) is an opaque `u32` chosen by the host, usually
+/// an index into its own storage of database connections, files, etc.
+/// Handles are never reused, so a stale handle can't alias a newer object,
+/// and `0` is never a valid handle.
+#[derive(Debug, Clone)]
+pub struct HandleTable {
+    entries: HashMap<u32, HandleEntry>,
+    next_handle: u32,
+}
+
+impl Default for HandleTable {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            next_handle: 1,
+        }
+    }
+}
+
+impl HandleTable {
+    /// Creates a new empty handle table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new handle of the `resource_id` type pointing to `rep`.
+    pub fn insert(&mut self, resource_id: u64, rep: u32) -> Result<u32, HandleError> {
+        let handle = self.next_handle;
+        self.next_handle = handle.checked_add(1).ok_or(HandleError::TableIsFull)?;
+        self.entries
+            .insert(handle, HandleEntry { resource_id, rep });
+
+        Ok(handle)
+    }
+
+    /// Returns the representation behind `handle`, checking that it's
+    /// alive and has the `resource_id` type. The handle stays alive.
+    pub fn get(&self, handle: u32, resource_id: u64) -> Result<u32, HandleError> {
+        let entry = self.entry(handle, resource_id)?;
+
+        Ok(entry.rep)
+    }
+
+    /// Drops `handle` and returns the representation it pointed to,
+    /// checking that it's alive and has the `resource_id` type.
+    pub fn remove(&mut self, handle: u32, resource_id: u64) -> Result<u32, HandleError> {
+        let entry = self.entry(handle, resource_id)?;
+        self.entries.remove(&handle);
+
+        Ok(entry.rep)
+    }
+
+    /// Returns the number of alive handles.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no alive handles.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry(&self, handle: u32, resource_id: u64) -> Result<HandleEntry, HandleError> {
+        let entry = *self
+            .entries
+            .get(&handle)
+            .ok_or(HandleError::UnknownHandle { handle })?;
+
+        if entry.resource_id != resource_id {
+            return Err(HandleError::ResourceMismatch {
+                handle,
+                expected_resource_id: resource_id,
+                actual_resource_id: entry.resource_id,
+            });
+        }
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut table = HandleTable::new();
+        let handle = table.insert(1, 42).unwrap();
+
+        assert_ne!(handle, 0);
+        assert_eq!(table.get(handle, 1), Ok(42));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.remove(handle, 1), Ok(42));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_forged_handle() {
+        let mut table = HandleTable::new();
+        let handle = table.insert(1, 42).unwrap();
+
+        assert_eq!(
+            table.get(handle + 1, 1),
+            Err(HandleError::UnknownHandle { handle: handle + 1 })
+        );
+        assert_eq!(
+            table.get(0, 1),
+            Err(HandleError::UnknownHandle { handle: 0 })
+        );
+    }
+
+    #[test]
+    fn test_dropped_handle() {
+        let mut table = HandleTable::new();
+        let handle = table.insert(1, 42).unwrap();
+        table.remove(handle, 1).unwrap();
+
+        assert_eq!(
+            table.get(handle, 1),
+            Err(HandleError::UnknownHandle { handle })
+        );
+        assert_eq!(
+            table.remove(handle, 1),
+            Err(HandleError::UnknownHandle { handle })
+        );
+
+        let new_handle = table.insert(1, 42).unwrap();
+        assert_ne!(new_handle, handle);
+    }
+
+    #[test]
+    fn test_resource_mismatch() {
+        let mut table = HandleTable::new();
+        let handle = table.insert(1, 42).unwrap();
+
+        assert_eq!(
+            table.remove(handle, 2),
+            Err(HandleError::ResourceMismatch {
+                handle,
+                expected_resource_id: 2,
+                actual_resource_id: 1,
+            })
+        );
+        assert_eq!(table.get(handle, 1), Ok(42));
+    }
+}
@@ -2,7 +2,8 @@ use super::lilo;
 
 use crate::errors::InstructionResult;
 use crate::instr_error;
-use crate::interpreter::instructions::to_native;
+use crate::interpreter::instructions::pointer_to_ivalue;
+use crate::interpreter::instructions::to_native_pointer;
 use crate::interpreter::stack::Stackable;
 use crate::interpreter::{AsyncExecutableInstructionImpl, ExecutableInstruction, Runtime};
 use crate::{
@@ -13,7 +14,6 @@ use crate::{
 use it_lilo::lifter::ILifter;
 use it_lilo::lowerer::ILowerer;
 use it_lilo::lowerer::LoweredArray;
-use it_lilo::traits::DEFAULT_MEMORY_INDEX;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -21,10 +21,12 @@ use futures::FutureExt;
 struct ArrayLiftMemoryAsync {
     instruction: Instruction,
     value_type: IType,
+    memory_index: usize,
 }
 pub(crate) fn array_lift_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
     instruction: Instruction,
     value_type: IType,
+    memory_index: usize,
 ) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
 where
     Export: crate::interpreter::wasm::structures::Export,
@@ -43,6 +45,7 @@ where
     ExecutableInstruction::Async(Box::new(ArrayLiftMemoryAsync {
         instruction,
         value_type,
+        memory_index,
     }))
 }
 
@@ -78,20 +81,9 @@ where
                 )
             })?;
 
-            let offset = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
-
-            let size = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
-
-            log::trace!(
-                "array.lift_memory: lifting memory for value type: {:?}, popped offset {}, size {}",
-                value_type,
-                offset,
-                size
-            );
-
             let instance = &mut runtime.wasm_instance;
 
-            let memory_index = DEFAULT_MEMORY_INDEX;
+            let memory_index = self.memory_index;
             let memory_view = instance
                 .memory(memory_index)
                 .ok_or_else(|| {
@@ -102,8 +94,21 @@ where
                 })?
                 .view();
 
+            let address_type = memory_view.address_type();
+            let offset = to_native_pointer(inputs.remove(0), address_type, instruction.clone())?;
+            let size = to_native_pointer(inputs.remove(0), address_type, instruction.clone())?;
+
+            log::trace!(
+                "array.lift_memory: lifting memory for value type: {:?}, popped offset {}, size {}",
+                value_type,
+                offset,
+                size
+            );
+
             let li_helper = lilo::LiHelper::new(&**instance);
-            let lifter = ILifter::new(memory_view, &li_helper);
+            // handles read from memory must be known to the handle table
+            let check_handle = super::handle_check(&**instance);
+            let lifter = ILifter::new(memory_view, &li_helper).with_handle_check(&check_handle);
             let array = it_lilo::lifter::array_lift_memory(
                 runtime.store,
                 &lifter,
@@ -125,11 +130,13 @@ where
 struct ArrayLowerMemoryAsync {
     instruction: Instruction,
     value_type: IType,
+    memory_index: usize,
 }
 
 pub(crate) fn array_lower_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
     instruction: Instruction,
     value_type: IType,
+    memory_index: usize,
 ) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
 where
     Export: crate::interpreter::wasm::structures::Export,
@@ -148,6 +155,7 @@ where
     ExecutableInstruction::Async(Box::new(ArrayLowerMemoryAsync {
         instruction,
         value_type,
+        memory_index,
     }))
 }
 
@@ -198,7 +206,7 @@ where
                                 InstructionError::from_error_kind(instruction.clone(), e)
                             })?;
                     }
-                    let memory_index = DEFAULT_MEMORY_INDEX;
+                    let memory_index = self.memory_index;
                     let memory_view = instance
                         .memory(memory_index)
                         .ok_or_else(|| {
@@ -209,8 +217,9 @@ where
                         })?
                         .view();
 
+                    let address_type = memory_view.address_type();
                     let mut lo_helper = lilo::LoHelper::new(&**instance);
-                    let mut lowerer = ILowerer::new(memory_view, &mut lo_helper)
+                    let mut lowerer = ILowerer::new(memory_view, memory_index, &mut lo_helper)
                         .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
 
                     let LoweredArray { offset, size } =
@@ -223,14 +232,14 @@ where
                         offset,
                         size
                     );
-                    runtime.stack.push(IValue::I32(offset as _));
-                    runtime.stack.push(IValue::I32(size as _));
+                    runtime.stack.push(pointer_to_ivalue(offset, address_type));
+                    runtime.stack.push(pointer_to_ivalue(size, address_type));
 
                     Ok(())
                 }
                 IValue::ByteArray(bytearray) => {
                     let mut lo_helper = lilo::LoHelper::new(&**instance);
-                    let memory_index = DEFAULT_MEMORY_INDEX;
+                    let memory_index = self.memory_index;
                     let memory_view = instance
                         .memory(memory_index)
                         .ok_or_else(|| {
@@ -241,7 +250,8 @@ where
                         })?
                         .view();
 
-                    let mut lowerer = ILowerer::new(memory_view, &mut lo_helper)
+                    let address_type = memory_view.address_type();
+                    let mut lowerer = ILowerer::new(memory_view, memory_index, &mut lo_helper)
                         .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
 
                     let offset = lowerer
@@ -249,15 +259,15 @@ where
                         .write_bytes(runtime.store, &bytearray)
                         .await
                         .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
-                    let size = bytearray.len();
+                    let size = bytearray.len() as u64;
 
                     log::trace!(
                         "array.lower_memory: pushing bytes {}, {} on the stack",
                         offset,
                         size
                     );
-                    runtime.stack.push(IValue::I32(offset as _));
-                    runtime.stack.push(IValue::I32(size as _));
+                    runtime.stack.push(pointer_to_ivalue(offset, address_type));
+                    runtime.stack.push(pointer_to_ivalue(size, address_type));
 
                     Ok(())
                 }
@@ -273,3 +283,208 @@ where
         .boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    test_executable_instruction!(
+        test_array__roundtrip_s16 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory { value_type: IType::S16, memory_index: 0 },
+                Instruction::ArrayLiftMemory { value_type: IType::S16, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::Array(vec![IValue::S16(-1), IValue::S16(2), IValue::S16(300)])],
+            instance: Instance::new(),
+            stack: [IValue::Array(vec![IValue::S16(-1), IValue::S16(2), IValue::S16(300)])],
+    );
+
+    test_executable_instruction!(
+        test_array__roundtrip_bool_and_char =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory { value_type: IType::Boolean, memory_index: 0 },
+                Instruction::ArrayLiftMemory { value_type: IType::Boolean, memory_index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLowerMemory { value_type: IType::Char, memory_index: 0 },
+                Instruction::ArrayLiftMemory { value_type: IType::Char, memory_index: 0 },
+            ],
+            invocation_inputs: [
+                IValue::Array(vec![IValue::Boolean(true), IValue::Boolean(false)]),
+                IValue::Array(vec![IValue::Char('a'), IValue::Char('ё')]),
+            ],
+            instance: Instance::new(),
+            stack: [
+                IValue::Array(vec![IValue::Boolean(true), IValue::Boolean(false)]),
+                IValue::Array(vec![IValue::Char('a'), IValue::Char('ё')]),
+            ],
+    );
+
+    test_executable_instruction!(
+        test_array__roundtrip_u128_and_s128 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory { value_type: IType::U128, memory_index: 0 },
+                Instruction::ArrayLiftMemory { value_type: IType::U128, memory_index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLowerMemory { value_type: IType::S128, memory_index: 0 },
+                Instruction::ArrayLiftMemory { value_type: IType::S128, memory_index: 0 },
+            ],
+            invocation_inputs: [
+                IValue::Array(vec![IValue::U128(0), IValue::U128(u128::MAX)]),
+                IValue::Array(vec![IValue::S128(i128::MIN), IValue::S128(-1), IValue::S128(i128::MAX)]),
+            ],
+            instance: Instance::new(),
+            stack: [
+                IValue::Array(vec![IValue::U128(0), IValue::U128(u128::MAX)]),
+                IValue::Array(vec![IValue::S128(i128::MIN), IValue::S128(-1), IValue::S128(i128::MAX)]),
+            ],
+    );
+
+    test_executable_instruction!(
+        test_array_lift_memory__invalid_char =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLiftMemory { value_type: IType::Char, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0), IValue::I32(2)],
+            instance: Instance {
+                // the second char is a surrogate
+                memory: Memory::from(vec![0x61, 0, 0, 0, 0, 0xd8, 0, 0]),
+                ..Default::default()
+            },
+            error: "`array.lift_memory char` 0xd800 isn't a valid Unicode scalar value",
+    );
+
+    test_executable_instruction!(
+        test_array_lift_memory__char_out_of_range =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLiftMemory { value_type: IType::Char, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0), IValue::I32(1)],
+            instance: Instance {
+                // 0x110000 is above the last Unicode scalar value
+                memory: Memory::from(vec![0, 0, 0x11, 0]),
+                ..Default::default()
+            },
+            error: "`array.lift_memory char` 0x110000 isn't a valid Unicode scalar value",
+    );
+
+    test_executable_instruction!(
+        test_array__roundtrip_map_sorts_keys =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory {
+                    value_type: IType::Map(Box::new(IType::Char), Box::new(IType::U8)),
+                    memory_index: 0,
+                },
+                Instruction::ArrayLiftMemory {
+                    value_type: IType::Map(Box::new(IType::Char), Box::new(IType::U8)),
+                    memory_index: 0,
+                },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLowerMemory {
+                    value_type: IType::Map(Box::new(IType::Optional(Box::new(IType::U128))), Box::new(IType::U8)),
+                    memory_index: 0,
+                },
+                Instruction::ArrayLiftMemory {
+                    value_type: IType::Map(Box::new(IType::Optional(Box::new(IType::U128))), Box::new(IType::U8)),
+                    memory_index: 0,
+                },
+            ],
+            invocation_inputs: [
+                IValue::Array(vec![IValue::Map(vec![
+                    (IValue::Char('b'), IValue::U8(2)),
+                    (IValue::Char('a'), IValue::U8(1)),
+                ])]),
+                IValue::Array(vec![IValue::Map(vec![
+                    (IValue::Optional(Some(Box::new(IValue::U128(7)))), IValue::U8(2)),
+                    (IValue::Optional(None), IValue::U8(1)),
+                ])]),
+            ],
+            instance: Instance::new(),
+            stack: [
+                IValue::Array(vec![IValue::Map(vec![
+                    (IValue::Char('a'), IValue::U8(1)),
+                    (IValue::Char('b'), IValue::U8(2)),
+                ])]),
+                IValue::Array(vec![IValue::Map(vec![
+                    (IValue::Optional(None), IValue::U8(1)),
+                    (IValue::Optional(Some(Box::new(IValue::U128(7)))), IValue::U8(2)),
+                ])]),
+            ],
+    );
+
+    test_executable_instruction!(
+        test_array_lower_memory__duplicate_map_key =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory {
+                    value_type: IType::Map(Box::new(IType::Char), Box::new(IType::U8)),
+                    memory_index: 0,
+                },
+            ],
+            invocation_inputs: [
+                IValue::Array(vec![IValue::Map(vec![
+                    (IValue::Char('a'), IValue::U8(1)),
+                    (IValue::Char('b'), IValue::U8(2)),
+                    (IValue::Char('a'), IValue::U8(3)),
+                ])]),
+            ],
+            instance: Instance::new(),
+            error: "`array.lower_memory map (char) (u8)` map contains the key Char('a') more than once",
+    );
+
+    test_executable_instruction!(
+        test_array_lift_memory__handle =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLiftMemory { value_type: IType::Handle(3), memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0), IValue::I32(1)],
+            instance: Instance {
+                memory: Memory::from(vec![1, 0, 0, 0]),
+                handle_table: {
+                    let mut handle_table = crate::interpreter::handles::HandleTable::new();
+                    handle_table.insert(3, 42).unwrap();
+                    handle_table
+                },
+                ..Default::default()
+            },
+            stack: [IValue::Array(vec![IValue::Handle(1)])],
+    );
+
+    test_executable_instruction!(
+        test_array_lift_memory__forged_handle =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::ArrayLiftMemory { value_type: IType::Handle(3), memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0), IValue::I32(1)],
+            instance: Instance {
+                // the table is empty, so the guest made the handle up
+                memory: Memory::from(vec![7, 0, 0, 0]),
+                ..Default::default()
+            },
+            error: "`array.lift_memory handle 3` handle 7 doesn't exist, it's either forged or has been already dropped",
+    );
+
+    test_executable_instruction!(
+        test_array_lower_memory__second_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArrayLowerMemory { value_type: IType::S16, memory_index: 1 },
+            ],
+            invocation_inputs: [IValue::Array(vec![IValue::S16(-1), IValue::S16(2)])],
+            instance: Instance {
+                // the allocate export manages only the default memory
+                other_memories: vec![Memory::from(vec![0; 128])],
+                ..Instance::new()
+            },
+            error: "`array.lower_memory s16 1` memory `1` does not exist",
+    );
+}
@@ -1,4 +1,6 @@
+use super::pointer_to_ivalue;
 use super::to_native;
+use super::to_native_pointer;
 use crate::instr_error;
 use crate::IType;
 use crate::IValue;
@@ -8,19 +10,19 @@ use crate::{
     interpreter::Instruction,
     interpreter::Runtime,
 };
-
-use it_lilo::traits::DEFAULT_MEMORY_INDEX;
+use it_memory_traits::AddressType;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
 struct ByteArrayLiftMemoryAsync {
+    memory_index: usize,
     instruction: Instruction,
 }
 
 impl_async_executable_instruction!(
-    byte_array_lift_memory(instruction: Instruction) -> _ {
-        Box::new(ByteArrayLiftMemoryAsync{instruction})
+    byte_array_lift_memory(memory_index: usize, instruction: Instruction) -> _ {
+        Box::new(ByteArrayLiftMemoryAsync { memory_index, instruction })
     }
     ByteArrayLiftMemoryAsync {
         fn execute<'args>(&'args self, runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>)
@@ -33,7 +35,7 @@ impl_async_executable_instruction!(
                     )
                 })?;
 
-                let memory_index = DEFAULT_MEMORY_INDEX;
+                let memory_index = self.memory_index;
                 let memory = runtime
                     .wasm_instance
                     .memory(memory_index)
@@ -44,10 +46,10 @@ impl_async_executable_instruction!(
                         )
                     })?;
 
-                let pointer = to_native::<i32>(inputs.remove(0), self.instruction.clone())? as u32;
-                let length = to_native::<i32>(inputs.remove(0), self.instruction.clone())? as u32;
-
                 let memory_view = memory.view();
+                let address_type = memory_view.address_type();
+                let pointer = to_native_pointer(inputs.remove(0), address_type, self.instruction.clone())?;
+                let length = to_native_pointer(inputs.remove(0), address_type, self.instruction.clone())?;
 
                 if length == 0 {
                     runtime.stack.push(IValue::ByteArray(vec![]));
@@ -55,12 +57,10 @@ impl_async_executable_instruction!(
                     return Ok(())
                 }
 
-                memory_view
-                    .check_bounds(runtime.store, pointer, length)
+                let data = memory_view
+                    .try_read_vec(runtime.store, pointer, length)
                     .map_err(|e| InstructionError::from_memory_access(self.instruction.clone(), e))?;
 
-                let data = memory_view.read_vec(runtime.store, pointer, length);
-
                 log::debug!("byte_array.lift_memory: pushing {:?} on the stack", data);
                 runtime.stack.push(IValue::ByteArray(data));
 
@@ -71,12 +71,13 @@ impl_async_executable_instruction!(
 );
 
 struct ByteArrayLowerMemoryAsync {
+    memory_index: usize,
     instruction: Instruction,
 }
 
 impl_async_executable_instruction!(
-    byte_array_lower_memory(instruction: Instruction) -> _ {
-        Box::new(ByteArrayLowerMemoryAsync{instruction})
+    byte_array_lower_memory(memory_index: usize, instruction: Instruction) -> _ {
+        Box::new(ByteArrayLowerMemoryAsync { memory_index, instruction })
     }
 
     ByteArrayLowerMemoryAsync {
@@ -90,12 +91,12 @@ impl_async_executable_instruction!(
                     )
                 })?;
 
-                let array_pointer = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
+                let array_pointer = inputs.remove(0);
                 let array: Vec<u8> = to_native(inputs.remove(0), instruction.clone())?;
-                let length = array.len() as u32;
+                let length = array.len() as u64;
 
                 let instance = &mut runtime.wasm_instance;
-                let memory_index = DEFAULT_MEMORY_INDEX;
+                let memory_index = self.memory_index;
                 let memory_view = instance
                     .memory(memory_index)
                     .ok_or_else(|| {
@@ -106,15 +107,15 @@ impl_async_executable_instruction!(
                     })?
                     .view();
 
+                let address_type = memory_view.address_type();
+                let array_pointer = to_native_pointer(array_pointer, address_type, instruction.clone())?;
                 memory_view
-                    .check_bounds(runtime.store, array_pointer, array.len() as u32)
+                    .try_write_bytes(runtime.store, array_pointer, &array)
                     .map_err(|e| InstructionError::from_memory_access(instruction.clone(), e))?;
 
-                memory_view.write_bytes(runtime.store, array_pointer, &array);
-
                 log::debug!("string.lower_memory: pushing {}, {} on the stack", array_pointer, length);
-                runtime.stack.push(IValue::I32(array_pointer as i32));
-                runtime.stack.push(IValue::I32(length as i32));
+                runtime.stack.push(pointer_to_ivalue(array_pointer, address_type));
+                runtime.stack.push(pointer_to_ivalue(length, address_type));
 
                 Ok(())
             }.boxed()
@@ -135,12 +136,19 @@ impl_async_executable_instruction!(
         fn execute<'args>(&'args self, runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>) -> BoxFuture<InstructionResult<()>> {
             async move {
                 let instruction = &self.instruction;
+                // the size is sized like pointers of the default memory, I32 without a memory
+                let address_type = runtime
+                    .wasm_instance
+                    .memory_view(it_lilo::traits::DEFAULT_MEMORY_INDEX)
+                    .map(|memory_view| memory_view.address_type())
+                    .unwrap_or(AddressType::I32);
+
                 match runtime.stack.pop1() {
                     Some(IValue::ByteArray(array)) => {
-                        let length = array.len() as i32;
+                        let length = array.len() as u64;
 
                         log::debug!("byte_array.size: pushing {} on the stack", length);
-                        runtime.stack.push(IValue::I32(length));
+                        runtime.stack.push(pointer_to_ivalue(length, address_type));
 
                         Ok(())
                     },
@@ -148,10 +156,10 @@ impl_async_executable_instruction!(
                     Some(IValue::Array(array)) => {
                         let array = check_array_type(array, &instruction)?;
 
-                        let length = array.len() as i32;
+                        let length = array.len() as u64;
 
                         log::debug!("byte_array.size: pushing {} on the stack", length);
-                        runtime.stack.push(IValue::I32(length));
+                        runtime.stack.push(pointer_to_ivalue(length, address_type));
 
                         Ok(())
                     },
@@ -193,3 +201,39 @@ fn check_array_type(
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    test_executable_instruction!(
+        test_byte_array_size =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ByteArraySize,
+            ],
+            invocation_inputs: [IValue::ByteArray(vec![1, 2, 3])],
+            instance: Instance::new(),
+            stack: [IValue::I32(3)],
+    );
+
+    test_executable_instruction!(
+        test_byte_array_size__memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ByteArraySize,
+            ],
+            invocation_inputs: [IValue::ByteArray(vec![1, 2, 3])],
+            instance: Instance::with_address_type(it_memory_traits::AddressType::I64),
+            stack: [IValue::I64(3)],
+    );
+
+    test_executable_instruction!(
+        test_byte_array_size__array_of_u8_memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ByteArraySize,
+            ],
+            invocation_inputs: [IValue::Array(vec![IValue::U8(1), IValue::U8(2)])],
+            instance: Instance::with_address_type(it_memory_traits::AddressType::I64),
+            stack: [IValue::I64(2)],
+    );
+}
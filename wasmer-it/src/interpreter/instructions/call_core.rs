@@ -136,7 +136,7 @@ mod tests {
                 //              ^^^ mismatch with `42` signature
             ],
             instance: Instance::new(),
-            error: r#"`call-core 42` the local or import function `42` has the signature `[I32, I32] -> []` but it received values of kind `[I32, I64] -> []`"#,
+            error: r#"`call-core 42` expected a value of type 'i32', but I64(4) was found"#,
     );
 
     test_executable_instruction!(
@@ -155,19 +155,20 @@ mod tests {
                     let mut hashmap = HashMap::new();
                     hashmap.insert(
                         42,
-                        LocalImport {
-                            inputs: vec![IType::I32, IType::I32],
-                            outputs: vec![IType::I32],
-                            function: |_| Err(()),
-                            //            ^^^^^^^ function fails
-                        },
+                        LocalImport::new(
+                            "multiply",
+                            vec![IType::I32, IType::I32],
+                            vec![IType::I32],
+                            |_| Err(anyhow::anyhow!("multiply failed")),
+                            // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ function fails
+                        ),
                     );
 
                     hashmap
                 },
                 ..Default::default()
             },
-            error: r#"`call-core 42` failed while calling the local or import function `42`"#,
+            error: r#"`call-core 42` failed while calling the local or import function `multiply`: multiply failed"#,
     );
 
     test_executable_instruction!(
@@ -186,12 +187,13 @@ mod tests {
                     let mut hashmap = HashMap::new();
                     hashmap.insert(
                         42,
-                        LocalImport {
-                            inputs: vec![IType::I32, IType::I32],
-                            outputs: vec![IType::I32],
-                            function: |_| Ok(vec![]),
-                            //            ^^^^^^^^^^ void
-                        },
+                        LocalImport::new(
+                            "multiply",
+                            vec![IType::I32, IType::I32],
+                            vec![IType::I32],
+                            |_| Ok(vec![]),
+                            // ^^^^^^^^^^^^^^ void
+                        ),
                     );
 
                     hashmap
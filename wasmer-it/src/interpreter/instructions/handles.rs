@@ -0,0 +1,109 @@
+use crate::errors::{InstructionError, InstructionResult};
+use crate::instr_error;
+use crate::interpreter::instructions::to_native;
+use crate::interpreter::Instruction;
+use crate::{errors::InstructionErrorKind, IType, IValue};
+
+impl_sync_executable_instruction!(
+    handle_create(resource_id: u64, instruction: Instruction) -> _ {
+        move |runtime| -> _ {
+            let rep = match runtime.stack.pop1() {
+                Some(value) => to_native::<i32>(value, instruction.clone())? as u32,
+                None => {
+                    return instr_error!(
+                        instruction.clone(),
+                        InstructionErrorKind::StackIsTooSmall { needed: 1 }
+                    )
+                }
+            };
+
+            let handle_table = runtime.wasm_instance.handle_table_mut().ok_or_else(|| {
+                InstructionError::from_error_kind(
+                    instruction.clone(),
+                    InstructionErrorKind::HandleTableIsMissing,
+                )
+            })?;
+
+            let handle = handle_table.insert(resource_id, rep).map_err(|e| {
+                InstructionError::from_error_kind(instruction.clone(), e.into())
+            })?;
+
+            log::trace!("handle.create: created handle {} of resource {} for {}", handle, resource_id, rep);
+
+            runtime.stack.push(IValue::Handle(handle));
+
+            Ok(())
+        }
+    }
+);
+
+impl_sync_executable_instruction!(
+    handle_borrow(resource_id: u64, instruction: Instruction) -> _ {
+        move |runtime| -> _ {
+            let handle = pop_handle(runtime.stack.pop1(), resource_id, &instruction)?;
+
+            let handle_table = runtime.wasm_instance.handle_table().ok_or_else(|| {
+                InstructionError::from_error_kind(
+                    instruction.clone(),
+                    InstructionErrorKind::HandleTableIsMissing,
+                )
+            })?;
+
+            let rep = handle_table.get(handle, resource_id).map_err(|e| {
+                InstructionError::from_error_kind(instruction.clone(), e.into())
+            })?;
+
+            log::trace!("handle.borrow: borrowed handle {} of resource {}", handle, resource_id);
+
+            runtime.stack.push(IValue::I32(rep as i32));
+
+            Ok(())
+        }
+    }
+);
+
+impl_sync_executable_instruction!(
+    handle_drop(resource_id: u64, instruction: Instruction) -> _ {
+        move |runtime| -> _ {
+            let handle = pop_handle(runtime.stack.pop1(), resource_id, &instruction)?;
+
+            let handle_table = runtime.wasm_instance.handle_table_mut().ok_or_else(|| {
+                InstructionError::from_error_kind(
+                    instruction.clone(),
+                    InstructionErrorKind::HandleTableIsMissing,
+                )
+            })?;
+
+            let rep = handle_table.remove(handle, resource_id).map_err(|e| {
+                InstructionError::from_error_kind(instruction.clone(), e.into())
+            })?;
+
+            log::trace!("handle.drop: dropped handle {} of resource {}", handle, resource_id);
+
+            runtime.stack.push(IValue::I32(rep as i32));
+
+            Ok(())
+        }
+    }
+);
+
+fn pop_handle(
+    value: Option<IValue>,
+    resource_id: u64,
+    instruction: &Instruction,
+) -> InstructionResult<u32> {
+    match value {
+        Some(IValue::Handle(handle)) => Ok(handle),
+        Some(wrong_value) => instr_error!(
+            instruction.clone(),
+            InstructionErrorKind::InvalidValueOnTheStack {
+                expected_type: IType::Handle(resource_id),
+                received_value: wrong_value,
+            }
+        ),
+        None => instr_error!(
+            instruction.clone(),
+            InstructionErrorKind::StackIsTooSmall { needed: 1 }
+        ),
+    }
+}
@@ -1,5 +1,9 @@
 use crate::interpreter::wasm;
+use crate::IEnumType;
+use crate::IFlagsType;
 use crate::IRecordType;
+use crate::IType;
+use crate::IVariantType;
 
 use it_lilo::traits::RecordResolvable;
 use it_lilo::traits::RecordResolvableError;
@@ -63,4 +67,43 @@ where
 
         Ok(record)
     }
+
+    fn resolve_variant(
+        &self,
+        variant_type_id: u64,
+    ) -> Result<&IVariantType, RecordResolvableError> {
+        let variant = self
+            .instance
+            .wit_variant_by_id(variant_type_id)
+            .ok_or(RecordResolvableError::VariantNotFound(variant_type_id))?;
+
+        Ok(variant)
+    }
+
+    fn resolve_flags(&self, flags_type_id: u64) -> Result<&IFlagsType, RecordResolvableError> {
+        let flags = self
+            .instance
+            .wit_flags_by_id(flags_type_id)
+            .ok_or(RecordResolvableError::FlagsNotFound(flags_type_id))?;
+
+        Ok(flags)
+    }
+
+    fn resolve_enum(&self, enum_type_id: u64) -> Result<&IEnumType, RecordResolvableError> {
+        let enum_type = self
+            .instance
+            .wit_enum_by_id(enum_type_id)
+            .ok_or(RecordResolvableError::EnumNotFound(enum_type_id))?;
+
+        Ok(enum_type)
+    }
+
+    fn resolve_alias(&self, alias_type_id: u64) -> Result<&IType, RecordResolvableError> {
+        let ty = self
+            .instance
+            .wit_alias_by_id(alias_type_id)
+            .ok_or(RecordResolvableError::AliasNotFound(alias_type_id))?;
+
+        Ok(ty)
+    }
 }
@@ -5,6 +5,7 @@ use crate::IValue;
 use it_lilo::traits::Allocatable;
 use it_lilo::traits::AllocatableError;
 use it_lilo::traits::DEFAULT_MEMORY_INDEX;
+use it_memory_traits::AddressType;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -63,14 +64,21 @@ where
     fn allocate<'this, 'store: 'this, 'store_inner: 'this>(
         &'this mut self,
         store: &'store mut <Store as wasm::structures::Store>::ActualStore<'store_inner>,
-        size: u32,
+        memory_index: usize,
+        size: u64,
         type_tag: u32,
-    ) -> BoxFuture<'this, Result<(u32, MemoryView), AllocatableError>> {
+    ) -> BoxFuture<'this, Result<(u64, MemoryView), AllocatableError>> {
         async move {
             use crate::interpreter::instructions::ALLOCATE_FUNC_INDEX;
             use crate::interpreter::wasm::structures::TypedIndex;
             use AllocatableError::*;
 
+            // the allocate export manages only the default memory,
+            // an offset returned by it is meaningless in any other memory
+            if memory_index != DEFAULT_MEMORY_INDEX {
+                return Err(MemoryIsMissing { memory_index });
+            }
+
             let index = FunctionIndex::new(ALLOCATE_FUNC_INDEX as usize);
             let local_or_import =
                 self.instance
@@ -79,7 +87,16 @@ where
                         function_index: ALLOCATE_FUNC_INDEX,
                     })?;
 
-            let inputs = vec![IValue::I32(size as _), IValue::I32(type_tag as _)];
+            let address_type = self
+                .instance
+                .memory_view(memory_index)
+                .ok_or(MemoryIsMissing { memory_index })?
+                .address_type();
+
+            let inputs = vec![
+                crate::interpreter::instructions::pointer_to_ivalue(size, address_type),
+                IValue::I32(type_tag as _),
+            ];
             // TODO: we could check it only once on the module startup or memorize check result
             crate::interpreter::instructions::check_function_signature(
                 self.instance,
@@ -97,19 +114,19 @@ where
                 return Err(AllocateFuncIncompatibleOutput);
             }
 
-            match outcome[0] {
-                IValue::I32(offset) => {
-                    let view =
-                        self.instance
-                            .memory_view(DEFAULT_MEMORY_INDEX)
-                            .ok_or(MemoryIsMissing {
-                                memory_index: DEFAULT_MEMORY_INDEX,
-                            })?;
+            let offset = match (address_type, &outcome[0]) {
+                (AddressType::I32, IValue::I32(offset)) => *offset as u32 as u64,
+                (AddressType::I64, IValue::I64(offset)) => *offset as u64,
+                _ => return Err(AllocateFuncIncompatibleOutput),
+            };
 
-                    Ok((offset as _, view))
-                }
-                _ => Err(AllocateFuncIncompatibleOutput),
-            }
+            // the view is taken after the call, because allocation may grow the memory
+            let view = self
+                .instance
+                .memory_view(memory_index)
+                .ok_or(MemoryIsMissing { memory_index })?;
+
+            Ok((offset, view))
         }
         .boxed()
     }
@@ -3,10 +3,12 @@ mod arrays;
 mod byte_arrays;
 mod call_core;
 mod dup;
+mod handles;
 pub(self) mod lilo;
 mod numbers;
 mod push;
 mod records;
+mod results;
 mod strings;
 mod swap2;
 
@@ -16,20 +18,23 @@ use crate::errors::{
 use crate::interpreter::wasm;
 use crate::IType;
 use crate::IValue;
-use crate::NEVec;
 
 pub(crate) use argument_get::argument_get;
 pub(crate) use arrays::*;
 pub(crate) use byte_arrays::*;
 pub(crate) use call_core::call_core;
 pub(crate) use dup::dup;
+pub(crate) use handles::*;
 pub(crate) use numbers::*;
 pub(crate) use push::*;
 pub(crate) use records::*;
+pub(crate) use results::*;
 pub(crate) use strings::*;
 pub(crate) use swap2::swap2;
 
 use fluence_it_types::NativeType;
+use it_lilo::type_checker::TypeChecker;
+use it_memory_traits::AddressType;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -154,20 +159,50 @@ pub enum Instruction {
     /// The `i64.from_u64` instruction.
     I64FromU64,
 
+    /// The `char.from_i32` instruction.
+    CharFromI32,
+
+    /// The `i32.from_char` instruction.
+    I32FromChar,
+
+    /// The `u128.from_i64` instruction.
+    U128FromI64,
+
+    /// The `i64.from_u128` instruction.
+    I64FromU128,
+
+    /// The `s128.from_i64` instruction.
+    S128FromI64,
+
+    /// The `i64.from_s128` instruction.
+    I64FromS128,
+
     /// The `string.lift_memory` instruction.
-    StringLiftMemory,
+    StringLiftMemory {
+        /// The index of the memory.
+        memory_index: u32,
+    },
 
     /// The `string.lower_memory` instruction.
-    StringLowerMemory,
+    StringLowerMemory {
+        /// The index of the memory.
+        memory_index: u32,
+    },
 
     /// The `byte_array.size` instruction.
     ByteArraySize,
 
     /// The `byte_array.lift_memory` instruction.
-    ByteArrayLiftMemory,
+    ByteArrayLiftMemory {
+        /// The index of the memory.
+        memory_index: u32,
+    },
 
     /// The `byte_array.lower_memory` instruction.
-    ByteArrayLowerMemory,
+    ByteArrayLowerMemory {
+        /// The index of the memory.
+        memory_index: u32,
+    },
 
     /// The `string.size` instruction.
     StringSize,
@@ -176,24 +211,78 @@ pub enum Instruction {
     ArrayLiftMemory {
         /// Array value type.
         value_type: IType,
+
+        /// The index of the memory.
+        memory_index: u32,
     },
 
     /// The `array.lower_memory` instruction.
     ArrayLowerMemory {
         /// Array value type.
         value_type: IType,
+
+        /// The index of the memory.
+        memory_index: u32,
     },
 
     /// The `record.lift_memory` instruction.
     RecordLiftMemory {
         /// The type index of the record.
         record_type_id: u32,
+
+        /// The index of the memory.
+        memory_index: u32,
     },
 
     /// The `record.lower_memory` instruction.
     RecordLowerMemory {
         /// The type index of the record.
         record_type_id: u32,
+
+        /// The index of the memory.
+        memory_index: u32,
+    },
+
+    /// The `result.lift_memory` instruction.
+    ResultLiftMemory {
+        /// The type of the success value.
+        ok_type: IType,
+
+        /// The type of the error value.
+        err_type: IType,
+
+        /// The index of the memory.
+        memory_index: u32,
+    },
+
+    /// The `result.lower_memory` instruction.
+    ResultLowerMemory {
+        /// The type of the success value.
+        ok_type: IType,
+
+        /// The type of the error value.
+        err_type: IType,
+
+        /// The index of the memory.
+        memory_index: u32,
+    },
+
+    /// The `handle.create` instruction.
+    HandleCreate {
+        /// The resource type of the handle.
+        resource_id: u32,
+    },
+
+    /// The `handle.borrow` instruction.
+    HandleBorrow {
+        /// The resource type of the handle.
+        resource_id: u32,
+    },
+
+    /// The `handle.drop` instruction.
+    HandleDrop {
+        /// The resource type of the handle.
+        resource_id: u32,
     },
 
     /// The `i32.push` instruction.
@@ -226,6 +315,29 @@ where
     })
 }
 
+/// Converts a pointer or a size popped from the stack, it's an `I32` value for memories
+/// with 32-bit addresses and an `I64` value for memories with 64-bit addresses.
+pub(crate) fn to_native_pointer(
+    wit_value: IValue,
+    address_type: AddressType,
+    instruction: Instruction,
+) -> InstructionResult<u64> {
+    match address_type {
+        AddressType::I32 => {
+            to_native::<i32>(wit_value, instruction).map(|value| value as u32 as u64)
+        }
+        AddressType::I64 => to_native::<i64>(wit_value, instruction).map(|value| value as u64),
+    }
+}
+
+/// Converts a pointer or a size into a value to push on the stack, see [`to_native_pointer`].
+pub(crate) fn pointer_to_ivalue(value: u64, address_type: AddressType) -> IValue {
+    match address_type {
+        AddressType::I32 => IValue::I32(value as u32 as i32),
+        AddressType::I64 => IValue::I64(value as i64),
+    }
+}
+
 pub(crate) fn check_function_signature<
     'instance,
     Instance,
@@ -256,7 +368,8 @@ where
     Ok(())
 }
 
-/// Check whether the provided value could be a value of the provided type.
+/// Check whether the provided value could be a value of the provided type,
+/// handles must be present in the handle table.
 pub(crate) fn is_value_compatible_to_type<
     'instance,
     Instance,
@@ -278,58 +391,18 @@ where
     Instance: wasm::structures::Instance<Export, LocalImport, Memory, MemoryView, Store>,
     Store: wasm::structures::Store,
 {
-    match (&interface_type, interface_value) {
-        (IType::Boolean, IValue::Boolean(_)) => Ok(()),
-        (IType::S8, IValue::S8(_)) => Ok(()),
-        (IType::S16, IValue::S16(_)) => Ok(()),
-        (IType::S32, IValue::S32(_)) => Ok(()),
-        (IType::S64, IValue::S64(_)) => Ok(()),
-        (IType::U8, IValue::U8(_)) => Ok(()),
-        (IType::U16, IValue::U16(_)) => Ok(()),
-        (IType::U32, IValue::U32(_)) => Ok(()),
-        (IType::U64, IValue::U64(_)) => Ok(()),
-        (IType::I32, IValue::I32(_)) => Ok(()),
-        (IType::I64, IValue::I64(_)) => Ok(()),
-        (IType::F32, IValue::F32(_)) => Ok(()),
-        (IType::F64, IValue::F64(_)) => Ok(()),
-        (IType::String, IValue::String(_)) => Ok(()),
-        (IType::ByteArray, IValue::ByteArray(_)) => Ok(()),
-        (IType::Array(ty), IValue::Array(values)) => {
-            for value in values {
-                is_value_compatible_to_type(instance, ty, value)?
-            }
+    let li_helper = lilo::LiHelper::new(instance);
+    let check_handle = handle_check(instance);
 
-            Ok(())
-        }
-        (IType::ByteArray, IValue::Array(values)) => {
-            for value in values {
-                is_value_compatible_to_type(instance, &IType::U8, value)?
-            }
-
-            Ok(())
-        }
-        (IType::Array(ty), IValue::ByteArray(_)) => {
-            if ty.as_ref() == &IType::U8 {
-                return Ok(());
-            }
-
-            Err(InstructionErrorKind::InvalidValueOnTheStack {
-                expected_type: interface_type.clone(),
-                received_value: interface_value.clone(),
-            })
-        }
-        (IType::Record(ref record_type_id), IValue::Record(record_fields)) => {
-            is_record_fields_compatible_to_type(instance, *record_type_id, record_fields)?;
+    TypeChecker::new(&li_helper)
+        .with_handle_check(&check_handle)
+        .check_value(interface_type, interface_value)?;
 
-            Ok(())
-        }
-        _ => Err(InstructionErrorKind::InvalidValueOnTheStack {
-            expected_type: interface_type.clone(),
-            received_value: interface_value.clone(),
-        }),
-    }
+    Ok(())
 }
 
+/// Check whether the provided values could be fields of a record with the provided type,
+/// handles must be present in the handle table.
 pub(crate) fn is_record_fields_compatible_to_type<
     'instance,
     Instance,
@@ -351,48 +424,118 @@ where
     Instance: wasm::structures::Instance<Export, LocalImport, Memory, MemoryView, Store>,
     Store: wasm::structures::Store,
 {
-    let record_type = instance
-        .wit_record_by_id(record_type_id)
-        .ok_or(InstructionErrorKind::RecordTypeByNameIsMissing { record_type_id })?;
-
-    if record_fields.len() != record_type.fields.len() {
-        return Err(InstructionErrorKind::InvalidValueOnTheStack {
-            expected_type: IType::Record(record_type_id),
-            // unwrap is safe here - len's been already checked
-            received_value: IValue::Record(NEVec::new(record_fields.to_vec()).unwrap()),
-        });
-    }
+    let li_helper = lilo::LiHelper::new(instance);
+    let check_handle = handle_check(instance);
 
-    for (record_type_field, record_value_field) in
-        record_type.fields.iter().zip(record_fields.iter())
-    {
-        is_value_compatible_to_type(instance, &record_type_field.ty, record_value_field)?;
-    }
+    TypeChecker::new(&li_helper)
+        .with_handle_check(&check_handle)
+        .check_record_fields(record_type_id, record_fields)?;
 
     Ok(())
 }
 
+/// Returns a check that a handle is present in the handle table of the instance.
+fn handle_check<'instance, Instance, Export, LocalImport, Memory, MemoryView, Store>(
+    instance: &'instance Instance,
+) -> impl Fn(u64, u32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + 'instance
+where
+    Export: wasm::structures::Export + 'instance,
+    LocalImport: wasm::structures::LocalImport<Store> + 'instance,
+    Memory: wasm::structures::Memory<MemoryView, Store> + 'instance,
+    MemoryView: wasm::structures::MemoryView<Store>,
+    Instance: wasm::structures::Instance<Export, LocalImport, Memory, MemoryView, Store>,
+    Store: wasm::structures::Store,
+{
+    move |resource_id, handle| {
+        let handle_table = instance
+            .handle_table()
+            .ok_or(InstructionErrorKind::HandleTableIsMissing)?;
+
+        handle_table.get(handle, resource_id)?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::{ast::*, interpreter::wasm, types::*, values::*};
-    use std::{cell::Cell, collections::HashMap, convert::TryInto, ops::Deref, rc::Rc};
+    use crate::ast::FunctionArg;
+    use crate::interpreter::handles::HandleTable;
+    use crate::interpreter::wasm;
+    use crate::IRecordFieldType;
+    use crate::IRecordType;
+    use crate::IType;
+    use crate::IValue;
+    use crate::NEVec;
+
+    use it_lilo::in_memory::BumpAllocator;
+    use it_memory_traits::AddressType;
+    use it_memory_traits::InMemory;
+    use it_memory_traits::InMemoryStore;
+    use it_memory_traits::InMemoryView;
+
+    use futures::future::BoxFuture;
+    use futures::FutureExt;
+
+    use std::collections::HashMap;
+    use std::convert::TryInto;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    pub(crate) type Memory = InMemory;
+    pub(crate) type MemoryView = InMemoryView;
+    pub(crate) type Store = InMemoryStore;
+
+    pub(crate) type Function = Box<dyn Fn(&[IValue]) -> anyhow::Result<Vec<IValue>> + Send + Sync>;
+
+    fn function_args(inputs: Vec<IType>) -> Vec<FunctionArg> {
+        inputs
+            .into_iter()
+            .enumerate()
+            .map(|(position, ty)| FunctionArg {
+                name: format!("arg_{}", position),
+                ty,
+            })
+            .collect()
+    }
 
     pub(crate) struct Export {
-        pub(crate) inputs: Vec<IType>,
+        pub(crate) name: String,
+        pub(crate) inputs: Vec<FunctionArg>,
         pub(crate) outputs: Vec<IType>,
-        pub(crate) function: fn(arguments: &[IValue]) -> Result<Vec<IValue>, ()>,
+        pub(crate) function: Function,
+    }
+
+    impl Export {
+        pub(crate) fn new(
+            name: &str,
+            inputs: Vec<IType>,
+            outputs: Vec<IType>,
+            function: impl Fn(&[IValue]) -> anyhow::Result<Vec<IValue>> + Send + Sync + 'static,
+        ) -> Self {
+            Self {
+                name: name.to_string(),
+                inputs: function_args(inputs),
+                outputs,
+                function: Box::new(function),
+            }
+        }
     }
 
     impl wasm::structures::Export for Export {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
         fn inputs_cardinality(&self) -> usize {
-            self.inputs.len() as usize
+            self.inputs.len()
         }
 
         fn outputs_cardinality(&self) -> usize {
             self.outputs.len()
         }
 
-        fn arguments(&self) -> &[IType] {
+        fn arguments(&self) -> &[FunctionArg] {
             &self.inputs
         }
 
@@ -400,18 +543,42 @@ pub(crate) mod tests {
             &self.outputs
         }
 
-        fn call(&self, arguments: &[IValue]) -> Result<Vec<IValue>, ()> {
-            (self.function)(arguments)
+        fn call_async<'args>(
+            &'args self,
+            arguments: &'args [IValue],
+        ) -> BoxFuture<'args, anyhow::Result<Vec<IValue>>> {
+            async move { (self.function)(arguments) }.boxed()
         }
     }
 
     pub(crate) struct LocalImport {
-        pub(crate) inputs: Vec<IType>,
+        pub(crate) name: String,
+        pub(crate) inputs: Vec<FunctionArg>,
         pub(crate) outputs: Vec<IType>,
-        pub(crate) function: fn(arguments: &[IValue]) -> Result<Vec<IValue>, ()>,
+        pub(crate) function: Function,
+    }
+
+    impl LocalImport {
+        pub(crate) fn new(
+            name: &str,
+            inputs: Vec<IType>,
+            outputs: Vec<IType>,
+            function: impl Fn(&[IValue]) -> anyhow::Result<Vec<IValue>> + Send + Sync + 'static,
+        ) -> Self {
+            Self {
+                name: name.to_string(),
+                inputs: function_args(inputs),
+                outputs,
+                function: Box::new(function),
+            }
+        }
     }
 
-    impl wasm::structures::LocalImport for LocalImport {
+    impl wasm::structures::LocalImport<Store> for LocalImport {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
         fn inputs_cardinality(&self) -> usize {
             self.inputs.len()
         }
@@ -420,7 +587,7 @@ pub(crate) mod tests {
             self.outputs.len()
         }
 
-        fn arguments(&self) -> &[IType] {
+        fn arguments(&self) -> &[FunctionArg] {
             &self.inputs
         }
 
@@ -428,40 +595,12 @@ pub(crate) mod tests {
             &self.outputs
         }
 
-        fn call(&self, arguments: &[IValue]) -> Result<Vec<IValue>, ()> {
-            (self.function)(arguments)
-        }
-    }
-
-    #[derive(Default, Clone)]
-    pub(crate) struct MemoryView(Rc<Vec<Cell<u8>>>);
-
-    impl wasm::structures::MemoryView for MemoryView {}
-
-    impl Deref for MemoryView {
-        type Target = [Cell<u8>];
-
-        fn deref(&self) -> &Self::Target {
-            self.0.as_slice()
-        }
-    }
-
-    #[derive(Default)]
-    pub(crate) struct Memory {
-        pub(crate) view: MemoryView,
-    }
-
-    impl Memory {
-        pub(crate) fn new(data: Vec<Cell<u8>>) -> Self {
-            Self {
-                view: MemoryView(Rc::new(data)),
-            }
-        }
-    }
-
-    impl wasm::structures::Memory<MemoryView, Store> for Memory {
-        fn view(&self) -> MemoryView {
-            self.view.clone()
+        fn call_async<'args>(
+            &'args self,
+            _store: &'args mut (),
+            arguments: &'args [IValue],
+        ) -> BoxFuture<anyhow::Result<Vec<IValue>>> {
+            async move { (self.function)(arguments) }.boxed()
         }
     }
 
@@ -470,118 +609,176 @@ pub(crate) mod tests {
         pub(crate) exports: HashMap<String, Export>,
         pub(crate) locals_or_imports: HashMap<usize, LocalImport>,
         pub(crate) memory: Memory,
-        pub(crate) wit_types: Vec<Type>,
+        /// Memories with indices starting from 1.
+        pub(crate) other_memories: Vec<Memory>,
+        pub(crate) wit_records: HashMap<u64, Arc<IRecordType>>,
+        pub(crate) handle_table: HandleTable,
     }
 
     impl Instance {
         pub(crate) fn new() -> Self {
-            Self {
-                exports: {
-                    let mut hashmap = HashMap::new();
-                    hashmap.insert(
-                        "sum".into(),
-                        Export {
-                            inputs: vec![IType::I32, IType::I32],
-                            outputs: vec![IType::I32],
-                            function: |arguments: &[IValue]| {
-                                let a: i32 = (&arguments[0]).try_into().unwrap();
-                                let b: i32 = (&arguments[1]).try_into().unwrap();
-
-                                Ok(vec![IValue::I32(a + b)])
-                            },
-                        },
-                    );
-
-                    hashmap
-                },
-                locals_or_imports: {
-                    let mut hashmap = HashMap::new();
-                    // sum
-                    hashmap.insert(
-                        42,
-                        LocalImport {
-                            inputs: vec![IType::I32, IType::I32],
-                            outputs: vec![IType::I32],
-                            function: |arguments: &[IValue]| {
-                                let a: i32 = (&arguments[0]).try_into().unwrap();
-                                let b: i32 = (&arguments[1]).try_into().unwrap();
-
-                                Ok(vec![IValue::I32(a * b)])
-                            },
-                        },
-                    );
-                    // string allocator
-                    hashmap.insert(
-                        43,
-                        LocalImport {
-                            inputs: vec![IType::I32],
-                            outputs: vec![IType::I32],
-                            function: |arguments: &[IValue]| {
-                                let _size: i32 = (&arguments[0]).try_into().unwrap();
-
-                                Ok(vec![IValue::I32(0)])
-                            },
-                        },
-                    );
+            Self::with_address_type(AddressType::I32)
+        }
 
-                    hashmap
-                },
-                memory: Memory::new(vec![Cell::new(0); 128]),
-                wit_types: vec![Type::Record(RecordType {
+        /// Creates an instance whose memory has addresses of `address_type`,
+        /// the allocate import receives and returns pointers of this type.
+        pub(crate) fn with_address_type(address_type: AddressType) -> Self {
+            let memory = Memory::from(vec![0; 128]).with_address_type(address_type);
+            let pointer_type = match address_type {
+                AddressType::I32 => IType::I32,
+                AddressType::I64 => IType::I64,
+            };
+            let allocator = Arc::new(Mutex::new(BumpAllocator::new(memory.clone())));
+
+            let mut exports = HashMap::new();
+            exports.insert(
+                "sum".to_string(),
+                Export::new(
+                    "sum",
+                    vec![IType::I32, IType::I32],
+                    vec![IType::I32],
+                    |arguments| {
+                        let a: i32 = arguments[0].clone().try_into()?;
+                        let b: i32 = arguments[1].clone().try_into()?;
+
+                        Ok(vec![IValue::I32(a + b)])
+                    },
+                ),
+            );
+
+            let mut locals_or_imports = HashMap::new();
+            // allocator used by lowering instructions
+            locals_or_imports.insert(
+                super::ALLOCATE_FUNC_INDEX as usize,
+                LocalImport::new(
+                    "allocate",
+                    vec![pointer_type.clone(), IType::I32],
+                    vec![pointer_type],
+                    move |arguments| {
+                        let size = match arguments[0] {
+                            IValue::I32(size) => size as u32 as u64,
+                            IValue::I64(size) => size as u64,
+                            ref size => anyhow::bail!("invalid size {:?}", size),
+                        };
+                        let type_tag: i32 = arguments[1].clone().try_into()?;
+                        let offset = allocator.lock().unwrap().bump(size, type_tag as u32)?;
+
+                        Ok(vec![super::pointer_to_ivalue(offset, address_type)])
+                    },
+                ),
+            );
+            // multiply
+            locals_or_imports.insert(
+                42,
+                LocalImport::new(
+                    "multiply",
+                    vec![IType::I32, IType::I32],
+                    vec![IType::I32],
+                    |arguments| {
+                        let a: i32 = arguments[0].clone().try_into()?;
+                        let b: i32 = arguments[1].clone().try_into()?;
+
+                        Ok(vec![IValue::I32(a * b)])
+                    },
+                ),
+            );
+            // string allocator that always allocates at the beginning of the memory
+            locals_or_imports.insert(
+                43,
+                LocalImport::new(
+                    "allocate_string",
+                    vec![IType::I32],
+                    vec![IType::I32],
+                    |_| Ok(vec![IValue::I32(0)]),
+                ),
+            );
+
+            let mut wit_records = HashMap::new();
+            wit_records.insert(
+                0,
+                Arc::new(IRecordType {
                     name: String::from("RecordType0"),
-                    fields: vec1![
-                        RecordFieldType {
-                            name: String::from("field_0"),
+                    fields: NEVec::new(vec![
+                        IRecordFieldType {
+                            name: Some(String::from("field_0")),
                             ty: IType::I32,
                         },
-                        RecordFieldType {
-                            name: String::from("field_1"),
-                            ty: IType::Record(RecordType {
-                                name: String::from("RecordType1"),
-                                fields: vec1![
-                                    RecordFieldType {
-                                        name: String::from("field_0"),
-                                        ty: IType::String,
-                                    },
-                                    RecordFieldType {
-                                        name: String::from("field1"),
-                                        ty: IType::F32
-                                    }
-                                ],
-                            }),
+                        IRecordFieldType {
+                            name: Some(String::from("field_1")),
+                            ty: IType::Record(1),
                         },
-                        RecordFieldType {
-                            name: String::from("field_2"),
+                        IRecordFieldType {
+                            name: Some(String::from("field_2")),
                             ty: IType::I64,
-                        }
-                    ],
-                })],
+                        },
+                    ])
+                    .unwrap(),
+                }),
+            );
+            wit_records.insert(
+                1,
+                Arc::new(IRecordType {
+                    name: String::from("RecordType1"),
+                    fields: NEVec::new(vec![
+                        IRecordFieldType {
+                            name: Some(String::from("field_0")),
+                            ty: IType::String,
+                        },
+                        IRecordFieldType {
+                            name: Some(String::from("field1")),
+                            ty: IType::F32,
+                        },
+                    ])
+                    .unwrap(),
+                }),
+            );
+
+            Self {
+                exports,
+                locals_or_imports,
+                memory,
+                other_memories: vec![],
+                wit_records,
+                handle_table: HandleTable::default(),
             }
         }
     }
 
-    impl wasm::structures::Instance<Export, LocalImport, Memory, MemoryView> for Instance {
+    impl wasm::structures::Instance<Export, LocalImport, Memory, MemoryView, Store> for Instance {
         fn export(&self, export_name: &str) -> Option<&Export> {
             self.exports.get(export_name)
         }
 
         fn local_or_import<I: wasm::structures::TypedIndex + wasm::structures::LocalImportIndex>(
-            &mut self,
+            &self,
             index: I,
         ) -> Option<&LocalImport> {
             self.locals_or_imports.get(&index.index())
         }
 
-        fn memory(&self, _index: usize) -> Option<&Memory> {
-            Some(&self.memory)
+        fn memory(&self, index: usize) -> Option<&Memory> {
+            match index {
+                0 => Some(&self.memory),
+                index => self.other_memories.get(index - 1),
+            }
+        }
+
+        fn memory_view(&self, index: usize) -> Option<MemoryView> {
+            use wasm::structures::Memory;
+
+            self.memory(index).map(|memory| memory.view())
+        }
+
+        fn wit_record_by_id(&self, index: u64) -> Option<&Arc<IRecordType>> {
+            self.wit_records.get(&index)
         }
 
-        fn wit_type_by_id(&self, index: u32) -> Option<&Type> {
-            self.wit_types.get(index as usize)
+        fn handle_table(&self) -> Option<&HandleTable> {
+            Some(&self.handle_table)
         }
 
-        fn wit_record_by_id(&self, index: u64) -> Option<&RecordType> {
-            self.wit_types.get(index as _)
+        fn handle_table_mut(&mut self) -> Option<&mut HandleTable> {
+            Some(&mut self.handle_table)
         }
     }
 }
@@ -93,6 +93,10 @@ lowering_lifting!(i64_from_u8, "i64.from_u8", I64, U8);
 lowering_lifting!(i64_from_u16, "i64.from_u16", I64, U16);
 lowering_lifting!(i64_from_u32, "i64.from_u32", I64, U32);
 lowering_lifting!(i64_from_u64, "i64.from_u64", I64, U64);
+lowering_lifting!(u128_from_i64, "u128.from_i64", U128, I64);
+lowering_lifting!(i64_from_u128, "i64.from_u128", I64, U128);
+lowering_lifting!(s128_from_i64, "s128.from_i64", S128, I64);
+lowering_lifting!(i64_from_s128, "i64.from_s128", I64, S128);
 
 impl_sync_executable_instruction!(
     bool_from_i32(instruction: Instruction) -> _ {
@@ -132,6 +136,93 @@ impl_sync_executable_instruction!(
     }
 );
 
+impl_sync_executable_instruction!(
+    char_from_i32(instruction: Instruction) -> _ {
+        move |runtime| -> _ {
+            match runtime.stack.pop1() {
+                Some(IValue::I32(value)) => {
+                    runtime
+                        .stack
+                        .push({
+                            let converted_value = IValue::Char(
+                                char::from_u32(value as u32).ok_or_else(|| {
+                                    InstructionError::from_error_kind(
+                                        instruction.clone(),
+                                        InstructionErrorKind::LoweringLifting {
+                                            from: IType::I32,
+                                            to: IType::Char,
+                                        },
+                                    )
+                                })?,
+                            );
+
+                            log::trace!("char.from_i32: converting {:?} to {:?}" , value, converted_value);
+
+                            converted_value
+                        })
+                }
+                Some(wrong_value) => {
+                    return instr_error!(
+                        instruction.clone(),
+                        InstructionErrorKind::InvalidValueOnTheStack {
+                            expected_type: IType::I32,
+                            received_value: wrong_value,
+                        }
+                    )
+                },
+
+                None => {
+                    return instr_error!(
+                        instruction.clone(),
+                        InstructionErrorKind::StackIsTooSmall { needed: 1 }
+                    )
+                }
+            }
+
+            Ok(())
+        }
+    }
+);
+
+impl_sync_executable_instruction!(
+    i32_from_char(instruction: Instruction) -> _ {
+        move |runtime| -> _ {
+            match runtime.stack.pop1() {
+                Some(IValue::Char(value)) => {
+                    runtime
+                        .stack
+                        .push({
+                            // a Unicode scalar value is at most 0x10FFFF, so it always fits into i32
+                            let converted_value = IValue::I32(value as i32);
+
+                            log::trace!("i32.from_char: converting {:?} to {:?}" , value, converted_value);
+
+                            converted_value
+                        })
+                }
+                Some(wrong_value) => {
+                    return instr_error!(
+                        instruction.clone(),
+                        InstructionErrorKind::InvalidValueOnTheStack {
+                            expected_type: IType::Char,
+                            received_value: wrong_value,
+                        }
+                    )
+                },
+
+                None => {
+                    return instr_error!(
+                        instruction.clone(),
+                        InstructionErrorKind::StackIsTooSmall { needed: 1 }
+                    )
+                }
+            }
+
+            Ok(())
+        }
+    }
+);
+
 #[cfg(test)]
 mod tests {
     test_executable_instruction!(
@@ -147,7 +238,7 @@ mod tests {
             instructions: [Instruction::ArgumentGet { index: 0}, Instruction::S8FromI32],
             invocation_inputs: [IValue::I64(42)],
             instance: Instance::new(),
-            error: "`s8.from_i32` read a value of type `I64` from the stack, but the type `I32` was expected"
+            error: "`s8.from_i32` read a value `I32` from the stack, that can't be converted to `I64(42)`"
     );
 
     test_executable_instruction!(
@@ -413,4 +504,92 @@ mod tests {
             instance: Instance::new(),
             stack: [IValue::I64(42)],
     );
+
+    test_executable_instruction!(
+        test_char_from_i32 =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::CharFromI32],
+            invocation_inputs: [IValue::I32(0x451)],
+            instance: Instance::new(),
+            stack: [IValue::Char('ё')],
+    );
+
+    test_executable_instruction!(
+        test_char_from_i32__surrogate =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::CharFromI32],
+            invocation_inputs: [IValue::I32(0xd800)],
+            instance: Instance::new(),
+            error: "`char.from_i32` failed to cast `I32` to `Char`"
+    );
+
+    test_executable_instruction!(
+        test_char_from_i32__too_large =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::CharFromI32],
+            invocation_inputs: [IValue::I32(0x110000)],
+            instance: Instance::new(),
+            error: "`char.from_i32` failed to cast `I32` to `Char`"
+    );
+
+    test_executable_instruction!(
+        test_i32_from_char =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::I32FromChar],
+            invocation_inputs: [IValue::Char('\u{10ffff}')],
+            instance: Instance::new(),
+            stack: [IValue::I32(0x10ffff)],
+    );
+
+    test_executable_instruction!(
+        test_u128_from_i64 =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::U128FromI64],
+            invocation_inputs: [IValue::I64(42)],
+            instance: Instance::new(),
+            stack: [IValue::U128(42)],
+    );
+
+    test_executable_instruction!(
+        test_u128_from_i64__negative =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::U128FromI64],
+            invocation_inputs: [IValue::I64(-1)],
+            instance: Instance::new(),
+            error: "`u128.from_i64` failed to cast `I64` to `U128`"
+    );
+
+    test_executable_instruction!(
+        test_i64_from_u128 =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::I64FromU128],
+            invocation_inputs: [IValue::U128(42)],
+            instance: Instance::new(),
+            stack: [IValue::I64(42)],
+    );
+
+    test_executable_instruction!(
+        test_i64_from_u128__overflow =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::I64FromU128],
+            invocation_inputs: [IValue::U128(u128::MAX)],
+            instance: Instance::new(),
+            error: "`i64.from_u128` failed to cast `U128` to `I64`"
+    );
+
+    test_executable_instruction!(
+        test_s128_from_i64 =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::S128FromI64],
+            invocation_inputs: [IValue::I64(-42)],
+            instance: Instance::new(),
+            stack: [IValue::S128(-42)],
+    );
+
+    test_executable_instruction!(
+        test_i64_from_s128 =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::I64FromS128],
+            invocation_inputs: [IValue::S128(-42)],
+            instance: Instance::new(),
+            stack: [IValue::I64(-42)],
+    );
+
+    test_executable_instruction!(
+        test_i64_from_s128__overflow =
+            instructions: [Instruction::ArgumentGet { index: 0 }, Instruction::I64FromS128],
+            invocation_inputs: [IValue::S128(i128::MIN)],
+            instance: Instance::new(),
+            error: "`i64.from_s128` failed to cast `S128` to `I64`"
+    );
 }
@@ -1,6 +1,8 @@
 use super::lilo;
 use crate::instr_error;
-use crate::interpreter::instructions::{is_record_fields_compatible_to_type, to_native};
+use crate::interpreter::instructions::{
+    is_record_fields_compatible_to_type, pointer_to_ivalue, to_native_pointer,
+};
 use crate::IType;
 use crate::IValue;
 use crate::{errors::InstructionError, errors::InstructionErrorKind, interpreter::Instruction};
@@ -10,18 +12,19 @@ use crate::interpreter::stack::Stackable;
 use crate::interpreter::{AsyncExecutableInstructionImpl, ExecutableInstruction, Runtime};
 use it_lilo::lifter::ILifter;
 use it_lilo::lowerer::ILowerer;
-use it_lilo::traits::DEFAULT_MEMORY_INDEX;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
 struct RecordLiftMemoryAsync {
     record_type_id: u64,
+    memory_index: usize,
     instruction: Instruction,
 }
 
 pub(crate) fn record_lift_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
     record_type_id: u64,
+    memory_index: usize,
     instruction: Instruction,
 ) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
 where
@@ -40,6 +43,7 @@ where
 {
     ExecutableInstruction::Async(Box::new(RecordLiftMemoryAsync {
         record_type_id,
+        memory_index,
         instruction,
     }))
 }
@@ -76,8 +80,6 @@ where
                 )
             })?;
 
-            let offset: u32 = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
-
             // TODO: size = 0
             let instance = &runtime.wasm_instance;
             let record_type = instance.wit_record_by_id(record_type_id).ok_or_else(|| {
@@ -93,7 +95,7 @@ where
                 record_type_id
             );
 
-            let memory_index = DEFAULT_MEMORY_INDEX;
+            let memory_index = self.memory_index;
             let memory_view = instance
                 .memory(memory_index)
                 .ok_or_else(|| {
@@ -104,8 +106,16 @@ where
                 })?
                 .view();
 
+            let offset = to_native_pointer(
+                inputs.remove(0),
+                memory_view.address_type(),
+                instruction.clone(),
+            )?;
+
             let li_helper = lilo::LiHelper::new(&**instance);
-            let lifter = ILifter::new(memory_view, &li_helper);
+            // handles read from memory must be known to the handle table
+            let check_handle = super::handle_check(&**instance);
+            let lifter = ILifter::new(memory_view, &li_helper).with_handle_check(&check_handle);
             let record =
                 it_lilo::lifter::record_lift_memory(runtime.store, &lifter, record_type, offset)
                     .map_err(|e| InstructionError::from_li(instruction.clone(), e))?;
@@ -121,11 +131,13 @@ where
 
 struct RecordLowerMemoryAsync {
     record_type_id: u64,
+    memory_index: usize,
     instruction: Instruction,
 }
 
 pub(crate) fn record_lower_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
     record_type_id: u64,
+    memory_index: usize,
     instruction: Instruction,
 ) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
 where
@@ -144,6 +156,7 @@ where
 {
     ExecutableInstruction::Async(Box::new(RecordLowerMemoryAsync {
         record_type_id,
+        memory_index,
         instruction,
     }))
 }
@@ -189,7 +202,7 @@ where
                     record_type_id
                 );
 
-                    let memory_index = DEFAULT_MEMORY_INDEX;
+                    let memory_index = self.memory_index;
                     let memory_view = instance
                         .memory(memory_index)
                         .ok_or_else(|| {
@@ -200,9 +213,11 @@ where
                         })?
                         .view();
 
+                    let address_type = memory_view.address_type();
                     let mut lo_helper = lilo::LoHelper::new(&**instance);
-                    let mut memory_writer = ILowerer::new(memory_view, &mut lo_helper)
-                        .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
+                    let mut memory_writer =
+                        ILowerer::new(memory_view, memory_index, &mut lo_helper)
+                            .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
 
                     let offset = it_lilo::lowerer::record_lower_memory(
                         runtime.store,
@@ -213,7 +228,7 @@ where
                     .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
 
                     log::debug!("record.lower_memory: pushing {} on the stack", offset);
-                    runtime.stack.push(IValue::I32(offset as i32));
+                    runtime.stack.push(pointer_to_ivalue(offset, address_type));
 
                     Ok(())
                 }
@@ -233,3 +248,191 @@ where
         .boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::instructions::tests::Instance;
+    use crate::IRecordFieldType;
+    use crate::IRecordType;
+    use crate::IType;
+    use crate::IValue;
+    use crate::NEVec;
+
+    use it_memory_traits::AddressType;
+
+    use std::sync::Arc;
+
+    /// An instance with the record 2 of char, u128 and s128 fields.
+    fn scalars_instance() -> Instance {
+        let mut instance = Instance::new();
+        let fields = vec![IType::Char, IType::U128, IType::S128]
+            .into_iter()
+            .map(|ty| IRecordFieldType { name: None, ty })
+            .collect();
+        instance.wit_records.insert(
+            2,
+            Arc::new(IRecordType {
+                name: String::from("Scalars"),
+                fields: NEVec::new(fields).unwrap(),
+            }),
+        );
+
+        instance
+    }
+
+    /// An instance with the record 3 of a handle to resource 3 field.
+    fn handle_instance() -> Instance {
+        let mut instance = Instance::new();
+        let field = IRecordFieldType {
+            name: Some(String::from("connection")),
+            ty: IType::Handle(3),
+        };
+        instance.wit_records.insert(
+            3,
+            Arc::new(IRecordType {
+                name: String::from("Session"),
+                fields: NEVec::new(vec![field]).unwrap(),
+            }),
+        );
+
+        instance
+    }
+
+    fn scalars_value() -> IValue {
+        IValue::Record(
+            NEVec::new(vec![
+                IValue::Char('ё'),
+                IValue::U128(u128::MAX),
+                IValue::S128(i128::MIN),
+            ])
+            .unwrap(),
+        )
+    }
+
+    fn record_value() -> IValue {
+        IValue::Record(
+            NEVec::new(vec![
+                IValue::I32(1),
+                IValue::Record(
+                    NEVec::new(vec![IValue::String("Hello".into()), IValue::F32(2.5)]).unwrap(),
+                ),
+                IValue::I64(3),
+            ])
+            .unwrap(),
+        )
+    }
+
+    test_executable_instruction!(
+        test_record__roundtrip =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 0, memory_index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [record_value()],
+            instance: Instance::new(),
+            stack: [record_value()],
+    );
+
+    test_executable_instruction!(
+        test_record__roundtrip_memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 0, memory_index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [record_value()],
+            instance: Instance::with_address_type(AddressType::I64),
+            stack: [record_value()],
+    );
+
+    test_executable_instruction!(
+        test_record_lower_memory__memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [record_value()],
+            instance: Instance::with_address_type(AddressType::I64),
+            // the string is allocated at 128, the inner record with 8-byte pointers
+            // at 136 and the outer record at 160
+            stack: [IValue::I64(160)],
+    );
+
+    test_executable_instruction!(
+        test_record_lift_memory__memory64_i32_pointer =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0)],
+            instance: Instance::with_address_type(AddressType::I64),
+            error: "`record.lift_memory 0` failed to cast a WIT value to its native type: I32(0) can't be converted to a value of type 'i64'",
+    );
+
+    test_executable_instruction!(
+        test_record_lower_memory__missing_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 0, memory_index: 1 },
+            ],
+            invocation_inputs: [record_value()],
+            instance: Instance::new(),
+            error: "`record.lower_memory 0 1` memory `1` does not exist",
+    );
+
+    test_executable_instruction!(
+        test_record_lift_memory__read_out_of_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(120)],
+            instance: Instance::new(),
+            error: "`record.lift_memory 0` Out-of-bound Wasm memory access: offset 120, size 16, while memory_size 128",
+    );
+
+    test_executable_instruction!(
+        test_record__roundtrip_char_u128_s128 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 2, memory_index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 2, memory_index: 0 },
+            ],
+            invocation_inputs: [scalars_value()],
+            instance: scalars_instance(),
+            stack: [scalars_value()],
+    );
+
+    test_executable_instruction!(
+        test_record_lift_memory__forged_handle =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLiftMemory { record_type_id: 3, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::I32(0)],
+            // the memory is zeroed, and 0 is never a valid handle
+            instance: handle_instance(),
+            error: "`record.lift_memory 3` handle 0 doesn't exist, it's either forged or has been already dropped",
+    );
+
+    test_executable_instruction!(
+        test_record_lower_memory__nested_invalid_value =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::RecordLowerMemory { record_type_id: 0, memory_index: 0 },
+            ],
+            invocation_inputs: [IValue::Record(
+                NEVec::new(vec![
+                    IValue::I32(1),
+                    IValue::Record(
+                        NEVec::new(vec![IValue::String("Hello".into()), IValue::F64(2.5)]).unwrap(),
+                    ),
+                    IValue::I64(3),
+                ])
+                .unwrap(),
+            )],
+            instance: Instance::new(),
+            error: "`record.lower_memory 0` field_1.field1: expected a value of type 'f32', but F64(2.5) was found",
+    );
+}
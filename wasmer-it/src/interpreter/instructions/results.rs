@@ -0,0 +1,253 @@
+use super::lilo;
+use crate::instr_error;
+use crate::interpreter::instructions::{
+    is_value_compatible_to_type, pointer_to_ivalue, to_native_pointer,
+};
+use crate::IType;
+use crate::IValue;
+use crate::{errors::InstructionError, errors::InstructionErrorKind, interpreter::Instruction};
+
+use crate::errors::InstructionResult;
+use crate::interpreter::stack::Stackable;
+use crate::interpreter::{AsyncExecutableInstructionImpl, ExecutableInstruction, Runtime};
+use it_lilo::lifter::ILifter;
+use it_lilo::lowerer::ILowerer;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+struct ResultLiftMemoryAsync {
+    instruction: Instruction,
+    ok_type: IType,
+    err_type: IType,
+    memory_index: usize,
+}
+
+pub(crate) fn result_lift_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
+    instruction: Instruction,
+    ok_type: IType,
+    err_type: IType,
+    memory_index: usize,
+) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
+where
+    Export: crate::interpreter::wasm::structures::Export,
+    LocalImport: crate::interpreter::wasm::structures::LocalImport<Store>,
+    Memory: crate::interpreter::wasm::structures::Memory<MemoryView, Store>,
+    MemoryView: crate::interpreter::wasm::structures::MemoryView<Store>,
+    Instance: crate::interpreter::wasm::structures::Instance<
+        Export,
+        LocalImport,
+        Memory,
+        MemoryView,
+        Store,
+    >,
+    Store: crate::interpreter::wasm::structures::Store,
+{
+    ExecutableInstruction::Async(Box::new(ResultLiftMemoryAsync {
+        instruction,
+        ok_type,
+        err_type,
+        memory_index,
+    }))
+}
+
+impl<Instance, Export, LocalImport, Memory, MemoryView, Store>
+    AsyncExecutableInstructionImpl<Instance, Export, LocalImport, Memory, MemoryView, Store>
+    for ResultLiftMemoryAsync
+where
+    Export: crate::interpreter::wasm::structures::Export,
+    LocalImport: crate::interpreter::wasm::structures::LocalImport<Store>,
+    Memory: crate::interpreter::wasm::structures::Memory<MemoryView, Store>,
+    MemoryView: crate::interpreter::wasm::structures::MemoryView<Store>,
+    Instance: crate::interpreter::wasm::structures::Instance<
+        Export,
+        LocalImport,
+        Memory,
+        MemoryView,
+        Store,
+    >,
+    Store: crate::interpreter::wasm::structures::Store,
+{
+    fn execute<'args>(
+        &'args self,
+        runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>,
+    ) -> BoxFuture<InstructionResult<()>> {
+        async move {
+            let instruction = &self.instruction;
+
+            let mut inputs = runtime.stack.pop(1).ok_or_else(|| {
+                InstructionError::from_error_kind(
+                    instruction.clone(),
+                    InstructionErrorKind::StackIsTooSmall { needed: 1 },
+                )
+            })?;
+
+            let instance = &runtime.wasm_instance;
+
+            let memory_index = self.memory_index;
+            let memory_view = instance
+                .memory(memory_index)
+                .ok_or_else(|| {
+                    InstructionError::from_error_kind(
+                        instruction.clone(),
+                        InstructionErrorKind::MemoryIsMissing { memory_index },
+                    )
+                })?
+                .view();
+
+            let offset = to_native_pointer(
+                inputs.remove(0),
+                memory_view.address_type(),
+                instruction.clone(),
+            )?;
+
+            log::trace!(
+                "result.lift_memory: lifting memory for ok type: {:?}, err type: {:?}, popped offset {}",
+                self.ok_type,
+                self.err_type,
+                offset
+            );
+
+            let li_helper = lilo::LiHelper::new(&**instance);
+            // handles read from memory must be known to the handle table
+            let check_handle = super::handle_check(&**instance);
+            let lifter = ILifter::new(memory_view, &li_helper).with_handle_check(&check_handle);
+            let result = it_lilo::lifter::result_lift_memory(
+                runtime.store,
+                &lifter,
+                &self.ok_type,
+                &self.err_type,
+                offset,
+            )
+            .map_err(|e| InstructionError::from_li(instruction.clone(), e))?;
+
+            log::debug!("result.lift_memory: pushing {:?} on the stack", result);
+            runtime.stack.push(result);
+
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+struct ResultLowerMemoryAsync {
+    instruction: Instruction,
+    ok_type: IType,
+    err_type: IType,
+    memory_index: usize,
+}
+
+pub(crate) fn result_lower_memory<Instance, Export, LocalImport, Memory, MemoryView, Store>(
+    instruction: Instruction,
+    ok_type: IType,
+    err_type: IType,
+    memory_index: usize,
+) -> ExecutableInstruction<Instance, Export, LocalImport, Memory, MemoryView, Store>
+where
+    Export: crate::interpreter::wasm::structures::Export,
+    LocalImport: crate::interpreter::wasm::structures::LocalImport<Store>,
+    Memory: crate::interpreter::wasm::structures::Memory<MemoryView, Store>,
+    MemoryView: crate::interpreter::wasm::structures::MemoryView<Store>,
+    Instance: crate::interpreter::wasm::structures::Instance<
+        Export,
+        LocalImport,
+        Memory,
+        MemoryView,
+        Store,
+    >,
+    Store: crate::interpreter::wasm::structures::Store,
+{
+    ExecutableInstruction::Async(Box::new(ResultLowerMemoryAsync {
+        instruction,
+        ok_type,
+        err_type,
+        memory_index,
+    }))
+}
+
+impl<Instance, Export, LocalImport, Memory, MemoryView, Store>
+    AsyncExecutableInstructionImpl<Instance, Export, LocalImport, Memory, MemoryView, Store>
+    for ResultLowerMemoryAsync
+where
+    Export: crate::interpreter::wasm::structures::Export,
+    LocalImport: crate::interpreter::wasm::structures::LocalImport<Store>,
+    Memory: crate::interpreter::wasm::structures::Memory<MemoryView, Store>,
+    MemoryView: crate::interpreter::wasm::structures::MemoryView<Store>,
+    Instance: crate::interpreter::wasm::structures::Instance<
+        Export,
+        LocalImport,
+        Memory,
+        MemoryView,
+        Store,
+    >,
+    Store: crate::interpreter::wasm::structures::Store,
+{
+    fn execute<'args>(
+        &'args self,
+        runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>,
+    ) -> BoxFuture<InstructionResult<()>> {
+        async move {
+            let instruction = &self.instruction;
+            let instance = &mut runtime.wasm_instance;
+
+            match runtime.stack.pop1() {
+                Some(IValue::Result(result)) => {
+                    let (value_type, value) = match &result {
+                        Ok(value) => (&self.ok_type, value),
+                        Err(value) => (&self.err_type, value),
+                    };
+                    is_value_compatible_to_type(&**instance, value_type, value)
+                        .map_err(|e| InstructionError::from_error_kind(instruction.clone(), e))?;
+
+                    log::debug!(
+                        "result.lower_memory: obtained {:?} value on the stack for ok type {:?}, err type {:?}",
+                        result,
+                        self.ok_type,
+                        self.err_type
+                    );
+
+                    let memory_index = self.memory_index;
+                    let memory_view = instance
+                        .memory(memory_index)
+                        .ok_or_else(|| {
+                            InstructionError::from_error_kind(
+                                instruction.clone(),
+                                InstructionErrorKind::MemoryIsMissing { memory_index },
+                            )
+                        })?
+                        .view();
+
+                    let address_type = memory_view.address_type();
+                    let mut lo_helper = lilo::LoHelper::new(&**instance);
+                    let mut lowerer = ILowerer::new(memory_view, memory_index, &mut lo_helper)
+                        .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
+
+                    let offset =
+                        it_lilo::lowerer::result_lower_memory(runtime.store, &mut lowerer, result)
+                            .await
+                            .map_err(|e| InstructionError::from_lo(instruction.clone(), e))?;
+
+                    log::debug!("result.lower_memory: pushing {} on the stack", offset);
+                    runtime.stack.push(pointer_to_ivalue(offset, address_type));
+
+                    Ok(())
+                }
+                Some(value) => instr_error!(
+                    instruction.clone(),
+                    InstructionErrorKind::InvalidValueOnTheStack {
+                        expected_type: IType::Result(
+                            Box::new(self.ok_type.clone()),
+                            Box::new(self.err_type.clone())
+                        ),
+                        received_value: value,
+                    }
+                ),
+                None => instr_error!(
+                    instruction.clone(),
+                    InstructionErrorKind::StackIsTooSmall { needed: 1 }
+                ),
+            }
+        }
+        .boxed()
+    }
+}
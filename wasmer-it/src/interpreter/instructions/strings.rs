@@ -1,4 +1,6 @@
+use super::pointer_to_ivalue;
 use super::to_native;
+use super::to_native_pointer;
 use crate::instr_error;
 use crate::IType;
 use crate::IValue;
@@ -8,19 +10,19 @@ use crate::{
     interpreter::Instruction,
     interpreter::Runtime,
 };
-
-use it_lilo::traits::DEFAULT_MEMORY_INDEX;
+use it_memory_traits::AddressType;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 
 struct StringLiftMemory {
+    memory_index: usize,
     instruction: Instruction,
 }
 
 impl_async_executable_instruction!(
-    string_lift_memory(instruction: Instruction) -> _ {
-        Box::new(StringLiftMemory{instruction})
+    string_lift_memory(memory_index: usize, instruction: Instruction) -> _ {
+        Box::new(StringLiftMemory { memory_index, instruction })
     }
     StringLiftMemory {
         fn execute<'args>(&'args self, runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>) -> BoxFuture<InstructionResult<()>> {
@@ -33,7 +35,7 @@ impl_async_executable_instruction!(
                     )
                 })?;
 
-                let memory_index = DEFAULT_MEMORY_INDEX;
+                let memory_index = self.memory_index;
                 let memory = runtime
                     .wasm_instance
                     .memory(memory_index)
@@ -44,9 +46,10 @@ impl_async_executable_instruction!(
                         )
                     })?;
 
-                let pointer = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
-                let length = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
                 let memory_view = memory.view();
+                let address_type = memory_view.address_type();
+                let pointer = to_native_pointer(inputs.remove(0), address_type, instruction.clone())?;
+                let length = to_native_pointer(inputs.remove(0), address_type, instruction.clone())?;
 
                 if length == 0 {
                     runtime.stack.push(IValue::String("".into()));
@@ -54,11 +57,9 @@ impl_async_executable_instruction!(
                     return Ok(())
                 }
 
-                memory_view
-                    .check_bounds(runtime.store, pointer, length)
+                let data = memory_view
+                    .try_read_vec(runtime.store, pointer, length)
                     .map_err(|e| InstructionError::from_memory_access(instruction.clone(), e))?;
-
-                let data = memory_view.read_vec(runtime.store, pointer, length);
                 let string = String::from_utf8(data)
                     .map_err(|error| InstructionError::from_error_kind(instruction.clone(), InstructionErrorKind::String(error)))?;
 
@@ -72,12 +73,13 @@ impl_async_executable_instruction!(
 );
 
 struct StringLowerMemoryAsync {
+    memory_index: usize,
     instruction: Instruction,
 }
 
 impl_async_executable_instruction!(
-    string_lower_memory(instruction: Instruction) -> _ {
-        Box::new(StringLowerMemoryAsync {instruction})
+    string_lower_memory(memory_index: usize, instruction: Instruction) -> _ {
+        Box::new(StringLowerMemoryAsync { memory_index, instruction })
     }
     StringLowerMemoryAsync {
         fn execute<'args>(&'args self, runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>) -> BoxFuture<InstructionResult<()>> {
@@ -90,13 +92,13 @@ impl_async_executable_instruction!(
                     )
                 })?;
 
-                let string_pointer = to_native::<i32>(inputs.remove(0), instruction.clone())? as u32;
+                let string_pointer = inputs.remove(0);
                 let string: String = to_native(inputs.remove(0), instruction.clone())?;
                 let string_bytes = string.as_bytes();
-                let string_length: u32 = string_bytes.len() as u32;
+                let string_length = string_bytes.len() as u64;
 
                 let instance = &mut runtime.wasm_instance;
-                let memory_index = DEFAULT_MEMORY_INDEX;
+                let memory_index = self.memory_index;
                 let memory_view = instance
                     .memory_view(memory_index)
                     .ok_or_else(|| {
@@ -106,15 +108,15 @@ impl_async_executable_instruction!(
                         )
                     })?;
 
+                let address_type = memory_view.address_type();
+                let string_pointer = to_native_pointer(string_pointer, address_type, instruction.clone())?;
                 memory_view
-                    .check_bounds(runtime.store, string_pointer, string_length)
+                    .try_write_bytes(runtime.store, string_pointer, string_bytes)
                     .map_err(|e| InstructionError::from_memory_access(instruction.clone(), e))?;
 
-                memory_view.write_bytes(runtime.store, string_pointer, string_bytes);
-
                 log::debug!("string.lower_memory: pushing {}, {} on the stack", string_pointer, string_length);
-                runtime.stack.push(IValue::I32(string_pointer as i32));
-                runtime.stack.push(IValue::I32(string_length as i32));
+                runtime.stack.push(pointer_to_ivalue(string_pointer, address_type));
+                runtime.stack.push(pointer_to_ivalue(string_length, address_type));
 
                 Ok(())
             }.boxed()
@@ -135,12 +137,19 @@ impl_async_executable_instruction!(
         fn execute<'args>(&'args self, runtime: &'args mut Runtime<Instance, Export, LocalImport, Memory, MemoryView, Store>) -> BoxFuture<InstructionResult<()>> {
             async move {
             let instruction = &self.instruction;
+            // the size is sized like pointers of the default memory, I32 without a memory
+            let address_type = runtime
+                .wasm_instance
+                .memory_view(it_lilo::traits::DEFAULT_MEMORY_INDEX)
+                .map(|memory_view| memory_view.address_type())
+                .unwrap_or(AddressType::I32);
+
             match runtime.stack.pop1() {
                 Some(IValue::String(string)) => {
-                    let length = string.len() as i32;
+                    let length = string.len() as u64;
 
                     log::debug!("string.size: pushing {} on the stack", length);
-                    runtime.stack.push(IValue::I32(length));
+                    runtime.stack.push(pointer_to_ivalue(length, address_type));
 
                     Ok(())
                 },
@@ -170,7 +179,7 @@ mod tests {
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(0),
@@ -179,25 +188,86 @@ mod tests {
                 //              ^^^^^^^ length
             ],
             instance: Instance {
-                memory: Memory::new("Hello, World!".as_bytes().iter().map(|u| Cell::new(*u)).collect()),
+                memory: Memory::from(b"Hello, World!".to_vec()),
                 ..Default::default()
             },
             stack: [IValue::String("Hello, World!".into())],
     );
 
+    test_executable_instruction!(
+        test_string_lift_memory__second_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::StringLiftMemory { memory_index: 1 },
+            ],
+            invocation_inputs: [
+                IValue::I32(0),
+                IValue::I32(6),
+            ],
+            instance: Instance {
+                memory: Memory::from(b"Hello!".to_vec()),
+                other_memories: vec![Memory::from(b"World!".to_vec())],
+                ..Default::default()
+            },
+            stack: [IValue::String("World!".into())],
+    );
+
+    test_executable_instruction!(
+        test_string_lower_memory__second_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::StringLowerMemory { memory_index: 1 },
+                Instruction::StringLiftMemory { memory_index: 1 },
+                Instruction::PushI32 { value: 0 },
+                Instruction::PushI32 { value: 2 },
+                Instruction::StringLiftMemory { memory_index: 0 },
+            ],
+            invocation_inputs: [
+                IValue::I32(0),
+                IValue::String("Hi".into()),
+            ],
+            instance: Instance {
+                memory: Memory::from(b"Hello!".to_vec()),
+                other_memories: vec![Memory::from(b"World!".to_vec())],
+                ..Default::default()
+            },
+            stack: [IValue::String("Hi".into()), IValue::String("He".into())],
+    );
+
+    test_executable_instruction!(
+        test_string_lift_memory__memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::StringLiftMemory { memory_index: 0 },
+            ],
+            invocation_inputs: [
+                IValue::I64(7),
+                IValue::I64(6),
+            ],
+            instance: Instance {
+                memory: Memory::from(b"Hello, World!".to_vec())
+                    .with_address_type(it_memory_traits::AddressType::I64),
+                ..Default::default()
+            },
+            stack: [IValue::String("World!".into())],
+    );
+
     test_executable_instruction!(
         test_string_lift_memory__empty_string =
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(0),
                 IValue::I32(0),
             ],
             instance: Instance {
-                memory: Memory::new(vec![]),
+                memory: Memory::from(vec![]),
                 ..Default::default()
             },
             stack: [IValue::String("".into())],
@@ -208,17 +278,17 @@ mod tests {
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(-42),
                 IValue::I32(13),
             ],
             instance: Instance {
-                memory: Memory::new("Hello!".as_bytes().iter().map(|u| Cell::new(*u)).collect()),
+                memory: Memory::from(b"Hello!".to_vec()),
                 ..Default::default()
             },
-            error: r#"`string.lift_memory` attempted to convert `pointer` but it appears to be a negative value"#,
+            error: r#"`string.lift_memory` read out of the memory bounds (index 4294967267 > memory length 6)"#,
     );
 
     test_executable_instruction!(
@@ -226,17 +296,33 @@ mod tests {
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(0),
                 IValue::I32(-1),
             ],
             instance: Instance {
-                memory: Memory::new("Hello!".as_bytes().iter().map(|u| Cell::new(*u)).collect()),
+                memory: Memory::from(b"Hello!".to_vec()),
                 ..Default::default()
             },
-            error: r#"`string.lift_memory` attempted to convert `length` but it appears to be a negative value"#,
+            error: r#"`string.lift_memory` read out of the memory bounds (index 4294967295 > memory length 6)"#,
+    );
+
+    test_executable_instruction!(
+        test_string_lift_memory__missing_memory =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::ArgumentGet { index: 1 },
+                Instruction::StringLiftMemory { memory_index: 1 },
+                //                                            ^ there is only memory 0
+            ],
+            invocation_inputs: [
+                IValue::I32(0),
+                IValue::I32(13),
+            ],
+            instance: Instance::new(),
+            error: "`string.lift_memory 1` memory `1` does not exist",
     );
 
     test_executable_instruction!(
@@ -244,7 +330,7 @@ mod tests {
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(0),
@@ -253,7 +339,7 @@ mod tests {
                 //              ^^^^^^^ length is too long
             ],
             instance: Instance {
-                memory: Memory::new("Hello!".as_bytes().iter().map(|u| Cell::new(*u)).collect()),
+                memory: Memory::from(b"Hello!".to_vec()),
                 ..Default::default()
             },
             error: r#"`string.lift_memory` read out of the memory bounds (index 13 > memory length 6)"#,
@@ -264,7 +350,7 @@ mod tests {
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
                 Instruction::ArgumentGet { index: 1 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [
                 IValue::I32(0),
@@ -273,7 +359,7 @@ mod tests {
                 //              ^^^^^^ length is too long
             ],
             instance: Instance {
-                memory: Memory::new(vec![0, 159, 146, 150].iter().map(|b| Cell::new(*b)).collect::<Vec<Cell<u8>>>()),
+                memory: Memory::from(vec![0, 159, 146, 150]),
                 ..Default::default()
             },
             error: r#"`string.lift_memory` invalid utf-8 sequence of 1 bytes from index 1"#,
@@ -283,7 +369,7 @@ mod tests {
         test_string_lift_memory__stack_is_too_small =
             instructions: [
                 Instruction::ArgumentGet { index: 0 },
-                Instruction::StringLiftMemory,
+                Instruction::StringLiftMemory { memory_index: 0 },
                 //           ^^^^^^^^^^^^^^^^ `string.lift_memory` expects 2 values on the stack, only one is present.
             ],
             invocation_inputs: [
@@ -301,7 +387,7 @@ mod tests {
                 Instruction::StringSize,
                 Instruction::CallCore { function_index: 43 },
                 Instruction::ArgumentGet { index: 0 },
-                Instruction::StringLowerMemory,
+                Instruction::StringLowerMemory { memory_index: 0 },
 
             ],
             invocation_inputs: [IValue::String("Hello, World!".into())],
@@ -321,8 +407,8 @@ mod tests {
                 Instruction::StringSize,
                 Instruction::CallCore { function_index: 43 },
                 Instruction::ArgumentGet { index: 0 },
-                Instruction::StringLowerMemory,
-                Instruction::StringLiftMemory,
+                Instruction::StringLowerMemory { memory_index: 0 },
+                Instruction::StringLiftMemory { memory_index: 0 },
             ],
             invocation_inputs: [IValue::String("Hello, World!".into())],
             instance: Instance::new(),
@@ -332,7 +418,7 @@ mod tests {
     test_executable_instruction!(
         test_string_lower_memory__stack_is_too_small =
             instructions: [
-                Instruction::StringLowerMemory,
+                Instruction::StringLowerMemory { memory_index: 0 },
             ],
             invocation_inputs: [],
             instance: Instance::new(),
@@ -350,6 +436,17 @@ mod tests {
             stack: [IValue::I32(13)],
     );
 
+    test_executable_instruction!(
+        test_string_size__memory64 =
+            instructions: [
+                Instruction::ArgumentGet { index: 0 },
+                Instruction::StringSize,
+            ],
+            invocation_inputs: [IValue::String("Hello, World!".into())],
+            instance: Instance::with_address_type(it_memory_traits::AddressType::I64),
+            stack: [IValue::I64(13)],
+    );
+
     test_executable_instruction!(
         test_string_size__stack_is_too_small =
             instructions: [
@@ -368,6 +465,6 @@ mod tests {
             ],
             invocation_inputs: [IValue::I32(42)],
             instance: Instance::new(),
-            error: r#"`string.size` read a value of type `I32` from the stack, but the type `String` was expected"#,
+            error: r#"`string.size` read a value `String` from the stack, that can't be converted to `I32(42)`"#,
     );
 }
@@ -1,5 +1,6 @@
 //! A stack-based interpreter to execute instructions of WIT adapters.
 
+pub mod handles;
 mod instructions;
 pub mod stack;
 pub mod wasm;
@@ -417,34 +418,97 @@ where
                 Instruction::I64FromU16 => instructions::i64_from_u16(instruction),
                 Instruction::I64FromU32 => instructions::i64_from_u32(instruction),
                 Instruction::I64FromU64 => instructions::i64_from_u64(instruction),
+                Instruction::CharFromI32 => instructions::char_from_i32(instruction),
+                Instruction::I32FromChar => instructions::i32_from_char(instruction),
+                Instruction::U128FromI64 => instructions::u128_from_i64(instruction),
+                Instruction::I64FromU128 => instructions::i64_from_u128(instruction),
+                Instruction::S128FromI64 => instructions::s128_from_i64(instruction),
+                Instruction::I64FromS128 => instructions::i64_from_s128(instruction),
                 Instruction::PushI32 { value } => instructions::push_i32(value),
                 Instruction::PushI64 { value } => instructions::push_i64(value),
 
-                Instruction::StringLiftMemory => instructions::string_lift_memory(instruction),
-                Instruction::StringLowerMemory => instructions::string_lower_memory(instruction),
+                Instruction::StringLiftMemory { memory_index } => {
+                    instructions::string_lift_memory(memory_index as _, instruction)
+                }
+                Instruction::StringLowerMemory { memory_index } => {
+                    instructions::string_lower_memory(memory_index as _, instruction)
+                }
                 Instruction::StringSize => instructions::string_size(instruction),
 
-                Instruction::ByteArrayLiftMemory => {
-                    instructions::byte_array_lift_memory(instruction)
+                Instruction::ByteArrayLiftMemory { memory_index } => {
+                    instructions::byte_array_lift_memory(memory_index as _, instruction)
                 }
-                Instruction::ByteArrayLowerMemory => {
-                    instructions::byte_array_lower_memory(instruction)
+                Instruction::ByteArrayLowerMemory { memory_index } => {
+                    instructions::byte_array_lower_memory(memory_index as _, instruction)
                 }
                 Instruction::ByteArraySize => instructions::byte_array_size(instruction),
 
-                Instruction::ArrayLiftMemory { ref value_type } => {
+                Instruction::ArrayLiftMemory {
+                    ref value_type,
+                    memory_index,
+                } => {
                     let value_type = value_type.clone();
-                    instructions::array_lift_memory(instruction, value_type)
+                    instructions::array_lift_memory(instruction, value_type, memory_index as _)
                 }
-                Instruction::ArrayLowerMemory { ref value_type } => {
+                Instruction::ArrayLowerMemory {
+                    ref value_type,
+                    memory_index,
+                } => {
                     let value_type = value_type.clone();
-                    instructions::array_lower_memory(instruction, value_type)
+                    instructions::array_lower_memory(instruction, value_type, memory_index as _)
                 }
-                Instruction::RecordLiftMemory { record_type_id } => {
-                    instructions::record_lift_memory(record_type_id as _, instruction)
+                Instruction::RecordLiftMemory {
+                    record_type_id,
+                    memory_index,
+                } => instructions::record_lift_memory(
+                    record_type_id as _,
+                    memory_index as _,
+                    instruction,
+                ),
+                Instruction::RecordLowerMemory {
+                    record_type_id,
+                    memory_index,
+                } => instructions::record_lower_memory(
+                    record_type_id as _,
+                    memory_index as _,
+                    instruction,
+                ),
+                Instruction::ResultLiftMemory {
+                    ref ok_type,
+                    ref err_type,
+                    memory_index,
+                } => {
+                    let ok_type = ok_type.clone();
+                    let err_type = err_type.clone();
+                    instructions::result_lift_memory(
+                        instruction,
+                        ok_type,
+                        err_type,
+                        memory_index as _,
+                    )
                 }
-                Instruction::RecordLowerMemory { record_type_id } => {
-                    instructions::record_lower_memory(record_type_id as _, instruction)
+                Instruction::ResultLowerMemory {
+                    ref ok_type,
+                    ref err_type,
+                    memory_index,
+                } => {
+                    let ok_type = ok_type.clone();
+                    let err_type = err_type.clone();
+                    instructions::result_lower_memory(
+                        instruction,
+                        ok_type,
+                        err_type,
+                        memory_index as _,
+                    )
+                }
+                Instruction::HandleCreate { resource_id } => {
+                    instructions::handle_create(resource_id as _, instruction)
+                }
+                Instruction::HandleBorrow { resource_id } => {
+                    instructions::handle_borrow(resource_id as _, instruction)
+                }
+                Instruction::HandleDrop { resource_id } => {
+                    instructions::handle_drop(resource_id as _, instruction)
                 }
                 Instruction::Dup => instructions::dup(instruction),
                 Instruction::Swap2 => instructions::swap2(instruction),
@@ -1,9 +1,13 @@
 #![allow(missing_docs)]
 
 use crate::ast::FunctionArg;
+use crate::interpreter::handles::HandleTable;
+use crate::IEnumType;
+use crate::IFlagsType;
 use crate::IRecordType;
 use crate::IType;
 use crate::IValue;
+use crate::IVariantType;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -89,6 +93,36 @@ where
     fn memory(&self, index: usize) -> Option<&M>;
     fn memory_view(&self, index: usize) -> Option<MV>;
     fn wit_record_by_id(&self, index: u64) -> Option<&Arc<IRecordType>>;
+
+    /// Returns a variant type, by default no variants are known.
+    fn wit_variant_by_id(&self, _index: u64) -> Option<&Arc<IVariantType>> {
+        None
+    }
+
+    /// Returns a flags type, by default no flags are known.
+    fn wit_flags_by_id(&self, _index: u64) -> Option<&Arc<IFlagsType>> {
+        None
+    }
+
+    /// Returns an enum type, by default no enums are known.
+    fn wit_enum_by_id(&self, _index: u64) -> Option<&Arc<IEnumType>> {
+        None
+    }
+
+    /// Returns the type an alias refers to, by default no aliases are known.
+    fn wit_alias_by_id(&self, _index: u64) -> Option<&IType> {
+        None
+    }
+
+    /// Returns the table of handles, by default handles aren't supported.
+    fn handle_table(&self) -> Option<&HandleTable> {
+        None
+    }
+
+    /// Returns the mutable table of handles, by default handles aren't supported.
+    fn handle_table_mut(&mut self) -> Option<&mut HandleTable> {
+        None
+    }
 }
 
 impl<Store: self::Store> LocalImport<Store> for () {
@@ -150,21 +184,21 @@ impl Export for () {
 pub(crate) struct EmptyMemoryView;
 
 impl<S: Store> MemoryWritable<S> for EmptyMemoryView {
-    fn write_byte(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u32, _value: u8) {}
+    fn write_byte(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u64, _value: u8) {}
 
-    fn write_bytes(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u32, _bytes: &[u8]) {
+    fn write_bytes(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u64, _bytes: &[u8]) {
     }
 }
 
 impl<S: Store> MemoryReadable<S> for EmptyMemoryView {
-    fn read_byte(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u32) -> u8 {
+    fn read_byte(&self, _store: &mut <S as Store>::ActualStore<'_>, _offset: u64) -> u8 {
         0
     }
 
     fn read_array<const COUNT: usize>(
         &self,
         _store: &mut <S as Store>::ActualStore<'_>,
-        _offset: u32,
+        _offset: u64,
     ) -> [u8; COUNT] {
         [0; COUNT]
     }
@@ -172,19 +206,28 @@ impl<S: Store> MemoryReadable<S> for EmptyMemoryView {
     fn read_vec(
         &self,
         _store: &mut <S as Store>::ActualStore<'_>,
-        _offset: u32,
-        _size: u32,
+        _offset: u64,
+        _size: u64,
     ) -> Vec<u8> {
         Vec::default()
     }
+
+    fn read_into(
+        &self,
+        _store: &mut <S as Store>::ActualStore<'_>,
+        _offset: u64,
+        buffer: &mut [u8],
+    ) {
+        buffer.fill(0);
+    }
 }
 
 impl<S: Store> MemoryView<S> for EmptyMemoryView {
     fn check_bounds(
         &self,
         _store: &mut <S as Store>::ActualStore<'_>,
-        offset: u32,
-        size: u32,
+        offset: u64,
+        size: u64,
     ) -> Result<(), MemoryAccessError> {
         Err(MemoryAccessError::OutOfBounds {
             size,
@@ -227,4 +270,28 @@ where
     fn wit_record_by_id(&self, _index: u64) -> Option<&Arc<IRecordType>> {
         None
     }
+
+    fn wit_variant_by_id(&self, _index: u64) -> Option<&Arc<IVariantType>> {
+        None
+    }
+
+    fn wit_flags_by_id(&self, _index: u64) -> Option<&Arc<IFlagsType>> {
+        None
+    }
+
+    fn wit_enum_by_id(&self, _index: u64) -> Option<&Arc<IEnumType>> {
+        None
+    }
+
+    fn wit_alias_by_id(&self, _index: u64) -> Option<&IType> {
+        None
+    }
+
+    fn handle_table(&self) -> Option<&HandleTable> {
+        None
+    }
+
+    fn handle_table_mut(&mut self) -> Option<&mut HandleTable> {
+        None
+    }
 }
@@ -0,0 +1,133 @@
+use crate::IType;
+
+use it_lilo::traits::RecordResolvableError;
+use it_lilo::type_checker::TypeCheckErrorKind;
+use it_lilo::type_checker::ValuePath;
+use serde_json::Value as JValue;
+use thiserror::Error as ThisError;
+
+use std::fmt;
+
+/// Describes why a value can't be converted.
+#[derive(Debug, ThisError)]
+pub enum JsonErrorKind {
+    /// The JSON value has another type.
+    #[error("expected a value of type '{}', but {value} was found", .expected_type.to_string())]
+    TypeMismatch {
+        /// The type the value was converted to.
+        expected_type: IType,
+
+        /// The offending JSON value.
+        value: JValue,
+    },
+
+    /// The JSON number doesn't fit into the integer type.
+    #[error("{value} is out of range of type '{}'", .expected_type.to_string())]
+    OutOfRange {
+        /// The integer type the value was converted to.
+        expected_type: IType,
+
+        /// The offending JSON number.
+        value: JValue,
+    },
+
+    /// A record field is absent in the JSON object.
+    #[error("field is missing")]
+    MissingField,
+
+    /// The JSON object has a field the record doesn't have.
+    #[error("record with name '{record_name}' doesn't have such field")]
+    UnknownField {
+        /// The name of the record type.
+        record_name: String,
+    },
+
+    /// A JSON array for a record with anonymous fields has another length.
+    #[error("record with name '{record_name}' has {expected} fields, but {actual} were found")]
+    FieldsCountMismatch {
+        /// The name of the record type.
+        record_name: String,
+
+        /// The number of fields of the record type.
+        expected: usize,
+
+        /// The length of the JSON array.
+        actual: usize,
+    },
+
+    /// A map entry isn't a `[key, value]` pair.
+    #[error("expected a [key, value] pair, but {0} was found")]
+    InvalidMapEntry(JValue),
+
+    /// A byte array string isn't valid base64.
+    #[error("invalid base64 string")]
+    InvalidBase64,
+
+    /// The variant doesn't have a case with such name.
+    #[error("variant with name '{variant_name}' doesn't have a case '{case_name}'")]
+    UnknownVariantCase {
+        /// The name of the variant type.
+        variant_name: String,
+
+        /// The offending case name.
+        case_name: String,
+    },
+
+    /// The flags don't have a flag with such name.
+    #[error("flags with name '{flags_name}' don't have a flag '{flag}'")]
+    UnknownFlag {
+        /// The name of the flags type.
+        flags_name: String,
+
+        /// The offending flag name.
+        flag: String,
+    },
+
+    /// The enum doesn't have a member with such name.
+    #[error("enum with name '{enum_name}' doesn't have a member '{member}'")]
+    UnknownEnumMember {
+        /// The name of the enum type.
+        enum_name: String,
+
+        /// The offending member name.
+        member: String,
+    },
+
+    /// JSON numbers can't be NaN or infinite.
+    #[error("{0} can't be represented in JSON")]
+    NonFiniteFloat(f64),
+
+    /// The interface value doesn't match its type.
+    #[error("{0}")]
+    TypeCheck(#[from] TypeCheckErrorKind),
+
+    /// A type id can't be resolved.
+    #[error("{0}")]
+    RecordResolvableError(#[from] RecordResolvableError),
+}
+
+/// A conversion error along with the path to the offending value.
+#[derive(Debug)]
+pub struct JsonError {
+    /// The path to the offending value.
+    pub path: ValuePath,
+
+    /// The error kind.
+    pub kind: JsonErrorKind,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path, self.kind)
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.kind)
+    }
+}
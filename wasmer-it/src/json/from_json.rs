@@ -0,0 +1,448 @@
+use super::JsonError;
+use super::JsonErrorKind;
+use super::JsonResult;
+use crate::IType;
+use crate::IValue;
+use crate::NEVec;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use it_lilo::traits::RecordResolvable;
+use it_lilo::traits::RecordResolvableError;
+use it_lilo::type_checker::PathSegment;
+use it_lilo::type_checker::TypeCheckErrorKind;
+use it_lilo::type_checker::ValuePath;
+use it_lilo::utils::resolve_alias;
+use serde_json::Value as JValue;
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Converts a JSON value into an interface value of type `ty`.
+///
+/// Records are read from JSON objects by field names, or from JSON arrays
+/// if some of their fields are anonymous; missing optional fields are `null`.
+/// Byte arrays are read either from padded base64 strings or from arrays of numbers,
+/// 128-bit integers are read either from numbers or from decimal strings.
+/// Variants are `"case"` or `{"case": payload}`, results are `{"ok": value}`
+/// or `{"err": value}`, maps with string keys are JSON objects and other maps
+/// are arrays of `[key, value]` pairs, flags are arrays of flag names, enums are
+/// member names.
+pub fn json_to_ivalue<R: RecordResolvable>(
+    resolver: &R,
+    ty: &IType,
+    value: &JValue,
+) -> JsonResult<IValue> {
+    FromJson {
+        resolver,
+        path: Vec::new(),
+    }
+    .convert(ty, value)
+}
+
+struct FromJson<'r, R: RecordResolvable> {
+    resolver: &'r R,
+    path: Vec<PathSegment>,
+}
+
+impl<'r, R: RecordResolvable> FromJson<'r, R> {
+    fn convert(&mut self, ty: &IType, value: &JValue) -> JsonResult<IValue> {
+        let ivalue = match ty {
+            IType::Boolean => {
+                IValue::Boolean(value.as_bool().ok_or_else(|| self.mismatch(ty, value))?)
+            }
+            IType::S8 => IValue::S8(self.integer(ty, value)?),
+            IType::S16 => IValue::S16(self.integer(ty, value)?),
+            IType::S32 => IValue::S32(self.integer(ty, value)?),
+            IType::S64 => IValue::S64(self.integer(ty, value)?),
+            IType::U8 => IValue::U8(self.integer(ty, value)?),
+            IType::U16 => IValue::U16(self.integer(ty, value)?),
+            IType::U32 => IValue::U32(self.integer(ty, value)?),
+            IType::U64 => IValue::U64(self.integer(ty, value)?),
+            IType::I32 => IValue::I32(self.integer(ty, value)?),
+            IType::I64 => IValue::I64(self.integer(ty, value)?),
+            IType::U128 => IValue::U128(self.wide_integer(ty, value)?),
+            IType::S128 => IValue::S128(self.wide_integer(ty, value)?),
+            IType::F32 => IValue::F32(self.float(ty, value)? as f32),
+            IType::F64 => IValue::F64(self.float(ty, value)?),
+            IType::Char => {
+                let string = self.string(ty, value)?;
+                let mut chars = string.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => IValue::Char(c),
+                    _ => return Err(self.mismatch(ty, value)),
+                }
+            }
+            IType::String => IValue::String(self.string(ty, value)?.to_string()),
+            IType::Handle(_) => IValue::Handle(self.integer(ty, value)?),
+            IType::ByteArray => self.byte_array(ty, value)?,
+            IType::Array(element_ty) => {
+                let is_byte_array = self.resolve_alias(element_ty)? == &IType::U8;
+
+                match value {
+                    JValue::String(_) if is_byte_array => self.byte_array(ty, value)?,
+                    JValue::Array(elements) => IValue::Array(self.elements(element_ty, elements)?),
+                    _ => return Err(self.mismatch(ty, value)),
+                }
+            }
+            IType::Record(record_type_id) => self.record(*record_type_id, ty, value)?,
+            IType::Optional(_) if value.is_null() => IValue::Optional(None),
+            IType::Optional(inner_ty) => {
+                IValue::Optional(Some(Box::new(self.convert(inner_ty, value)?)))
+            }
+            IType::Variant(variant_type_id) => self.variant(*variant_type_id, ty, value)?,
+            IType::Result(ok_ty, err_ty) => {
+                let (arm, payload) = self.single_entry(ty, value)?;
+
+                let result = match arm.as_str() {
+                    "ok" => Ok(Box::new(self.convert_inner(
+                        PathSegment::Field(arm.clone()),
+                        ok_ty,
+                        payload,
+                    )?)),
+                    "err" => Err(Box::new(self.convert_inner(
+                        PathSegment::Field(arm.clone()),
+                        err_ty,
+                        payload,
+                    )?)),
+                    _ => return Err(self.mismatch(ty, value)),
+                };
+
+                IValue::Result(result)
+            }
+            IType::Map(key_ty, value_ty) => self.map(key_ty, value_ty, ty, value)?,
+            IType::Flags(flags_type_id) => {
+                let flags_type = self.resolve(|r| r.resolve_flags(*flags_type_id))?;
+                let names = match value {
+                    JValue::Array(names) => names,
+                    _ => return Err(self.mismatch(ty, value)),
+                };
+
+                let mut bits = 0u64;
+                for (position, name) in names.iter().enumerate() {
+                    self.path.push(PathSegment::Index(position));
+
+                    let name = self.string(&IType::String, name)?;
+                    // flags beyond 64 don't fit in the bits, so they can't be set
+                    let flag = flags_type
+                        .flags
+                        .iter()
+                        .take(crate::IFlagsType::MAX_FLAGS_COUNT)
+                        .position(|flag| flag == name)
+                        .ok_or_else(|| {
+                            self.error(JsonErrorKind::UnknownFlag {
+                                flags_name: flags_type.name.clone(),
+                                flag: name.to_string(),
+                            })
+                        })?;
+                    bits |= 1 << flag;
+
+                    self.path.pop();
+                }
+
+                IValue::Flags(bits)
+            }
+            IType::Enum(enum_type_id) => {
+                let enum_type = self.resolve(|r| r.resolve_enum(*enum_type_id))?;
+                let name = self.string(ty, value)?;
+                let discriminant = enum_type
+                    .members
+                    .iter()
+                    .position(|member| member == name)
+                    .ok_or_else(|| {
+                        self.error(JsonErrorKind::UnknownEnumMember {
+                            enum_name: enum_type.name.clone(),
+                            member: name.to_string(),
+                        })
+                    })?;
+
+                IValue::Enum(discriminant as u32)
+            }
+            IType::Alias(_) => {
+                let ty = self.resolve_alias(ty)?;
+                self.convert(ty, value)?
+            }
+        };
+
+        Ok(ivalue)
+    }
+
+    fn record(&mut self, record_type_id: u64, ty: &IType, value: &JValue) -> JsonResult<IValue> {
+        let record_type = self.resolve(|r| r.resolve_record(record_type_id))?;
+        let is_named = record_type.fields.iter().all(|field| field.name.is_some());
+
+        let fields = match value {
+            JValue::Object(object) if is_named => {
+                if let Some(unknown) = object.keys().find(|key| {
+                    !record_type
+                        .fields
+                        .iter()
+                        .any(|field| field.name.as_ref() == Some(*key))
+                }) {
+                    self.path.push(PathSegment::Field(unknown.clone()));
+                    return Err(self.error(JsonErrorKind::UnknownField {
+                        record_name: record_type.name.clone(),
+                    }));
+                }
+
+                let mut fields = Vec::with_capacity(record_type.fields.len());
+                for field in record_type.fields.iter() {
+                    let name = field.name.as_deref().unwrap_or_default();
+                    self.path.push(PathSegment::Field(name.to_string()));
+
+                    let field_value = match object.get(name) {
+                        Some(field_value) => self.convert(&field.ty, field_value)?,
+                        None => match self.resolve_alias(&field.ty)? {
+                            IType::Optional(_) => IValue::Optional(None),
+                            _ => return Err(self.error(JsonErrorKind::MissingField)),
+                        },
+                    };
+                    fields.push(field_value);
+
+                    self.path.pop();
+                }
+
+                fields
+            }
+            JValue::Array(elements) if !is_named => {
+                if elements.len() != record_type.fields.len() {
+                    return Err(self.error(JsonErrorKind::FieldsCountMismatch {
+                        record_name: record_type.name.clone(),
+                        expected: record_type.fields.len(),
+                        actual: elements.len(),
+                    }));
+                }
+
+                let mut fields = Vec::with_capacity(elements.len());
+                for (position, (field, element)) in
+                    record_type.fields.iter().zip(elements).enumerate()
+                {
+                    let name = match &field.name {
+                        Some(name) => name.clone(),
+                        None => position.to_string(),
+                    };
+                    fields.push(self.convert_inner(
+                        PathSegment::Field(name),
+                        &field.ty,
+                        element,
+                    )?);
+                }
+
+                fields
+            }
+            _ => return Err(self.mismatch(ty, value)),
+        };
+
+        // the record type has at least one field, so is the value
+        Ok(IValue::Record(NEVec::new(fields).unwrap()))
+    }
+
+    fn variant(&mut self, variant_type_id: u64, ty: &IType, value: &JValue) -> JsonResult<IValue> {
+        let variant_type = self.resolve(|r| r.resolve_variant(variant_type_id))?;
+
+        let (case_name, payload) = match value {
+            JValue::String(case_name) => (case_name.clone(), None),
+            _ => {
+                let (case_name, payload) = self.single_entry(ty, value)?;
+                (case_name, Some(payload))
+            }
+        };
+
+        let (discriminant, case) = variant_type
+            .cases
+            .iter()
+            .enumerate()
+            .find(|(_, case)| case.name == case_name)
+            .ok_or_else(|| {
+                self.error(JsonErrorKind::UnknownVariantCase {
+                    variant_name: variant_type.name.clone(),
+                    case_name: case_name.clone(),
+                })
+            })?;
+
+        let payload = match (&case.ty, payload) {
+            (None, None) | (None, Some(JValue::Null)) => None,
+            (Some(case_ty), Some(payload)) => Some(Box::new(self.convert_inner(
+                PathSegment::Field(case_name),
+                case_ty,
+                payload,
+            )?)),
+            _ => {
+                return Err(self.error(
+                    TypeCheckErrorKind::VariantPayloadMismatch {
+                        variant_name: variant_type.name.clone(),
+                        case_name,
+                    }
+                    .into(),
+                ))
+            }
+        };
+
+        Ok(IValue::Variant(discriminant as u32, payload))
+    }
+
+    fn map(
+        &mut self,
+        key_ty: &IType,
+        value_ty: &IType,
+        ty: &IType,
+        value: &JValue,
+    ) -> JsonResult<IValue> {
+        let has_string_keys = self.resolve_alias(key_ty)? == &IType::String;
+
+        let entries = match value {
+            JValue::Object(object) if has_string_keys => {
+                let mut entries = Vec::with_capacity(object.len());
+                for (key, entry_value) in object {
+                    let entry_value =
+                        self.convert_inner(PathSegment::Field(key.clone()), value_ty, entry_value)?;
+                    entries.push((IValue::String(key.clone()), entry_value));
+                }
+
+                entries
+            }
+            JValue::Array(pairs) => {
+                let mut entries = Vec::with_capacity(pairs.len());
+                for (position, pair) in pairs.iter().enumerate() {
+                    self.path.push(PathSegment::Index(position));
+
+                    let (key, entry_value) = match pair.as_array().map(Vec::as_slice) {
+                        Some([key, entry_value]) => (key, entry_value),
+                        _ => return Err(self.error(JsonErrorKind::InvalidMapEntry(pair.clone()))),
+                    };
+                    let key = self.convert_inner(PathSegment::Index(0), key_ty, key)?;
+                    let entry_value =
+                        self.convert_inner(PathSegment::Index(1), value_ty, entry_value)?;
+                    entries.push((key, entry_value));
+
+                    self.path.pop();
+                }
+
+                entries
+            }
+            _ => return Err(self.mismatch(ty, value)),
+        };
+
+        Ok(IValue::Map(entries))
+    }
+
+    fn byte_array(&self, ty: &IType, value: &JValue) -> JsonResult<IValue> {
+        match value {
+            JValue::String(encoded) => BASE64
+                .decode(encoded)
+                .map(IValue::ByteArray)
+                .map_err(|_| self.error(JsonErrorKind::InvalidBase64)),
+            JValue::Array(elements) => {
+                let mut bytes = Vec::with_capacity(elements.len());
+                for element in elements {
+                    bytes.push(self.integer(&IType::U8, element)?);
+                }
+
+                Ok(IValue::ByteArray(bytes))
+            }
+            _ => Err(self.mismatch(ty, value)),
+        }
+    }
+
+    fn elements(&mut self, ty: &IType, elements: &[JValue]) -> JsonResult<Vec<IValue>> {
+        elements
+            .iter()
+            .enumerate()
+            .map(|(position, element)| {
+                self.convert_inner(PathSegment::Index(position), ty, element)
+            })
+            .collect()
+    }
+
+    fn convert_inner(
+        &mut self,
+        segment: PathSegment,
+        ty: &IType,
+        value: &JValue,
+    ) -> JsonResult<IValue> {
+        self.path.push(segment);
+        let value = self.convert(ty, value)?;
+        self.path.pop();
+
+        Ok(value)
+    }
+
+    /// Returns the only entry of a JSON object like `{"ok": value}`.
+    fn single_entry<'v>(&self, ty: &IType, value: &'v JValue) -> JsonResult<(String, &'v JValue)> {
+        match value {
+            JValue::Object(object) if object.len() == 1 => {
+                let (key, value) = object.iter().next().unwrap();
+                Ok((key.clone(), value))
+            }
+            _ => Err(self.mismatch(ty, value)),
+        }
+    }
+
+    fn integer<T>(&self, ty: &IType, value: &JValue) -> JsonResult<T>
+    where
+        T: TryFrom<i64> + TryFrom<u64>,
+    {
+        let result = match (value.as_i64(), value.as_u64()) {
+            (Some(number), _) => T::try_from(number).ok(),
+            (_, Some(number)) => T::try_from(number).ok(),
+            _ => return Err(self.mismatch(ty, value)),
+        };
+
+        result.ok_or_else(|| self.out_of_range(ty, value))
+    }
+
+    fn wide_integer<T>(&self, ty: &IType, value: &JValue) -> JsonResult<T>
+    where
+        T: TryFrom<i64> + TryFrom<u64> + FromStr,
+    {
+        match value {
+            JValue::Number(_) => self.integer(ty, value),
+            JValue::String(number) => number.parse().map_err(|_| self.out_of_range(ty, value)),
+            _ => Err(self.mismatch(ty, value)),
+        }
+    }
+
+    fn float(&self, ty: &IType, value: &JValue) -> JsonResult<f64> {
+        value.as_f64().ok_or_else(|| self.mismatch(ty, value))
+    }
+
+    fn string<'v>(&self, ty: &IType, value: &'v JValue) -> JsonResult<&'v str> {
+        value.as_str().ok_or_else(|| self.mismatch(ty, value))
+    }
+
+    fn resolve_alias<'t>(&self, ty: &'t IType) -> JsonResult<&'t IType>
+    where
+        'r: 't,
+    {
+        resolve_alias(self.resolver, ty).map_err(|e| self.error(e.into()))
+    }
+
+    fn resolve<T: ?Sized>(
+        &self,
+        resolve: impl FnOnce(&'r R) -> Result<&'r T, RecordResolvableError>,
+    ) -> JsonResult<&'r T> {
+        resolve(self.resolver).map_err(|e| self.error(e.into()))
+    }
+
+    fn mismatch(&self, ty: &IType, value: &JValue) -> JsonError {
+        self.error(JsonErrorKind::TypeMismatch {
+            expected_type: ty.clone(),
+            value: value.clone(),
+        })
+    }
+
+    fn out_of_range(&self, ty: &IType, value: &JValue) -> JsonError {
+        self.error(JsonErrorKind::OutOfRange {
+            expected_type: ty.clone(),
+            value: value.clone(),
+        })
+    }
+
+    fn error(&self, kind: JsonErrorKind) -> JsonError {
+        JsonError {
+            path: ValuePath(self.path.clone()),
+            kind,
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Conversion between JSON values and interface values, guided by interface types.
+//!
+//! `IValue::Record` is positional, so the record types are resolved to map
+//! JSON objects to records by field names and back.
+
+mod error;
+mod from_json;
+mod to_json;
+
+pub use error::JsonError;
+pub use error::JsonErrorKind;
+pub use from_json::json_to_ivalue;
+pub use to_json::ivalue_to_json;
+
+/// The result type of JSON conversions.
+pub type JsonResult<T> = std::result::Result<T, JsonError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ne_vec, IEnumType, IFlagsType, IRecordFieldType, IRecordType, IType, IValue,
+        IVariantCaseType, IVariantType, NEVec,
+    };
+
+    use it_lilo::traits::RecordResolvable;
+    use it_lilo::traits::RecordResolvableError;
+    use serde_json::json;
+
+    const USER: u64 = 0;
+    const ADDRESS: u64 = 1;
+    const PAIR: u64 = 2;
+    const STATUS: u64 = 3;
+    const PERMISSIONS: u64 = 4;
+    const COLOR: u64 = 5;
+    const PEER_ID: u64 = 6;
+
+    struct Resolver {
+        records: Vec<IRecordType>,
+        variant: IVariantType,
+        flags: IFlagsType,
+        enum_type: IEnumType,
+        alias: IType,
+    }
+
+    impl Resolver {
+        fn new() -> Self {
+            let field = |name: &str, ty| IRecordFieldType {
+                name: Some(name.to_string()),
+                ty,
+            };
+
+            Self {
+                records: vec![
+                    IRecordType {
+                        name: "User".to_string(),
+                        fields: ne_vec![
+                            field("name", IType::Alias(PEER_ID)),
+                            field("age", IType::U8),
+                            field("avatar", IType::ByteArray),
+                            field("addresses", IType::Array(Box::new(IType::Record(ADDRESS)))),
+                            field("nickname", IType::Optional(Box::new(IType::String))),
+                        ],
+                    },
+                    IRecordType {
+                        name: "Address".to_string(),
+                        fields: ne_vec![field("zip", IType::U32)],
+                    },
+                    IRecordType {
+                        name: "Pair".to_string(),
+                        fields: ne_vec![
+                            IRecordFieldType {
+                                name: None,
+                                ty: IType::S64,
+                            },
+                            IRecordFieldType {
+                                name: None,
+                                ty: IType::U128,
+                            },
+                        ],
+                    },
+                ],
+                variant: IVariantType {
+                    name: "Status".to_string(),
+                    cases: ne_vec![
+                        IVariantCaseType {
+                            name: "idle".to_string(),
+                            ty: None,
+                        },
+                        IVariantCaseType {
+                            name: "busy".to_string(),
+                            ty: Some(IType::U32),
+                        },
+                    ],
+                },
+                flags: IFlagsType {
+                    name: "Permissions".to_string(),
+                    flags: ne_vec!["read".to_string(), "write".to_string()],
+                },
+                enum_type: IEnumType {
+                    name: "Color".to_string(),
+                    members: ne_vec!["red".to_string(), "green".to_string()],
+                },
+                alias: IType::String,
+            }
+        }
+    }
+
+    impl RecordResolvable for Resolver {
+        fn resolve_record(&self, id: u64) -> Result<&IRecordType, RecordResolvableError> {
+            self.records
+                .get(id as usize)
+                .ok_or(RecordResolvableError::RecordNotFound(id))
+        }
+
+        fn resolve_variant(&self, id: u64) -> Result<&IVariantType, RecordResolvableError> {
+            match id {
+                STATUS => Ok(&self.variant),
+                _ => Err(RecordResolvableError::VariantNotFound(id)),
+            }
+        }
+
+        fn resolve_flags(&self, id: u64) -> Result<&IFlagsType, RecordResolvableError> {
+            match id {
+                PERMISSIONS => Ok(&self.flags),
+                _ => Err(RecordResolvableError::FlagsNotFound(id)),
+            }
+        }
+
+        fn resolve_enum(&self, id: u64) -> Result<&IEnumType, RecordResolvableError> {
+            match id {
+                COLOR => Ok(&self.enum_type),
+                _ => Err(RecordResolvableError::EnumNotFound(id)),
+            }
+        }
+
+        fn resolve_alias(&self, id: u64) -> Result<&IType, RecordResolvableError> {
+            match id {
+                PEER_ID => Ok(&self.alias),
+                _ => Err(RecordResolvableError::AliasNotFound(id)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_record_by_field_names() {
+        let resolver = Resolver::new();
+        let json = json!({
+            "addresses": [{"zip": 1}, {"zip": 2}],
+            "age": 42,
+            "avatar": "AQID",
+            "name": "alice",
+        });
+
+        let value = json_to_ivalue(&resolver, &IType::Record(USER), &json).unwrap();
+
+        assert_eq!(
+            value,
+            IValue::Record(ne_vec![
+                IValue::String("alice".to_string()),
+                IValue::U8(42),
+                IValue::ByteArray(vec![1, 2, 3]),
+                IValue::Array(vec![
+                    IValue::Record(ne_vec![IValue::U32(1)]),
+                    IValue::Record(ne_vec![IValue::U32(2)]),
+                ]),
+                IValue::Optional(None),
+            ])
+        );
+
+        let back = ivalue_to_json(&resolver, &IType::Record(USER), &value).unwrap();
+        assert_eq!(
+            back,
+            json!({
+                "addresses": [{"zip": 1}, {"zip": 2}],
+                "age": 42,
+                "avatar": "AQID",
+                "name": "alice",
+                "nickname": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_byte_array_from_numbers() {
+        let resolver = Resolver::new();
+
+        let value = json_to_ivalue(&resolver, &IType::ByteArray, &json!([1, 2, 255])).unwrap();
+        assert_eq!(value, IValue::ByteArray(vec![1, 2, 255]));
+
+        let error = json_to_ivalue(&resolver, &IType::ByteArray, &json!([1, 256])).unwrap_err();
+        assert_eq!(error.to_string(), "256 is out of range of type 'u8'");
+    }
+
+    #[test]
+    fn test_byte_array_from_base64() {
+        let resolver = Resolver::new();
+
+        for (encoded, bytes) in [
+            ("", &b""[..]),
+            ("Zg==", b"f"),
+            ("Zm8=", b"fo"),
+            ("Zm9v", b"foo"),
+        ] {
+            let value = json_to_ivalue(&resolver, &IType::ByteArray, &json!(encoded)).unwrap();
+            assert_eq!(value, IValue::ByteArray(bytes.to_vec()));
+
+            let back = ivalue_to_json(&resolver, &IType::ByteArray, &value).unwrap();
+            assert_eq!(back, json!(encoded));
+        }
+
+        // missing and extra padding, non-zero trailing bits and a symbol out of the alphabet
+        for encoded in ["Zg", "Zg=", "Zg===", "Zh==", "Zm9=", "Zm9v!A=="] {
+            let error = json_to_ivalue(&resolver, &IType::ByteArray, &json!(encoded)).unwrap_err();
+            assert_eq!(error.to_string(), "invalid base64 string", "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_error_path() {
+        let resolver = Resolver::new();
+        let json = json!({
+            "addresses": [{"zip": 1}, {"zip": -1}],
+            "age": 42,
+            "avatar": [],
+            "name": "alice",
+        });
+
+        let error = json_to_ivalue(&resolver, &IType::Record(USER), &json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "addresses[1].zip: -1 is out of range of type 'u32'"
+        );
+
+        let json = json!({"age": 42, "avatar": [], "addresses": []});
+        let error = json_to_ivalue(&resolver, &IType::Record(USER), &json).unwrap_err();
+        assert_eq!(error.to_string(), "name: field is missing");
+
+        let json = json!({"name": "bob", "age": 42, "avatar": [], "addresses": [], "admin": true});
+        let error = json_to_ivalue(&resolver, &IType::Record(USER), &json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "admin: record with name 'User' doesn't have such field"
+        );
+    }
+
+    #[test]
+    fn test_anonymous_fields() {
+        let resolver = Resolver::new();
+        let json = json!([-1, "340282366920938463463374607431768211455"]);
+
+        let value = json_to_ivalue(&resolver, &IType::Record(PAIR), &json).unwrap();
+        assert_eq!(
+            value,
+            IValue::Record(ne_vec![IValue::S64(-1), IValue::U128(u128::MAX)])
+        );
+        assert_eq!(
+            ivalue_to_json(&resolver, &IType::Record(PAIR), &value).unwrap(),
+            json
+        );
+    }
+
+    #[test]
+    fn test_variant_result_map_flags_enum() {
+        let resolver = Resolver::new();
+        let ty = IType::Array(Box::new(IType::Variant(STATUS)));
+        let json = json!(["idle", {"busy": 7}]);
+        let value = json_to_ivalue(&resolver, &ty, &json).unwrap();
+
+        assert_eq!(
+            value,
+            IValue::Array(vec![
+                IValue::Variant(0, None),
+                IValue::Variant(1, Some(Box::new(IValue::U32(7)))),
+            ])
+        );
+        assert_eq!(ivalue_to_json(&resolver, &ty, &value).unwrap(), json);
+
+        let ty = IType::Result(Box::new(IType::Enum(COLOR)), Box::new(IType::String));
+        let value = json_to_ivalue(&resolver, &ty, &json!({"ok": "green"})).unwrap();
+        assert_eq!(value, IValue::Result(Ok(Box::new(IValue::Enum(1)))));
+
+        let ty = IType::Map(Box::new(IType::String), Box::new(IType::Flags(PERMISSIONS)));
+        let json = json!({"alice": ["read", "write"], "bob": []});
+        let value = json_to_ivalue(&resolver, &ty, &json).unwrap();
+        assert_eq!(
+            value,
+            IValue::Map(vec![
+                (IValue::String("alice".to_string()), IValue::Flags(0b11)),
+                (IValue::String("bob".to_string()), IValue::Flags(0)),
+            ])
+        );
+        assert_eq!(ivalue_to_json(&resolver, &ty, &value).unwrap(), json);
+
+        let ty = IType::Map(Box::new(IType::U8), Box::new(IType::Boolean));
+        let json = json!([[1, true], [2, false]]);
+        let value = json_to_ivalue(&resolver, &ty, &json).unwrap();
+        assert_eq!(ivalue_to_json(&resolver, &ty, &value).unwrap(), json);
+
+        let error = json_to_ivalue(&resolver, &ty, &json!([[1, true], [2, 3]])).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "[1][1]: expected a value of type 'bool', but 3 was found"
+        );
+    }
+
+    #[test]
+    fn test_wide_flags() {
+        let mut resolver = Resolver::new();
+        // more flags than fit in the bits, the type can still be built by hand
+        resolver.flags.flags =
+            NEVec::new((0..70).map(|flag| format!("f{}", flag)).collect()).unwrap();
+        let ty = IType::Flags(PERMISSIONS);
+
+        let json = json!(["f0", "f63"]);
+        let value = json_to_ivalue(&resolver, &ty, &json).unwrap();
+        assert_eq!(value, IValue::Flags(1 | 1 << 63));
+        assert_eq!(ivalue_to_json(&resolver, &ty, &value).unwrap(), json);
+
+        assert!(json_to_ivalue(&resolver, &ty, &json!(["f64"])).is_err());
+    }
+
+    #[test]
+    fn test_to_json_type_mismatch() {
+        let resolver = Resolver::new();
+        let value = IValue::Record(ne_vec![IValue::String("zip".to_string())]);
+
+        let error = ivalue_to_json(&resolver, &IType::Record(ADDRESS), &value).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "zip: expected a value of type 'u32', but String(\"zip\") was found"
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use super::JsonError;
+use super::JsonErrorKind;
+use super::JsonResult;
+use crate::IType;
+use crate::IValue;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use it_lilo::traits::RecordResolvable;
+use it_lilo::traits::RecordResolvableError;
+use it_lilo::type_checker::PathSegment;
+use it_lilo::type_checker::TypeCheckErrorKind;
+use it_lilo::type_checker::ValuePath;
+use it_lilo::utils::resolve_alias;
+use serde_json::Map as JMap;
+use serde_json::Value as JValue;
+
+/// Converts an interface value of type `ty`, e.g. a lifted result of a call,
+/// into a JSON value.
+///
+/// The JSON layout is the same [`json_to_ivalue`](super::json_to_ivalue) accepts,
+/// byte arrays become base64 strings and 128-bit integers become decimal strings.
+pub fn ivalue_to_json<R: RecordResolvable>(
+    resolver: &R,
+    ty: &IType,
+    value: &IValue,
+) -> JsonResult<JValue> {
+    ToJson {
+        resolver,
+        path: Vec::new(),
+    }
+    .convert(ty, value)
+}
+
+struct ToJson<'r, R: RecordResolvable> {
+    resolver: &'r R,
+    path: Vec<PathSegment>,
+}
+
+impl<'r, R: RecordResolvable> ToJson<'r, R> {
+    fn convert(&mut self, ty: &IType, value: &IValue) -> JsonResult<JValue> {
+        let json = match (ty, value) {
+            (IType::Boolean, IValue::Boolean(value)) => JValue::from(*value),
+            (IType::S8, IValue::S8(value)) => JValue::from(*value),
+            (IType::S16, IValue::S16(value)) => JValue::from(*value),
+            (IType::S32, IValue::S32(value)) => JValue::from(*value),
+            (IType::S64, IValue::S64(value)) => JValue::from(*value),
+            (IType::U8, IValue::U8(value)) => JValue::from(*value),
+            (IType::U16, IValue::U16(value)) => JValue::from(*value),
+            (IType::U32, IValue::U32(value)) => JValue::from(*value),
+            (IType::U64, IValue::U64(value)) => JValue::from(*value),
+            (IType::I32, IValue::I32(value)) => JValue::from(*value),
+            (IType::I64, IValue::I64(value)) => JValue::from(*value),
+            (IType::U128, IValue::U128(value)) => JValue::from(value.to_string()),
+            (IType::S128, IValue::S128(value)) => JValue::from(value.to_string()),
+            (IType::F32, IValue::F32(value)) => self.float(*value as f64)?,
+            (IType::F64, IValue::F64(value)) => self.float(*value)?,
+            (IType::Char, IValue::Char(value)) => JValue::from(value.to_string()),
+            (IType::String, IValue::String(value)) => JValue::from(value.as_str()),
+            (IType::Handle(_), IValue::Handle(value)) => JValue::from(*value),
+            (IType::ByteArray, IValue::ByteArray(bytes)) => JValue::from(BASE64.encode(bytes)),
+            (IType::ByteArray, IValue::Array(values)) => {
+                JValue::Array(self.elements(&IType::U8, values)?)
+            }
+            (IType::Array(element_ty), IValue::ByteArray(bytes))
+                if self.resolve_alias(element_ty)? == &IType::U8 =>
+            {
+                JValue::Array(bytes.iter().map(|byte| JValue::from(*byte)).collect())
+            }
+            (IType::Array(element_ty), IValue::Array(values)) => {
+                JValue::Array(self.elements(element_ty, values)?)
+            }
+            (IType::Record(record_type_id), IValue::Record(fields)) => {
+                self.record(*record_type_id, fields)?
+            }
+            (IType::Optional(_), IValue::Optional(None)) => JValue::Null,
+            (IType::Optional(inner_ty), IValue::Optional(Some(value))) => {
+                self.convert(inner_ty, value)?
+            }
+            (IType::Variant(variant_type_id), IValue::Variant(discriminant, payload)) => {
+                self.variant(*variant_type_id, *discriminant, payload.as_deref())?
+            }
+            (IType::Result(ok_ty, _), IValue::Result(Ok(value))) => {
+                self.single_entry("ok", ok_ty, value)?
+            }
+            (IType::Result(_, err_ty), IValue::Result(Err(value))) => {
+                self.single_entry("err", err_ty, value)?
+            }
+            (IType::Map(key_ty, value_ty), IValue::Map(entries)) => {
+                self.map(key_ty, value_ty, entries)?
+            }
+            (IType::Flags(flags_type_id), IValue::Flags(bits)) => {
+                let flags_type = self.resolve(|r| r.resolve_flags(*flags_type_id))?;
+                let flags_count = flags_type.flags.len();
+
+                if flags_count < crate::IFlagsType::MAX_FLAGS_COUNT && bits >> flags_count != 0 {
+                    return Err(self.error(
+                        TypeCheckErrorKind::InvalidFlags {
+                            flags_name: flags_type.name.clone(),
+                            bits: *bits,
+                        }
+                        .into(),
+                    ));
+                }
+
+                let names = flags_type
+                    .flags
+                    .iter()
+                    .enumerate()
+                    .filter(|(flag, _)| {
+                        // flags beyond 64 don't fit in the bits, so they are never set
+                        1u64.checked_shl(*flag as u32)
+                            .map_or(false, |mask| bits & mask != 0)
+                    })
+                    .map(|(_, name)| JValue::from(name.as_str()))
+                    .collect();
+
+                JValue::Array(names)
+            }
+            (IType::Enum(enum_type_id), IValue::Enum(discriminant)) => {
+                let enum_type = self.resolve(|r| r.resolve_enum(*enum_type_id))?;
+
+                match enum_type.members.get(*discriminant as usize) {
+                    Some(member) => JValue::from(member.as_str()),
+                    None => {
+                        return Err(self.error(
+                            TypeCheckErrorKind::InvalidEnumDiscriminant {
+                                enum_name: enum_type.name.clone(),
+                                discriminant: *discriminant,
+                            }
+                            .into(),
+                        ))
+                    }
+                }
+            }
+            (IType::Alias(_), _) => {
+                let ty = self.resolve_alias(ty)?;
+                self.convert(ty, value)?
+            }
+            _ => {
+                return Err(self.error(
+                    TypeCheckErrorKind::TypeMismatch {
+                        expected_type: ty.clone(),
+                        received_value: value.clone(),
+                    }
+                    .into(),
+                ))
+            }
+        };
+
+        Ok(json)
+    }
+
+    fn record(&mut self, record_type_id: u64, fields: &[IValue]) -> JsonResult<JValue> {
+        let record_type = self.resolve(|r| r.resolve_record(record_type_id))?;
+
+        if fields.len() != record_type.fields.len() {
+            return Err(self.error(
+                TypeCheckErrorKind::FieldsCountMismatch {
+                    record_name: record_type.name.clone(),
+                    expected: record_type.fields.len(),
+                    actual: fields.len(),
+                }
+                .into(),
+            ));
+        }
+
+        let is_named = record_type.fields.iter().all(|field| field.name.is_some());
+        if !is_named {
+            let mut elements = Vec::with_capacity(fields.len());
+            for (position, (field_type, field)) in record_type.fields.iter().zip(fields).enumerate()
+            {
+                let name = match &field_type.name {
+                    Some(name) => name.clone(),
+                    None => position.to_string(),
+                };
+                elements.push(self.convert_inner(
+                    PathSegment::Field(name),
+                    &field_type.ty,
+                    field,
+                )?);
+            }
+
+            return Ok(JValue::Array(elements));
+        }
+
+        let mut object = JMap::new();
+        for (field_type, field) in record_type.fields.iter().zip(fields) {
+            let name = field_type.name.clone().unwrap_or_default();
+            let json =
+                self.convert_inner(PathSegment::Field(name.clone()), &field_type.ty, field)?;
+            object.insert(name, json);
+        }
+
+        Ok(JValue::Object(object))
+    }
+
+    fn variant(
+        &mut self,
+        variant_type_id: u64,
+        discriminant: u32,
+        payload: Option<&IValue>,
+    ) -> JsonResult<JValue> {
+        let variant_type = self.resolve(|r| r.resolve_variant(variant_type_id))?;
+
+        let case = match variant_type.cases.get(discriminant as usize) {
+            Some(case) => case,
+            None => {
+                return Err(self.error(
+                    TypeCheckErrorKind::InvalidVariantDiscriminant {
+                        variant_name: variant_type.name.clone(),
+                        discriminant,
+                    }
+                    .into(),
+                ))
+            }
+        };
+
+        match (&case.ty, payload) {
+            (None, None) => Ok(JValue::from(case.name.as_str())),
+            (Some(ty), Some(value)) => self.single_entry(&case.name, ty, value),
+            _ => Err(self.error(
+                TypeCheckErrorKind::VariantPayloadMismatch {
+                    variant_name: variant_type.name.clone(),
+                    case_name: case.name.clone(),
+                }
+                .into(),
+            )),
+        }
+    }
+
+    fn map(
+        &mut self,
+        key_ty: &IType,
+        value_ty: &IType,
+        entries: &[(IValue, IValue)],
+    ) -> JsonResult<JValue> {
+        if self.resolve_alias(key_ty)? == &IType::String {
+            let mut object = JMap::new();
+            for (position, (key, value)) in entries.iter().enumerate() {
+                let key = match key {
+                    IValue::String(key) => key.clone(),
+                    _ => {
+                        self.path.push(PathSegment::Index(position));
+                        self.path.push(PathSegment::Index(0));
+                        return Err(self.error(
+                            TypeCheckErrorKind::TypeMismatch {
+                                expected_type: IType::String,
+                                received_value: key.clone(),
+                            }
+                            .into(),
+                        ));
+                    }
+                };
+
+                let json = self.convert_inner(PathSegment::Field(key.clone()), value_ty, value)?;
+                object.insert(key, json);
+            }
+
+            return Ok(JValue::Object(object));
+        }
+
+        let mut pairs = Vec::with_capacity(entries.len());
+        for (position, (key, value)) in entries.iter().enumerate() {
+            self.path.push(PathSegment::Index(position));
+
+            let key = self.convert_inner(PathSegment::Index(0), key_ty, key)?;
+            let value = self.convert_inner(PathSegment::Index(1), value_ty, value)?;
+            pairs.push(JValue::Array(vec![key, value]));
+
+            self.path.pop();
+        }
+
+        Ok(JValue::Array(pairs))
+    }
+
+    fn elements(&mut self, ty: &IType, values: &[IValue]) -> JsonResult<Vec<JValue>> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(position, value)| self.convert_inner(PathSegment::Index(position), ty, value))
+            .collect()
+    }
+
+    /// Builds a JSON object with the only entry like `{"ok": value}`.
+    fn single_entry(&mut self, key: &str, ty: &IType, value: &IValue) -> JsonResult<JValue> {
+        let json = self.convert_inner(PathSegment::Field(key.to_string()), ty, value)?;
+
+        let mut object = JMap::new();
+        object.insert(key.to_string(), json);
+
+        Ok(JValue::Object(object))
+    }
+
+    fn convert_inner(
+        &mut self,
+        segment: PathSegment,
+        ty: &IType,
+        value: &IValue,
+    ) -> JsonResult<JValue> {
+        self.path.push(segment);
+        let json = self.convert(ty, value)?;
+        self.path.pop();
+
+        Ok(json)
+    }
+
+    fn float(&self, value: f64) -> JsonResult<JValue> {
+        serde_json::Number::from_f64(value)
+            .map(JValue::Number)
+            .ok_or_else(|| self.error(JsonErrorKind::NonFiniteFloat(value)))
+    }
+
+    fn resolve_alias<'t>(&self, ty: &'t IType) -> JsonResult<&'t IType>
+    where
+        'r: 't,
+    {
+        resolve_alias(self.resolver, ty).map_err(|e| self.error(e.into()))
+    }
+
+    fn resolve<T: ?Sized>(
+        &self,
+        resolve: impl FnOnce(&'r R) -> Result<&'r T, RecordResolvableError>,
+    ) -> JsonResult<&'r T> {
+        resolve(self.resolver).map_err(|e| self.error(e.into()))
+    }
+
+    fn error(&self, kind: JsonErrorKind) -> JsonError {
+        JsonError {
+            path: ValuePath(self.path.clone()),
+            kind,
+        }
+    }
+}
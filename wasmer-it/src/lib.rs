@@ -59,22 +59,44 @@ pub mod decoders;
 pub mod encoders;
 pub mod errors;
 pub mod interpreter;
+pub mod json;
 #[cfg(feature = "serde")]
 mod serde;
+mod typed_value;
 mod values;
 
 // re-exports
 pub use fluence_it_types::ne_vec;
 pub use fluence_it_types::ne_vec::NEVec;
+pub use fluence_it_types::CanonicalIValue;
+pub use fluence_it_types::IEnumType;
+pub use fluence_it_types::IFlagsType;
+pub use fluence_it_types::IRecord;
 pub use fluence_it_types::IRecordFieldType;
 pub use fluence_it_types::IRecordType;
 pub use fluence_it_types::IType;
 pub use fluence_it_types::IValue;
+pub use fluence_it_types::IVariantCaseType;
+pub use fluence_it_types::IVariantType;
+pub use fluence_it_types::InferTypeError;
+pub use fluence_it_types::InterfaceType;
+pub use fluence_it_types::RecordRegistry;
 
 pub use it_to_bytes::ToBytes;
 
+pub use crate::typed_value::TypedIValue;
+
 #[cfg(feature = "serde")]
 pub use crate::serde::de::from_interface_values;
 
+#[cfg(feature = "serde")]
+pub use crate::serde::de::DeserializeError;
+
 #[cfg(feature = "serde")]
 pub use crate::serde::ser::to_interface_value;
+
+#[cfg(feature = "serde")]
+pub use crate::serde::ser::SerializeError;
+
+#[cfg(feature = "serde")]
+pub use crate::serde::typed_ser::to_typed_interface_value;
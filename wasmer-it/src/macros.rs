@@ -125,28 +125,31 @@ macro_rules! test_executable_instruction {
         fn $test_name() {
             use crate::{
                 interpreter::{
-                    instructions::tests::{Export, Instance, LocalImport, Memory, MemoryView},
+                    instructions::tests::{
+                        Export, Instance, LocalImport, Memory, MemoryView, Store,
+                    },
                     stack::Stackable,
                     Instruction, Interpreter,
                 },
-                types::IType,
-                values::IValue,
+                IType, IValue,
             };
-            use std::{cell::Cell, collections::HashMap, convert::TryInto};
+            use std::{collections::HashMap, convert::TryInto};
 
-            let interpreter: Interpreter<Instance, Export, LocalImport, Memory, MemoryView> =
-                (&vec![$($instructions),*]).try_into().unwrap();
+            let interpreter: Interpreter<Instance, Export, LocalImport, Memory, MemoryView, Store> =
+                vec![$($instructions),*].try_into().unwrap();
 
             let invocation_inputs = vec![$($invocation_inputs),*];
             let mut instance = $instance;
-            let run = interpreter.run(&invocation_inputs, &mut instance);
+            let run = futures::executor::block_on(
+                interpreter.run(&invocation_inputs, &mut instance, &mut ()),
+            );
 
             let err = match &run {
                 Ok(_) => "".to_string(),
                 Err(e) => e.to_string(),
             };
 
-            assert!(run.is_ok(), err);
+            assert!(run.is_ok(), "{}", err);
 
             let stack = run.unwrap();
 
@@ -167,21 +170,24 @@ macro_rules! test_executable_instruction {
         fn $test_name() {
             use crate::{
                 interpreter::{
-                    instructions::tests::{Export, Instance, LocalImport, Memory, MemoryView},
+                    instructions::tests::{
+                        Export, Instance, LocalImport, Memory, MemoryView, Store,
+                    },
                     stack::Stackable,
                     Instruction, Interpreter,
                 },
-                types::IType,
-                values::IValue,
+                IType, IValue,
             };
-            use std::{cell::Cell, collections::HashMap, convert::TryInto};
+            use std::{collections::HashMap, convert::TryInto};
 
-            let interpreter: Interpreter<Instance, Export, LocalImport, Memory, MemoryView> =
-                (&vec![$($instructions),*]).try_into().unwrap();
+            let interpreter: Interpreter<Instance, Export, LocalImport, Memory, MemoryView, Store> =
+                vec![$($instructions),*].try_into().unwrap();
 
             let invocation_inputs = vec![$($invocation_inputs),*];
             let mut instance = $instance;
-            let run = interpreter.run(&invocation_inputs, &mut instance);
+            let run = futures::executor::block_on(
+                interpreter.run(&invocation_inputs, &mut instance, &mut ()),
+            );
 
             assert!(run.is_err());
 
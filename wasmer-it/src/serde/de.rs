@@ -1,7 +1,11 @@
 //! Provides a deserializer from WIT values to Rust value.
 
 use crate::{values::FlattenIRecordIterator, IType, IValue};
-use serde::{de, Deserialize};
+use it_lilo::type_checker::{PathSegment, ValuePath};
+use serde::{
+    de::{self, value::SeqDeserializer, IntoDeserializer},
+    Deserialize,
+};
 use std::{
     fmt::{self, Display},
     iter::Peekable,
@@ -17,10 +21,7 @@ use std::{
 /// # Example
 ///
 /// ```rust
-/// use wasmer_interface_types::{
-///     values::{IValue, from_interface_values},
-///     vec1::Vec1,
-/// };
+/// use wasmer_interface_types_fl::{from_interface_values, IValue, NEVec};
 /// use serde::Deserialize;
 ///
 /// #[derive(Deserialize, Debug, PartialEq)]
@@ -74,6 +75,21 @@ impl<'de> Deserializer<'de> {
             iterator: FlattenIRecordIterator::new(input).peekable(),
         }
     }
+
+    /// Deserializes a value nested into another one (an optional or a
+    /// variant payload), the nested value must be entirely consumed.
+    fn nested<T, F>(value: &'de IValue, deserialize: F) -> Result<T, DeserializeError>
+    where
+        F: FnOnce(&mut Deserializer<'de>) -> Result<T, DeserializeError>,
+    {
+        let mut deserializer = Deserializer::new(std::slice::from_ref(value));
+        let result = deserialize(&mut deserializer)?;
+
+        match deserializer.iterator.peek() {
+            None => Ok(result),
+            _ => Err(DeserializeError::InputNotEmpty),
+        }
+    }
 }
 
 macro_rules! next {
@@ -86,10 +102,10 @@ macro_rules! next {
                     Ok(*value)
                 }
 
-                Some(wrong_value) => Err(DeserializeError::TypeMismatch {
-                    expected_type: IType::$variant,
-                    received_value: (*wrong_value).clone(),
-                }),
+                Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                    IType::$variant,
+                    (*wrong_value).clone(),
+                )),
 
                 None => Err(DeserializeError::InputEmpty),
             }
@@ -108,6 +124,45 @@ impl<'de> Deserializer<'de> {
     next!(next_u64, U64, u64);
     next!(next_f32, F32, f32);
     next!(next_f64, F64, f64);
+    next!(next_u128, U128, u128);
+    next!(next_s128, S128, i128);
+    next!(next_char, Char, char);
+
+    fn next_flags(&mut self) -> Result<u64, DeserializeError> {
+        match self.iterator.peek() {
+            Some(IValue::Flags(bits)) => {
+                self.iterator.next();
+
+                Ok(*bits)
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                // TODO: change default
+                IType::Flags(0),
+                (*wrong_value).clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
+    }
+
+    fn next_handle(&mut self) -> Result<u32, DeserializeError> {
+        match self.iterator.peek() {
+            Some(IValue::Handle(handle)) => {
+                self.iterator.next();
+
+                Ok(*handle)
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                // TODO: change default
+                IType::Handle(0),
+                (*wrong_value).clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
+    }
 
     fn next_string(&mut self) -> Result<&'de str, DeserializeError> {
         match self.iterator.peek() {
@@ -117,30 +172,45 @@ impl<'de> Deserializer<'de> {
                 Ok(v)
             }
 
-            Some(wrong_value) => Err(DeserializeError::TypeMismatch {
-                expected_type: IType::String,
-                received_value: (*wrong_value).clone(),
-            }),
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                IType::String,
+                (*wrong_value).clone(),
+            )),
 
             None => Err(DeserializeError::InputEmpty),
         }
     }
 
-    fn next_array(&mut self) -> Result<&'de [u8], DeserializeError> {
-        match self.iterator.peek() {
-            Some(IValue::Array(_)) => {
+    next!(next_bool, Boolean, bool);
+
+    /// Returns bytes of a byte array or of an array of `U8`.
+    fn next_bytes(&mut self) -> Result<Bytes<'de>, DeserializeError> {
+        match self.iterator.peek().copied() {
+            Some(IValue::ByteArray(bytes)) => {
                 self.iterator.next();
 
-                // Ok(v)
+                Ok(Bytes::Borrowed(bytes))
+            }
+
+            Some(IValue::Array(values)) => {
+                let bytes = values
+                    .iter()
+                    .enumerate()
+                    .map(|(position, value)| match value {
+                        IValue::U8(byte) => Ok(*byte),
+                        _ => Err(DeserializeError::type_mismatch(IType::U8, value.clone())
+                            .at(PathSegment::Index(position))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.iterator.next();
 
-                unimplemented!()
+                Ok(Bytes::Owned(bytes))
             }
 
-            Some(wrong_value) => Err(DeserializeError::TypeMismatch {
-                // TODO: change default
-                expected_type: IType::Array(Box::new(IType::S8)),
-                received_value: (*wrong_value).clone(),
-            }),
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                IType::ByteArray,
+                wrong_value.clone(),
+            )),
 
             None => Err(DeserializeError::InputEmpty),
         }
@@ -150,6 +220,12 @@ impl<'de> Deserializer<'de> {
     next!(next_i64, I64, i64);
 }
 
+/// Bytes of a byte array, an array of `U8` has to be copied.
+enum Bytes<'de> {
+    Borrowed(&'de [u8]),
+    Owned(Vec<u8>),
+}
+
 /// Represents an error while deserializing.
 #[derive(Clone, Debug, PartialEq)]
 pub enum DeserializeError {
@@ -161,6 +237,9 @@ pub enum DeserializeError {
 
     /// The current value hasn't the expected type.
     TypeMismatch {
+        /// The path from the deserialized value to the current one, e.g. `user.addresses[2].zip`.
+        path: ValuePath,
+
         /// The expected type.
         expected_type: IType,
 
@@ -172,6 +251,26 @@ pub enum DeserializeError {
     Message(String),
 }
 
+impl DeserializeError {
+    fn type_mismatch(expected_type: IType, received_value: IValue) -> Self {
+        Self::TypeMismatch {
+            path: ValuePath::default(),
+            expected_type,
+            received_value,
+        }
+    }
+
+    /// Prepends a path segment to the path of a type mismatch,
+    /// the path is built up while the error bubbles up.
+    fn at(mut self, segment: PathSegment) -> Self {
+        if let Self::TypeMismatch { path, .. } = &mut self {
+            path.0.insert(0, segment);
+        }
+
+        self
+    }
+}
+
 impl de::Error for DeserializeError {
     fn custom<T: Display>(msg: T) -> Self {
         Self::Message(msg.to_string())
@@ -185,13 +284,23 @@ impl Display for DeserializeError {
             Self::Message(ref msg) => write!(formatter, "{}", msg),
             Self::InputEmpty => write!(formatter, "Unexpected end of input"),
             Self::TypeMismatch {
+                ref path,
                 ref expected_type,
                 ref received_value,
-            } => write!(
+            } if path.is_empty() => write!(
                 formatter,
                 "Type mismatch detected: `{:?}` can't be converted to `{:?}`",
                 received_value, expected_type,
             ),
+            Self::TypeMismatch {
+                ref path,
+                ref expected_type,
+                ref received_value,
+            } => write!(
+                formatter,
+                "Type mismatch detected at `{}`: `{:?}` can't be converted to `{:?}`",
+                path, received_value, expected_type,
+            ),
         }
     }
 }
@@ -219,10 +328,23 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
             Some(IValue::F64(_)) => self.deserialize_f64(visitor),
             Some(IValue::String(_)) => self.deserialize_string(visitor),
             Some(IValue::ByteArray(_)) => self.deserialize_bytes(visitor),
-            Some(IValue::Array(_)) => self.deserialize_bytes(visitor),
+            Some(IValue::Array(_)) => self.deserialize_seq(visitor),
             Some(IValue::I32(_)) => self.deserialize_i32(visitor),
             Some(IValue::I64(_)) => self.deserialize_i64(visitor),
-            Some(IValue::Record(..)) => unreachable!("Records should have been flattened."), // already flattened
+            // records are flattened, so their fields are deserialized one by one
+            Some(IValue::Record(..)) => Err(DeserializeError::Message(
+                "a record can't be deserialized as a self-describing value".to_string(),
+            )),
+            Some(IValue::Optional(_)) => self.deserialize_option(visitor),
+            Some(IValue::Variant(..)) => self.deserialize_enum("", &[], visitor),
+            Some(IValue::Result(_)) => self.deserialize_enum("Result", &["Ok", "Err"], visitor),
+            Some(IValue::Map(_)) => self.deserialize_map(visitor),
+            Some(IValue::Flags(_)) => self.deserialize_u64(visitor),
+            Some(IValue::Enum(_)) => self.deserialize_enum("", &[], visitor),
+            Some(IValue::Char(_)) => self.deserialize_char(visitor),
+            Some(IValue::U128(_)) => self.deserialize_u128(visitor),
+            Some(IValue::S128(_)) => self.deserialize_i128(visitor),
+            Some(IValue::Handle(_)) => self.deserialize_u32(visitor),
             None => Err(DeserializeError::InputEmpty),
         }
     }
@@ -231,7 +353,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_bool(self.next_u8()? != 0)
+        // `u8` is accepted for backward compatibility.
+        visitor.visit_bool(
+            self.next_bool()
+                .or_else(|error| self.next_u8().map(|value| value != 0).map_err(|_| error))?,
+        )
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -284,14 +410,16 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u32(self.next_u32()?)
+        // Handles are indices into the host-side handle table.
+        visitor.visit_u32(self.next_u32().or_else(|_| self.next_handle())?)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u64(self.next_u64()?)
+        // Flags are packed into `u64`.
+        visitor.visit_u64(self.next_u64().or_else(|_| self.next_flags())?)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -308,11 +436,25 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_f64(self.next_f64()?)
     }
 
-    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i128(self.next_s128()?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u128(self.next_u128()?)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`char` is not supported by WIT for the moment.")
+        visitor.visit_char(self.next_char()?)
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -333,39 +475,73 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_bytes(self.next_array()?)
+        match self.next_bytes()? {
+            Bytes::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Bytes::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`bytes` buffer is not supported by WIT for the moment.")
+        self.deserialize_bytes(visitor)
     }
 
-    fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`option` is not supported by WIT for the moment.")
+        match self.iterator.peek().copied() {
+            Some(IValue::Optional(None)) => {
+                self.iterator.next();
+
+                visitor.visit_none()
+            }
+
+            Some(IValue::Optional(Some(value))) => {
+                self.iterator.next();
+
+                Deserializer::nested(value, |deserializer| visitor.visit_some(deserializer))
+            }
+
+            // A plain value is considered as a present optional one.
+            Some(_) => visitor.visit_some(self),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 
-    fn deserialize_unit<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    // A unit is serialized as an absent optional value.
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`unit` is not supported by WIT for the moment.")
+        match self.iterator.peek().copied() {
+            Some(IValue::Optional(None)) => {
+                self.iterator.next();
+
+                visitor.visit_unit()
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                IType::Optional(Box::new(IType::Boolean)),
+                wrong_value.clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 
     fn deserialize_unit_struct<V>(
         self,
         _name: &'static str,
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`unit_struct` is not supported by WIT for the moment.")
+        self.deserialize_unit(visitor)
     }
 
     fn deserialize_newtype_struct<V>(
@@ -379,18 +555,38 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_newtype_struct(self)
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`seq` is not supported by WIT for the moment.")
+        match self.iterator.peek().copied() {
+            Some(IValue::Array(values)) => {
+                self.iterator.next();
+
+                visitor.visit_seq(Elements::new(values))
+            }
+
+            Some(IValue::ByteArray(bytes)) => {
+                self.iterator.next();
+
+                visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied()))
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                // TODO: change default
+                IType::Array(Box::new(IType::S8)),
+                wrong_value.clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`tuple` is not supported by WIT for the moment.")
+        visitor.visit_seq(Sequence::new(self, None))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -402,65 +598,125 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(Sequence::new(&mut self))
+        visitor.visit_seq(Sequence::new(&mut self, None))
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`map` is not supported by WIT for the moment.")
+        match self.iterator.peek().copied() {
+            Some(IValue::Map(entries)) => {
+                self.iterator.next();
+
+                visitor.visit_map(Map::new(entries))
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                // TODO: change default
+                IType::Map(Box::new(IType::String), Box::new(IType::String)),
+                wrong_value.clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 
     fn deserialize_struct<V>(
         mut self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_seq(Sequence::new(&mut self))
+        visitor.visit_seq(Sequence::new(&mut self, Some(fields)))
     }
 
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
+        variants: &'static [&'static str],
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`enum` is not supported by WIT for the moment.")
+        match self.iterator.peek().copied() {
+            Some(IValue::Variant(discriminant, payload)) => {
+                self.iterator.next();
+
+                let case_name = match variants.get(*discriminant as usize) {
+                    Some(name) => name.to_string(),
+                    None => discriminant.to_string(),
+                };
+
+                visitor.visit_enum(Enum::new(*discriminant, payload.as_deref(), case_name))
+            }
+
+            Some(IValue::Enum(discriminant)) => {
+                self.iterator.next();
+
+                visitor.visit_enum(Enum::new(*discriminant, None, String::new()))
+            }
+
+            // `Result` is deserialized as an enum, `Ok` is its first variant and `Err` is the second one.
+            Some(IValue::Result(result)) => {
+                self.iterator.next();
+
+                match result {
+                    Ok(value) => visitor.visit_enum(Enum::new(0, Some(value), "ok".to_string())),
+                    Err(value) => visitor.visit_enum(Enum::new(1, Some(value), "err".to_string())),
+                }
+            }
+
+            Some(wrong_value) => Err(DeserializeError::type_mismatch(
+                // TODO: change default
+                IType::Variant(0),
+                wrong_value.clone(),
+            )),
+
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 
-    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`identifier` is not supported by WIT for the moment.");
+        self.deserialize_str(visitor)
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        todo!("`ignored_any` is not implemented for the moment.")
+        match self.iterator.next() {
+            Some(_) => visitor.visit_unit(),
+            None => Err(DeserializeError::InputEmpty),
+        }
     }
 }
 
+/// Fields of a flattened record, they are named with `fields` in errors,
+/// or by their position if the record is a tuple.
 struct Sequence<'a, 'de>
 where
     'de: 'a,
 {
     de: &'a mut Deserializer<'de>,
+    fields: Option<&'static [&'static str]>,
+    position: usize,
 }
 
 impl<'a, 'de> Sequence<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>) -> Self {
-        Sequence { de }
+    fn new(de: &'a mut Deserializer<'de>, fields: Option<&'static [&'static str]>) -> Self {
+        Sequence {
+            de,
+            fields,
+            position: 0,
+        }
     }
 }
 
@@ -475,7 +731,194 @@ impl<'de, 'a> de::SeqAccess<'de> for Sequence<'a, 'de> {
             return Ok(None);
         }
 
-        seed.deserialize(&mut *self.de).map(Some)
+        let name = match self.fields.and_then(|fields| fields.get(self.position)) {
+            Some(name) => name.to_string(),
+            None => self.position.to_string(),
+        };
+        self.position += 1;
+
+        seed.deserialize(&mut *self.de)
+            .map(Some)
+            .map_err(|e| e.at(PathSegment::Field(name)))
+    }
+}
+
+/// Elements of an array, each of them is deserialized separately.
+struct Elements<'de> {
+    values: std::slice::Iter<'de, IValue>,
+    position: usize,
+}
+
+impl<'de> Elements<'de> {
+    fn new(values: &'de [IValue]) -> Self {
+        Elements {
+            values: values.iter(),
+            position: 0,
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'de> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        let value = match self.values.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let position = self.position;
+        self.position += 1;
+
+        Deserializer::nested(value, |deserializer| seed.deserialize(deserializer))
+            .map(Some)
+            .map_err(|e| e.at(PathSegment::Index(position)))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Key-value pairs of a map value.
+struct Map<'de> {
+    entries: std::slice::Iter<'de, (IValue, IValue)>,
+    value: Option<&'de IValue>,
+    position: usize,
+}
+
+impl<'de> Map<'de> {
+    fn new(entries: &'de [(IValue, IValue)]) -> Self {
+        Map {
+            entries: entries.iter(),
+            value: None,
+            position: 0,
+        }
+    }
+}
+
+impl<'de> Map<'de> {
+    /// Prepends the path of the current entry, like `[1].key`.
+    fn entry_error(&self, error: DeserializeError, part: &str) -> DeserializeError {
+        error
+            .at(PathSegment::Field(part.to_string()))
+            .at(PathSegment::Index(self.position - 1))
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Map<'de> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                self.position += 1;
+
+                Deserializer::nested(key, |deserializer| seed.deserialize(deserializer))
+                    .map(Some)
+                    .map_err(|e| self.entry_error(e, "key"))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let value = self.value.take().ok_or(DeserializeError::InputEmpty)?;
+
+        Deserializer::nested(value, |deserializer| seed.deserialize(deserializer))
+            .map_err(|e| self.entry_error(e, "value"))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// A variant value, i.e. a discriminant and an optional payload.
+struct Enum<'de> {
+    discriminant: u32,
+    payload: Option<&'de IValue>,
+    case_name: String,
+}
+
+impl<'de> Enum<'de> {
+    fn new(discriminant: u32, payload: Option<&'de IValue>, case_name: String) -> Self {
+        Enum {
+            discriminant,
+            payload,
+            case_name,
+        }
+    }
+
+    /// Deserializes the payload, errors are prefixed with the case name.
+    fn payload<T, F>(self, deserialize: F) -> Result<T, DeserializeError>
+    where
+        F: FnOnce(&mut Deserializer<'de>) -> Result<T, DeserializeError>,
+    {
+        let payload = self.payload.ok_or(DeserializeError::InputEmpty)?;
+
+        Deserializer::nested(payload, deserialize)
+            .map_err(|e| e.at(PathSegment::Field(self.case_name)))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for Enum<'de> {
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.discriminant.into_deserializer())?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Enum<'de> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.payload {
+            None => Ok(()),
+            Some(_) => Err(DeserializeError::InputNotEmpty),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        self.payload(|deserializer| seed.deserialize(deserializer))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.payload(|deserializer| visitor.visit_seq(Sequence::new(deserializer, None)))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.payload(|deserializer| visitor.visit_seq(Sequence::new(deserializer, Some(fields))))
     }
 }
 
@@ -526,6 +969,9 @@ mod tests {
 
     deserialize_value!(test_deserialize_value__i32, I32, i32, 42);
     deserialize_value!(test_deserialize_value__i64, I64, i64, 42);
+    deserialize_value!(test_deserialize_value__u128, U128, u128, u128::MAX);
+    deserialize_value!(test_deserialize_value__s128, S128, i128, i128::MIN);
+    deserialize_value!(test_deserialize_value__char, Char, char, 'ы');
 
     #[test]
     #[allow(non_snake_case)]
@@ -533,7 +979,7 @@ mod tests {
         #[derive(Deserialize, Debug, PartialEq)]
         struct S(i8);
 
-        let input = vec![IValue::Record(vec1![IValue::S8(42)])];
+        let input = vec![IValue::Record(ne_vec![IValue::S8(42)])];
         let output = S(42);
 
         assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
@@ -545,7 +991,7 @@ mod tests {
         #[derive(Deserialize, Debug, PartialEq)]
         struct S(i8, f32);
 
-        let input = vec![IValue::Record(vec1![IValue::S8(7), IValue::F32(42.),])];
+        let input = vec![IValue::Record(ne_vec![IValue::S8(7), IValue::F32(42.),])];
         let output = S(7, 42.);
 
         assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
@@ -560,7 +1006,7 @@ mod tests {
             y: f32,
         }
 
-        let input = vec![IValue::Record(vec1![IValue::S8(7), IValue::F32(42.),])];
+        let input = vec![IValue::Record(ne_vec![IValue::S8(7), IValue::F32(42.),])];
         let output = S { x: 7, y: 42. };
 
         assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
@@ -582,9 +1028,9 @@ mod tests {
             p2: Point,
         }
 
-        let input = vec![IValue::Record(vec1![
-            IValue::Record(vec1![IValue::I32(1), IValue::I32(2), IValue::I32(3),]),
-            IValue::Record(vec1![IValue::I32(4), IValue::I32(5), IValue::I32(6),]),
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Record(ne_vec![IValue::I32(1), IValue::I32(2), IValue::I32(3),]),
+            IValue::Record(ne_vec![IValue::I32(4), IValue::I32(5), IValue::I32(6),]),
         ])];
         let output = Line {
             p1: Point { x: 1, y: 2, z: 3 },
@@ -593,4 +1039,371 @@ mod tests {
 
         assert_eq!(from_interface_values::<Line>(&input).unwrap(), output);
     }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__option() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            p: Option<Point>,
+            q: Option<i8>,
+            z: f32,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Optional(Some(Box::new(IValue::Record(ne_vec![
+                IValue::I32(1),
+                IValue::I32(2),
+            ])))),
+            IValue::Optional(None),
+            IValue::F32(42.),
+        ])];
+        let output = S {
+            p: Some(Point { x: 1, y: 2 }),
+            q: None,
+            z: 42.,
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__enum() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum E {
+            A,
+            B(i8),
+            C(i8, f32),
+            D { x: i32, y: String },
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            a: E,
+            b: E,
+            c: E,
+            d: E,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Variant(0, None),
+            IValue::Variant(1, Some(Box::new(IValue::S8(7)))),
+            IValue::Variant(
+                2,
+                Some(Box::new(IValue::Record(ne_vec![
+                    IValue::S8(7),
+                    IValue::F32(42.)
+                ]))),
+            ),
+            IValue::Variant(
+                3,
+                Some(Box::new(IValue::Record(ne_vec![
+                    IValue::I32(1),
+                    IValue::String("foo".to_string())
+                ]))),
+            ),
+        ])];
+        let output = S {
+            a: E::A,
+            b: E::B(7),
+            c: E::C(7, 42.),
+            d: E::D {
+                x: 1,
+                y: "foo".to_string(),
+            },
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__enum_unexpected_payload() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum E {
+            A,
+        }
+
+        let input = vec![IValue::Variant(0, Some(Box::new(IValue::S8(7))))];
+
+        assert_eq!(
+            from_interface_values::<E>(&input),
+            Err(DeserializeError::InputNotEmpty)
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__result() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            x: Result<i32, String>,
+            y: Result<i32, String>,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Result(Ok(Box::new(IValue::S32(7)))),
+            IValue::Result(Err(Box::new(IValue::String("foo".to_string())))),
+        ])];
+        let output = S {
+            x: Ok(7),
+            y: Err("foo".to_string()),
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__tuple() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            x: (i8, f32),
+            y: i32,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Record(ne_vec![IValue::S8(7), IValue::F32(42.)]),
+            IValue::I32(1),
+        ])];
+        let output = S { x: (7, 42.), y: 1 };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__map() {
+        use std::collections::HashMap;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            x: HashMap<String, u32>,
+            y: i32,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Map(vec![
+                (IValue::String("a".to_string()), IValue::U32(1)),
+                (IValue::String("b".to_string()), IValue::U32(2)),
+            ]),
+            IValue::I32(7),
+        ])];
+
+        let mut x = HashMap::new();
+        x.insert("a".to_string(), 1);
+        x.insert("b".to_string(), 2);
+        let output = S { x, y: 7 };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__flags_and_enum() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum Status {
+            Ok,
+            Failed,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            permissions: u64,
+            status: Status,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Flags(0b101),
+            IValue::Enum(1),
+        ])];
+        let output = S {
+            permissions: 0b101,
+            status: Status::Failed,
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__handle() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            connection: u32,
+        }
+
+        let input = vec![IValue::Record(ne_vec![IValue::Handle(7)])];
+        let output = S { connection: 7 };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__seq() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Polygon {
+            name: String,
+            points: Vec<Point>,
+            tags: Vec<Option<String>>,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::String("triangle".to_string()),
+            IValue::Array(vec![
+                IValue::Record(ne_vec![IValue::I32(0), IValue::I32(0)]),
+                IValue::Record(ne_vec![IValue::I32(1), IValue::I32(0)]),
+                IValue::Record(ne_vec![IValue::I32(0), IValue::I32(1)]),
+            ]),
+            IValue::Array(vec![
+                IValue::Optional(None),
+                IValue::Optional(Some(Box::new(IValue::String("red".to_string())))),
+            ]),
+        ])];
+        let output = Polygon {
+            name: "triangle".to_string(),
+            points: vec![
+                Point { x: 0, y: 0 },
+                Point { x: 1, y: 0 },
+                Point { x: 0, y: 1 },
+            ],
+            tags: vec![None, Some("red".to_string())],
+        };
+
+        assert_eq!(from_interface_values::<Polygon>(&input).unwrap(), output);
+        assert_eq!(
+            from_interface_values::<Vec<Vec<u16>>>(&[IValue::Array(vec![
+                IValue::Array(vec![IValue::U16(1), IValue::U16(2)]),
+                IValue::Array(vec![]),
+            ])])
+            .unwrap(),
+            vec![vec![1, 2], vec![]]
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__bytes() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S<'a> {
+            borrowed: &'a [u8],
+            bytes: Vec<u8>,
+            seq: Vec<u8>,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::ByteArray(vec![1, 2]),
+            IValue::ByteArray(vec![3]),
+            IValue::Array(vec![IValue::U8(4), IValue::U8(5)]),
+        ])];
+        let output = S {
+            borrowed: &[1, 2],
+            bytes: vec![3],
+            seq: vec![4, 5],
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+        assert_eq!(
+            from_interface_values::<Vec<u8>>(&[IValue::ByteArray(vec![6, 7])]).unwrap(),
+            vec![6, 7]
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__bool_and_unit() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Unit;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct S {
+            flag: bool,
+            legacy_flag: bool,
+            nothing: (),
+            unit: Unit,
+        }
+
+        let input = vec![IValue::Record(ne_vec![
+            IValue::Boolean(true),
+            IValue::U8(1),
+            IValue::Optional(None),
+            IValue::Optional(None),
+        ])];
+        let output = S {
+            flag: true,
+            legacy_flag: true,
+            nothing: (),
+            unit: Unit,
+        };
+
+        assert_eq!(from_interface_values::<S>(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__ignored_any() {
+        assert!(from_interface_values::<serde::de::IgnoredAny>(&[IValue::Array(vec![])]).is_ok());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_deserialize_value__type_mismatch_path() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Polygon {
+            points: Vec<Point>,
+        }
+
+        let input = vec![IValue::Record(ne_vec![IValue::Array(vec![
+            IValue::Record(ne_vec![IValue::I32(0), IValue::I32(0)]),
+            IValue::Record(ne_vec![IValue::S8(1), IValue::I32(0)]),
+        ])])];
+
+        let error = from_interface_values::<Polygon>(&input).unwrap_err();
+        match &error {
+            DeserializeError::TypeMismatch {
+                path,
+                expected_type,
+                received_value,
+            } => {
+                assert_eq!(path.to_string(), "points[1].x");
+                assert_eq!(expected_type, &IType::I32);
+                assert_eq!(received_value, &IValue::S8(1));
+            }
+            _ => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Type mismatch detected at `points[1].x`: `S8(1)` can't be converted to `I32`"
+        );
+
+        let input = vec![IValue::Map(vec![(
+            IValue::String("a".to_string()),
+            IValue::Boolean(true),
+        )])];
+        let error =
+            from_interface_values::<std::collections::HashMap<String, u8>>(&input).unwrap_err();
+        assert!(
+            matches!(&error, DeserializeError::TypeMismatch { path, .. } if path.to_string() == "[0].value")
+        );
+    }
 }
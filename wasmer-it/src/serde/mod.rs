@@ -4,3 +4,4 @@
 
 pub(crate) mod de;
 pub(crate) mod ser;
+pub(crate) mod typed_ser;
@@ -1,7 +1,9 @@
 //! Provides a serializer from Rust value to WIT values.
 
+use crate::IType;
 use crate::IValue;
 use crate::NEVec;
+use it_lilo::type_checker::{PathSegment, ValuePath};
 use serde::{ser, Serialize};
 use std::fmt::{self, Display};
 
@@ -14,10 +16,7 @@ use std::fmt::{self, Display};
 /// # Example
 ///
 /// ```rust
-/// use wasmer_interface_types::{
-///     values::{IValue, to_interface_value},
-///     vec1::Vec1,
-/// };
+/// use wasmer_interface_types_fl::{to_interface_value, IValue, NEVec};
 /// use serde::Serialize;
 ///
 /// #[derive(Serialize)]
@@ -70,17 +69,19 @@ where
 /// The serializer.
 struct Serializer {
     values: Vec<Vec<IValue>>,
+    variant_indices: Vec<u32>,
 }
 
 impl Serializer {
     fn new() -> Self {
         Self {
             values: vec![vec![]],
+            variant_indices: vec![],
         }
     }
 
     fn last(&mut self) -> &mut Vec<IValue> {
-        self.values.last_mut().unwrap()
+        self.values.last_mut().unwrap() // this `unwrap` is safe because `pop` never removes the first `vec`
     }
 
     fn push_with_capacity(&mut self, capacity: usize) {
@@ -96,6 +97,38 @@ impl Serializer {
             Ok(self.values.pop().unwrap()) // this `unwrap` is safe before `self.values` contains at least 2 items
         }
     }
+
+    /// Serializes a value aside from the current values and returns it.
+    fn serialize_single<T>(&mut self, value: &T) -> Result<IValue, SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push_with_capacity(1);
+        value.serialize(&mut *self)?;
+
+        let mut values = self.pop()?;
+
+        if values.len() != 1 {
+            return Err(SerializeError::InternalValuesCorrupted);
+        }
+
+        Ok(values.pop().unwrap()) // this `unwrap` is safe because we are sure the length is 1.
+    }
+
+    /// Finishes a tuple or a struct variant, its fields are gathered into a record payload.
+    fn end_variant(&mut self) -> Result<(), SerializeError> {
+        let fields =
+            NEVec::new(self.pop()?).map_err(|_| SerializeError::RecordNeedsAtLeastOneField)?;
+        let variant_index = self
+            .variant_indices
+            .pop()
+            .ok_or(SerializeError::InternalValuesCorrupted)?;
+
+        let variant = IValue::Variant(variant_index, Some(Box::new(IValue::Record(fields))));
+        self.last().push(variant);
+
+        Ok(())
+    }
 }
 
 /// Represents an error while serializing.
@@ -111,10 +144,118 @@ pub enum SerializeError {
     /// A record must contain at least one field.
     RecordNeedsAtLeastOneField,
 
+    /// The value can't be converted to the expected type.
+    TypeMismatch {
+        /// The path from the serialized value to the current one, e.g. `user.addresses[2].zip`.
+        path: ValuePath,
+
+        /// The expected type.
+        expected_type: IType,
+
+        /// The kind of the serialized value, like `i64` or `struct`.
+        received: &'static str,
+    },
+
+    /// The number doesn't fit into the expected type.
+    OutOfRange {
+        /// The path from the serialized value to the current one.
+        path: ValuePath,
+
+        /// The expected type.
+        expected_type: IType,
+
+        /// The serialized number.
+        value: String,
+    },
+
+    /// The struct has a field the record type doesn't have.
+    UnknownField {
+        /// The path from the serialized value to the struct.
+        path: ValuePath,
+
+        /// The name of the record type.
+        record_name: String,
+
+        /// The name of the unknown field.
+        field_name: String,
+    },
+
+    /// The struct lacks a field of the record type that isn't optional.
+    MissingField {
+        /// The path from the serialized value to the struct.
+        path: ValuePath,
+
+        /// The name of the record type.
+        record_name: String,
+
+        /// The name of the missing field.
+        field_name: String,
+    },
+
+    /// The enum has a variant that is neither a case of the variant type,
+    /// nor a member of the enum type.
+    UnknownCase {
+        /// The path from the serialized value to the enum.
+        path: ValuePath,
+
+        /// The name of the variant or the enum type.
+        type_name: String,
+
+        /// The name of the unknown case.
+        case_name: String,
+    },
+
+    /// A type of the schema can't be resolved.
+    TypeNotResolved {
+        /// The path from the serialized value to the current one.
+        path: ValuePath,
+
+        /// Why the type can't be resolved.
+        reason: String,
+    },
+
     /// Arbitrary message.
     Message(String),
 }
 
+impl SerializeError {
+    pub(crate) fn type_mismatch(expected_type: &IType, received: &'static str) -> Self {
+        Self::TypeMismatch {
+            path: ValuePath::default(),
+            expected_type: expected_type.clone(),
+            received,
+        }
+    }
+
+    /// Prepends a path segment to the path of the error,
+    /// the path is built up while the error bubbles up.
+    pub(crate) fn at(mut self, segment: PathSegment) -> Self {
+        match &mut self {
+            Self::TypeMismatch { path, .. }
+            | Self::OutOfRange { path, .. }
+            | Self::UnknownField { path, .. }
+            | Self::MissingField { path, .. }
+            | Self::UnknownCase { path, .. }
+            | Self::TypeNotResolved { path, .. } => path.0.insert(0, segment),
+            _ => {}
+        }
+
+        self
+    }
+
+    fn path(&self) -> Option<&ValuePath> {
+        match self {
+            Self::TypeMismatch { path, .. }
+            | Self::OutOfRange { path, .. }
+            | Self::UnknownField { path, .. }
+            | Self::MissingField { path, .. }
+            | Self::UnknownCase { path, .. }
+            | Self::TypeNotResolved { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+}
+
 impl ser::Error for SerializeError {
     fn custom<T: Display>(msg: T) -> Self {
         Self::Message(msg.to_string())
@@ -123,6 +264,10 @@ impl ser::Error for SerializeError {
 
 impl Display for SerializeError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(path) = self.path().filter(|path| !path.is_empty()) {
+            write!(formatter, "{}: ", path)?;
+        }
+
         match self {
             Self::TransformationNotFinished => write!(
                 formatter,
@@ -136,6 +281,54 @@ impl Display for SerializeError {
                 formatter,
                 "a record must contain at least one field, zero given"
             ),
+            Self::TypeMismatch {
+                ref expected_type,
+                received,
+                ..
+            } => write!(
+                formatter,
+                "expected a value of type '{}', but {} was found",
+                expected_type.to_string(),
+                received,
+            ),
+            Self::OutOfRange {
+                ref expected_type,
+                ref value,
+                ..
+            } => write!(
+                formatter,
+                "{} is out of range of type '{}'",
+                value,
+                expected_type.to_string(),
+            ),
+            Self::UnknownField {
+                ref record_name,
+                ref field_name,
+                ..
+            } => write!(
+                formatter,
+                "record with name '{}' doesn't have field '{}'",
+                record_name, field_name,
+            ),
+            Self::MissingField {
+                ref record_name,
+                ref field_name,
+                ..
+            } => write!(
+                formatter,
+                "field '{}' of record with name '{}' is missing",
+                field_name, record_name,
+            ),
+            Self::UnknownCase {
+                ref type_name,
+                ref case_name,
+                ..
+            } => write!(
+                formatter,
+                "type with name '{}' doesn't have case '{}'",
+                type_name, case_name,
+            ),
+            Self::TypeNotResolved { ref reason, .. } => write!(formatter, "{}", reason),
             Self::Message(ref msg) => write!(formatter, "{}", msg),
         }
     }
@@ -155,8 +348,10 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
-    fn serialize_bool(self, _value: bool) -> Result<Self::Ok, Self::Error> {
-        unimplemented!("`bool` is not supported by WIT for the moment.")
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        self.last().push(IValue::Boolean(value));
+
+        Ok(())
     }
 
     fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
@@ -207,6 +402,18 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         Ok(())
     }
 
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok, Self::Error> {
+        self.last().push(value.into());
+
+        Ok(())
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok, Self::Error> {
+        self.last().push(value.into());
+
+        Ok(())
+    }
+
     fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
         self.last().push(value.into());
 
@@ -219,8 +426,10 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         Ok(())
     }
 
-    fn serialize_char(self, _value: char) -> Result<Self::Ok, Self::Error> {
-        todo!("`char` is not supported by WIT for the moment.")
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        self.last().push(value.into());
+
+        Ok(())
     }
 
     fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
@@ -229,36 +438,49 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         Ok(())
     }
 
-    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        todo!("`bytes` is not supported by WIT for the moment.")
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.last().push(IValue::ByteArray(value.to_vec()));
+
+        Ok(())
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        self.serialize_unit()
+        self.last().push(IValue::Optional(None));
+
+        Ok(())
     }
 
-    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        todo!("`some` is not supported by WIT for the moment.")
+        let value = self.serialize_single(value)?;
+        self.last().push(IValue::Optional(Some(Box::new(value))));
+
+        Ok(())
     }
 
+    // WIT doesn't have a unit type, a value without data is an absent optional value,
+    // e.g. `Result<(), String>` is mapped onto `result<option<_>, string>`.
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        todo!("`unit` is not supported by WIT for the moment.")
+        self.last().push(IValue::Optional(None));
+
+        Ok(())
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        todo!("`unit_struct` is not supported by WIT for the moment.")
+        self.serialize_unit()
     }
 
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        todo!("`unit_variant` is not supported by WIT for the moment.")
+        self.last().push(IValue::Variant(variant_index, None));
+
+        Ok(())
     }
 
     fn serialize_newtype_struct<T>(
@@ -274,29 +496,38 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        todo!("`newtype_variant` is not supported by WIT for the moment.")
+        let value = Box::new(self.serialize_single(value)?);
+
+        // `Result` is serialized as an enum with the `Ok` and `Err` newtype variants,
+        // it's mapped onto the dedicated result value.
+        let value = match (name, variant) {
+            ("Result", "Ok") => IValue::Result(Ok(value)),
+            ("Result", "Err") => IValue::Result(Err(value)),
+            _ => IValue::Variant(variant_index, Some(value)),
+        };
+        self.last().push(value);
+
+        Ok(())
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        if let Some(len) = len {
-            self.push_with_capacity(len);
+        self.push_with_capacity(len.unwrap_or_default());
 
-            Ok(self)
-        } else {
-            todo!("`seq` without known size are not supported for the moment")
-        }
+        Ok(self)
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        todo!("`tuple` is not supported by WIT for the moment.")
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.push_with_capacity(len);
+
+        Ok(self)
     }
 
     fn serialize_tuple_struct(
@@ -312,21 +543,21 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     fn serialize_tuple_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         _variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        todo!("`tuple_variant` is not supported by WIT for the moment.")
+        self.variant_indices.push(variant_index);
+        self.push_with_capacity(len);
+
+        Ok(self)
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        if let Some(len) = len {
-            self.push_with_capacity(len);
+        // keys and values are interleaved
+        self.push_with_capacity(len.unwrap_or_default() * 2);
 
-            Ok(self)
-        } else {
-            todo!("`map` without known size are not supported for the moment")
-        }
+        Ok(self)
     }
 
     fn serialize_struct(
@@ -342,11 +573,14 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     fn serialize_struct_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         _variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        todo!("`struct_variant` is not supported by WIT for the moment.")
+        self.variant_indices.push(variant_index);
+        self.push_with_capacity(len);
+
+        Ok(self)
     }
 }
 
@@ -373,15 +607,20 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_element<T>(&mut self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        unimplemented!()
+        value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        let record = IValue::Record(
+            NEVec::new(self.pop()?).map_err(|_| Self::Error::RecordNeedsAtLeastOneField)?,
+        );
+        self.last().push(record);
+
+        Ok(())
     }
 }
 
@@ -410,15 +649,15 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T>(&mut self, _value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_field<T>(&mut self, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        unimplemented!()
+        value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.end_variant()
     }
 }
 
@@ -426,10 +665,13 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
     where
         T: Serialize,
     {
+        let key = self.serialize_single(key)?;
+        self.last().push(key);
+
         Ok(())
     }
 
@@ -437,14 +679,27 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
     where
         T: Serialize,
     {
-        value.serialize(&mut **self)
+        let value = self.serialize_single(value)?;
+        self.last().push(value);
+
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let record = IValue::Record(
-            NEVec::new(self.pop()?).map_err(|_| Self::Error::RecordNeedsAtLeastOneField)?,
-        );
-        self.last().push(record);
+        let values = self.pop()?;
+
+        // keys and values are interleaved
+        if values.len() % 2 != 0 {
+            return Err(SerializeError::InternalValuesCorrupted);
+        }
+
+        let mut entries = Vec::with_capacity(values.len() / 2);
+        let mut values = values.into_iter();
+        while let (Some(key), Some(value)) = (values.next(), values.next()) {
+            entries.push((key, value));
+        }
+
+        self.last().push(IValue::Map(entries));
 
         Ok(())
     }
@@ -475,19 +730,15 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
     type Ok = ();
     type Error = SerializeError;
 
-    fn serialize_field<T>(
-        &mut self,
-        _key: &'static str,
-        _value: &T,
-    ) -> Result<Self::Ok, Self::Error>
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        unimplemented!()
+        value.serialize(&mut **self)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        unimplemented!()
+        self.end_variant()
     }
 }
 
@@ -508,6 +759,7 @@ mod tests {
         };
     }
 
+    serialize_value!(test_serialize_value__bool, bool, Boolean, true);
     serialize_value!(test_serialize_value__s8, i8, S8, 42);
     serialize_value!(test_serialize_value__s16, i16, S16, 42);
     serialize_value!(test_serialize_value__i32, i32, I32, 42);
@@ -518,6 +770,9 @@ mod tests {
     serialize_value!(test_serialize_value__u64, u64, U64, 42);
     serialize_value!(test_serialize_value__f32, f32, F32, 42.);
     serialize_value!(test_serialize_value__f64, f32, F32, 42.);
+    serialize_value!(test_serialize_value__u128, u128, U128, u128::MAX);
+    serialize_value!(test_serialize_value__s128, i128, S128, i128::MIN);
+    serialize_value!(test_serialize_value__char, char, Char, 'ы');
     serialize_value!(
         test_serialize_value__string,
         String,
@@ -544,7 +799,7 @@ mod tests {
         struct S(i8, f32);
 
         let input = S(7, 42.);
-        let output = IValue::Record(vec1![IValue::S8(7), IValue::F32(42.)]);
+        let output = IValue::Record(ne_vec![IValue::S8(7), IValue::F32(42.)]);
 
         assert_eq!(to_interface_value(&input).unwrap(), output);
     }
@@ -559,7 +814,7 @@ mod tests {
         }
 
         let input = S { x: 7, y: 42. };
-        let output = IValue::Record(vec1![IValue::S8(7), IValue::F32(42.)]);
+        let output = IValue::Record(ne_vec![IValue::S8(7), IValue::F32(42.)]);
 
         assert_eq!(to_interface_value(&input).unwrap(), output);
     }
@@ -584,11 +839,196 @@ mod tests {
             p1: Point { x: 1, y: 2, z: 3 },
             p2: Point { x: 4, y: 5, z: 6 },
         };
-        let output = IValue::Record(vec1![
-            IValue::Record(vec1![IValue::I32(1), IValue::I32(2), IValue::I32(3),]),
-            IValue::Record(vec1![IValue::I32(4), IValue::I32(5), IValue::I32(6),]),
+        let output = IValue::Record(ne_vec![
+            IValue::Record(ne_vec![IValue::I32(1), IValue::I32(2), IValue::I32(3),]),
+            IValue::Record(ne_vec![IValue::I32(4), IValue::I32(5), IValue::I32(6),]),
+        ]);
+
+        assert_eq!(to_interface_value(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__option() {
+        #[derive(Serialize)]
+        struct S {
+            x: Option<i8>,
+            y: Option<String>,
+        }
+
+        let input = S {
+            x: Some(7),
+            y: None,
+        };
+        let output = IValue::Record(ne_vec![
+            IValue::Optional(Some(Box::new(IValue::S8(7)))),
+            IValue::Optional(None),
         ]);
 
         assert_eq!(to_interface_value(&input).unwrap(), output);
     }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__enum() {
+        #[derive(Serialize)]
+        enum E {
+            A,
+            B(i8),
+            C(i8, f32),
+            D { x: i32, y: String },
+        }
+
+        let input = vec![
+            E::A,
+            E::B(7),
+            E::C(7, 42.),
+            E::D {
+                x: 1,
+                y: "foo".to_string(),
+            },
+        ];
+        let output = vec![
+            IValue::Variant(0, None),
+            IValue::Variant(1, Some(Box::new(IValue::S8(7)))),
+            IValue::Variant(
+                2,
+                Some(Box::new(IValue::Record(ne_vec![
+                    IValue::S8(7),
+                    IValue::F32(42.)
+                ]))),
+            ),
+            IValue::Variant(
+                3,
+                Some(Box::new(IValue::Record(ne_vec![
+                    IValue::I32(1),
+                    IValue::String("foo".to_string())
+                ]))),
+            ),
+        ];
+
+        for (input, output) in input.iter().zip(output.into_iter()) {
+            assert_eq!(to_interface_value(input).unwrap(), output);
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__result() {
+        let input: Vec<Result<i32, String>> = vec![Ok(7), Err("foo".to_string())];
+        let output = vec![
+            IValue::Result(Ok(Box::new(IValue::I32(7)))),
+            IValue::Result(Err(Box::new(IValue::String("foo".to_string())))),
+        ];
+
+        for (input, output) in input.iter().zip(output.into_iter()) {
+            assert_eq!(to_interface_value(input).unwrap(), output);
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__tuple() {
+        let input = (7i8, (42f32, "foo".to_string()));
+        let output = IValue::Record(ne_vec![
+            IValue::S8(7),
+            IValue::Record(ne_vec![IValue::F32(42.), IValue::String("foo".to_string())]),
+        ]);
+
+        assert_eq!(to_interface_value(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__map() {
+        let mut input = std::collections::BTreeMap::new();
+        input.insert("a".to_string(), 1u32);
+        input.insert("b".to_string(), 2u32);
+
+        let output = IValue::Map(vec![
+            (IValue::String("a".to_string()), IValue::U32(1)),
+            (IValue::String("b".to_string()), IValue::U32(2)),
+        ]);
+
+        assert_eq!(to_interface_value(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__bytes() {
+        struct Bytes(Vec<u8>);
+
+        impl Serialize for Bytes {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        let input = Bytes(vec![1, 2, 3]);
+        let output = IValue::ByteArray(vec![1, 2, 3]);
+
+        assert_eq!(to_interface_value(&input).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__unit() {
+        #[derive(Serialize)]
+        struct U;
+
+        let input: Vec<Result<(), String>> = vec![Ok(())];
+        let output = IValue::Array(vec![IValue::Result(Ok(Box::new(IValue::Optional(None))))]);
+
+        assert_eq!(to_interface_value(&input).unwrap(), output);
+        assert_eq!(to_interface_value(&U).unwrap(), IValue::Optional(None));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__unsized_seq_and_map() {
+        struct Unsized;
+
+        impl Serialize for Unsized {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ser::SerializeMap;
+
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_key("a")?;
+                map.serialize_value(&UnsizedSeq)?;
+                map.end()
+            }
+        }
+
+        struct UnsizedSeq;
+
+        impl Serialize for UnsizedSeq {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ser::SerializeSeq;
+
+                let mut seq = serializer.serialize_seq(None)?;
+                seq.serialize_element(&true)?;
+                seq.serialize_element(&false)?;
+                seq.end()
+            }
+        }
+
+        let output = IValue::Map(vec![(
+            IValue::String("a".to_string()),
+            IValue::Array(vec![IValue::Boolean(true), IValue::Boolean(false)]),
+        )]);
+
+        assert_eq!(to_interface_value(&Unsized).unwrap(), output);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialize_value__empty_struct() {
+        #[derive(Serialize)]
+        struct S {}
+
+        assert_eq!(
+            to_interface_value(&S {}),
+            Err(SerializeError::RecordNeedsAtLeastOneField)
+        );
+    }
 }
@@ -0,0 +1,1066 @@
+//! Provides a serializer from Rust value to WIT values that follows
+//! the interface type the callee expects.
+
+use super::ser::SerializeError;
+use crate::IRecordType;
+use crate::IType;
+use crate::IValue;
+use crate::NEVec;
+
+use it_lilo::traits::RecordResolvable;
+use it_lilo::type_checker::PathSegment;
+use serde::{ser, Serialize};
+
+use std::convert::TryFrom;
+
+/// Serialize a type `T` that implements the `Serialize` trait to an
+/// `IValue` of type `ty`.
+///
+/// Unlike [`to_interface_value`](crate::to_interface_value), the produced
+/// value has exactly the expected type:
+///
+///   * integers are converted to the expected integer type if they fit into it,
+///   * `Vec<u8>` and other sequences of bytes become byte arrays if expected,
+///   * struct fields are matched with record fields by names, absent fields
+///     of optional types become `None`,
+///   * enum variants are matched with variant cases and enum members by names,
+///     so `#[serde(rename_all = "...")]` may be needed.
+///
+/// # Example
+///
+/// ```rust
+/// use wasmer_interface_types_fl::{
+///     ne_vec, to_typed_interface_value, IRecordFieldType, IRecordType, IType, IValue,
+/// };
+/// use it_lilo::traits::{RecordResolvable, RecordResolvableError};
+/// use serde::Serialize;
+///
+/// struct Resolver(IRecordType);
+///
+/// impl RecordResolvable for Resolver {
+///     fn resolve_record(&self, _: u64) -> Result<&IRecordType, RecordResolvableError> {
+///         Ok(&self.0)
+///     }
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Point {
+///     y: i32,
+///     x: i32,
+/// }
+///
+/// let resolver = Resolver(IRecordType {
+///     name: "Point".to_string(),
+///     fields: ne_vec![
+///         IRecordFieldType { name: Some("x".to_string()), ty: IType::S32 },
+///         IRecordFieldType { name: Some("y".to_string()), ty: IType::U8 },
+///     ],
+/// });
+///
+/// assert_eq!(
+///     to_typed_interface_value(&Point { y: 2, x: 1 }, &IType::Record(0), &resolver).unwrap(),
+///     IValue::Record(ne_vec![IValue::S32(1), IValue::U8(2)]),
+/// );
+/// ```
+pub fn to_typed_interface_value<T, R>(
+    value: &T,
+    ty: &IType,
+    resolver: &R,
+) -> Result<IValue, SerializeError>
+where
+    T: ?Sized + Serialize,
+    R: RecordResolvable,
+{
+    value.serialize(TypedSerializer { resolver, ty })
+}
+
+static U8: IType = IType::U8;
+
+/// The serializer of a single value of type `ty`.
+struct TypedSerializer<'r, R> {
+    resolver: &'r R,
+    ty: &'r IType,
+}
+
+impl<'r, R> Clone for TypedSerializer<'r, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, R> Copy for TypedSerializer<'r, R> {}
+
+impl<'r, R: RecordResolvable> TypedSerializer<'r, R> {
+    fn with_type(self, ty: &'r IType) -> Self {
+        Self {
+            resolver: self.resolver,
+            ty,
+        }
+    }
+
+    /// Returns the expected type with aliases resolved.
+    fn ty(&self) -> Result<&'r IType, SerializeError> {
+        it_lilo::utils::resolve_alias(self.resolver, self.ty).map_err(resolve_error)
+    }
+
+    fn resolve<T: ?Sized>(
+        &self,
+        resolve: impl FnOnce(&'r R) -> Result<&'r T, it_lilo::traits::RecordResolvableError>,
+    ) -> Result<&'r T, SerializeError> {
+        resolve(self.resolver).map_err(resolve_error)
+    }
+
+    fn mismatch(&self, received: &'static str) -> SerializeError {
+        SerializeError::type_mismatch(self.ty, received)
+    }
+
+    /// Converts an integer to any of integer types if it fits into it.
+    fn integer(self, value: i128, received: &'static str) -> Result<IValue, SerializeError> {
+        let ty = self.ty()?;
+        let out_of_range = |_| SerializeError::OutOfRange {
+            path: Default::default(),
+            expected_type: ty.clone(),
+            value: value.to_string(),
+        };
+
+        let value = match ty {
+            IType::S8 => IValue::S8(i8::try_from(value).map_err(out_of_range)?),
+            IType::S16 => IValue::S16(i16::try_from(value).map_err(out_of_range)?),
+            IType::S32 => IValue::S32(i32::try_from(value).map_err(out_of_range)?),
+            IType::S64 => IValue::S64(i64::try_from(value).map_err(out_of_range)?),
+            IType::S128 => IValue::S128(value),
+            IType::U8 => IValue::U8(u8::try_from(value).map_err(out_of_range)?),
+            IType::U16 => IValue::U16(u16::try_from(value).map_err(out_of_range)?),
+            IType::U32 => IValue::U32(u32::try_from(value).map_err(out_of_range)?),
+            IType::U64 => IValue::U64(u64::try_from(value).map_err(out_of_range)?),
+            IType::U128 => IValue::U128(u128::try_from(value).map_err(out_of_range)?),
+            IType::I32 => IValue::I32(i32::try_from(value).map_err(out_of_range)?),
+            IType::I64 => IValue::I64(i64::try_from(value).map_err(out_of_range)?),
+            IType::Handle(_) => IValue::Handle(u32::try_from(value).map_err(out_of_range)?),
+            _ => return Err(self.mismatch(received)),
+        };
+
+        Ok(value)
+    }
+
+    /// Converts a float to `f32` only if it's representable as `f32` exactly.
+    fn float(self, value: f64, received: &'static str) -> Result<IValue, SerializeError> {
+        match self.ty()? {
+            IType::F64 => Ok(IValue::F64(value)),
+            IType::F32 if value.is_nan() || value as f32 as f64 == value => {
+                Ok(IValue::F32(value as f32))
+            }
+            ty @ IType::F32 => Err(SerializeError::OutOfRange {
+                path: Default::default(),
+                expected_type: ty.clone(),
+                value: value.to_string(),
+            }),
+            _ => Err(self.mismatch(received)),
+        }
+    }
+
+    /// Finds the case of the expected variant or enum type by its name.
+    fn case(&self, case_name: &'static str) -> Result<Case<'r>, SerializeError> {
+        let unknown_case = |type_name: &str| SerializeError::UnknownCase {
+            path: Default::default(),
+            type_name: type_name.to_string(),
+            case_name: case_name.to_string(),
+        };
+
+        match self.ty()? {
+            IType::Variant(variant_type_id) => {
+                let variant_type = self.resolve(|r| r.resolve_variant(*variant_type_id))?;
+
+                variant_type
+                    .cases
+                    .iter()
+                    .position(|case| case.name == case_name)
+                    .map(|position| {
+                        Case::Variant(position as u32, variant_type.cases[position].ty.as_ref())
+                    })
+                    .ok_or_else(|| unknown_case(&variant_type.name))
+            }
+            IType::Enum(enum_type_id) => {
+                let enum_type = self.resolve(|r| r.resolve_enum(*enum_type_id))?;
+
+                enum_type
+                    .members
+                    .iter()
+                    .position(|member| member == case_name)
+                    .map(|position| Case::Enum(position as u32))
+                    .ok_or_else(|| unknown_case(&enum_type.name))
+            }
+            _ => Err(self.mismatch("enum")),
+        }
+    }
+
+    /// Starts serializing fields of a struct, a tuple or a variant payload
+    /// into a record of the expected type.
+    fn record(
+        self,
+        variant: Option<u32>,
+        received: &'static str,
+    ) -> Result<TypedRecord<'r, R>, SerializeError> {
+        let record_type = match self.ty()? {
+            IType::Record(record_type_id) => self.resolve(|r| r.resolve_record(*record_type_id))?,
+            _ => return Err(self.mismatch(received)),
+        };
+
+        Ok(TypedRecord {
+            serializer: self,
+            record_type,
+            fields: vec![None; record_type.fields.len()],
+            position: 0,
+            variant,
+        })
+    }
+
+    /// Starts serializing a variant payload that is a record.
+    fn variant_record(
+        self,
+        variant: &'static str,
+        received: &'static str,
+    ) -> Result<TypedRecord<'r, R>, SerializeError> {
+        let segment = PathSegment::Field(variant.to_string());
+
+        match self.case(variant)? {
+            Case::Variant(discriminant, Some(payload_ty)) => self
+                .with_type(payload_ty)
+                .record(Some(discriminant), received)
+                .map_err(|e| e.at(segment)),
+            _ => Err(self.mismatch(received)),
+        }
+    }
+}
+
+fn resolve_error(error: it_lilo::traits::RecordResolvableError) -> SerializeError {
+    SerializeError::TypeNotResolved {
+        path: Default::default(),
+        reason: error.to_string(),
+    }
+}
+
+/// A case of a variant type with its payload type, or a member of an enum type.
+enum Case<'r> {
+    Variant(u32, Option<&'r IType>),
+    Enum(u32),
+}
+
+impl<'r, R: RecordResolvable> ser::Serializer for TypedSerializer<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    type SerializeSeq = TypedSeq<'r, R>;
+    type SerializeTuple = TypedRecord<'r, R>;
+    type SerializeTupleStruct = TypedRecord<'r, R>;
+    type SerializeTupleVariant = TypedRecord<'r, R>;
+    type SerializeMap = TypedMap<'r, R>;
+    type SerializeStruct = TypedRecord<'r, R>;
+    type SerializeStructVariant = TypedRecord<'r, R>;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::Boolean => Ok(IValue::Boolean(value)),
+            _ => Err(self.mismatch("bool")),
+        }
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "i8")
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "i16")
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "i32")
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "i64")
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok, Self::Error> {
+        self.integer(value, "i128")
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "u8")
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "u16")
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "u32")
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
+        self.integer(value.into(), "u64")
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok, Self::Error> {
+        // only `u128` can hold values above `i128::MAX`
+        match (self.ty()?, i128::try_from(value)) {
+            (IType::U128, _) => Ok(IValue::U128(value)),
+            (_, Ok(value)) => self.integer(value, "u128"),
+            (ty, Err(_)) => Err(SerializeError::OutOfRange {
+                path: Default::default(),
+                expected_type: ty.clone(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        self.float(value.into(), "f32")
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        self.float(value, "f64")
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::Char => Ok(IValue::Char(value)),
+            IType::String => Ok(IValue::String(value.to_string())),
+            _ => Err(self.mismatch("char")),
+        }
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::String => Ok(IValue::String(value.to_string())),
+            _ => Err(self.mismatch("string")),
+        }
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::ByteArray => Ok(IValue::ByteArray(value.to_vec())),
+            IType::Array(element_ty) if self.with_type(element_ty).ty()? == &IType::U8 => Ok(
+                IValue::Array(value.iter().copied().map(IValue::U8).collect()),
+            ),
+            _ => Err(self.mismatch("bytes")),
+        }
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::Optional(_) => Ok(IValue::Optional(None)),
+            _ => Err(self.mismatch("none")),
+        }
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        match self.ty()? {
+            IType::Optional(inner_ty) => {
+                let value = value.serialize(self.with_type(inner_ty))?;
+
+                Ok(IValue::Optional(Some(Box::new(value))))
+            }
+            _ => Err(self.mismatch("some")),
+        }
+    }
+
+    // WIT doesn't have a unit type, a value without data is an absent optional value.
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        match self.ty()? {
+            IType::Optional(_) => Ok(IValue::Optional(None)),
+            _ => Err(self.mismatch("unit")),
+        }
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        match self.case(variant)? {
+            Case::Variant(discriminant, None) => Ok(IValue::Variant(discriminant, None)),
+            Case::Enum(discriminant) => Ok(IValue::Enum(discriminant)),
+            Case::Variant(_, Some(_)) => Err(self.mismatch("unit variant")),
+        }
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        // `Result` is serialized as an enum with the `Ok` and `Err` newtype variants.
+        if let (IType::Result(ok_ty, err_ty), "Result") = (self.ty()?, name) {
+            return match variant {
+                "Ok" => value
+                    .serialize(self.with_type(ok_ty))
+                    .map(|value| IValue::Result(Ok(Box::new(value))))
+                    .map_err(|e| e.at(PathSegment::Field("ok".to_string()))),
+                _ => value
+                    .serialize(self.with_type(err_ty))
+                    .map(|value| IValue::Result(Err(Box::new(value))))
+                    .map_err(|e| e.at(PathSegment::Field("err".to_string()))),
+            };
+        }
+
+        match self.case(variant)? {
+            Case::Variant(discriminant, Some(payload_ty)) => value
+                .serialize(self.with_type(payload_ty))
+                .map(|value| IValue::Variant(discriminant, Some(Box::new(value))))
+                .map_err(|e| e.at(PathSegment::Field(variant.to_string()))),
+            _ => Err(self.mismatch("newtype variant")),
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let (element_ty, bytes) = match self.ty()? {
+            IType::Array(element_ty) => (element_ty.as_ref(), false),
+            IType::ByteArray => (&U8, true),
+            _ => return Err(self.mismatch("sequence")),
+        };
+
+        Ok(TypedSeq {
+            serializer: self.with_type(element_ty),
+            values: Vec::with_capacity(len.unwrap_or_default()),
+            bytes,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.record(None, "tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.record(None, "tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.variant_record(variant, "tuple variant")
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        let (key_ty, value_ty) = match self.ty()? {
+            IType::Map(key_ty, value_ty) => (key_ty.as_ref(), value_ty.as_ref()),
+            _ => return Err(self.mismatch("map")),
+        };
+
+        Ok(TypedMap {
+            key_serializer: self.with_type(key_ty),
+            value_serializer: self.with_type(value_ty),
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.record(None, "struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.variant_record(variant, "struct variant")
+    }
+}
+
+/// Elements of an array or bytes of a byte array.
+struct TypedSeq<'r, R> {
+    serializer: TypedSerializer<'r, R>,
+    values: Vec<IValue>,
+    bytes: bool,
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeSeq for TypedSeq<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value
+            .serialize(self.serializer)
+            .map_err(|e| e.at(PathSegment::Index(self.values.len())))?;
+        self.values.push(value);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        if !self.bytes {
+            return Ok(IValue::Array(self.values));
+        }
+
+        let bytes = self
+            .values
+            .into_iter()
+            .map(|value| match value {
+                IValue::U8(byte) => byte,
+                _ => unreachable!("elements of a byte array are serialized as `u8`"),
+            })
+            .collect();
+
+        Ok(IValue::ByteArray(bytes))
+    }
+}
+
+/// Fields of a record, they are matched by names if serialized as a struct,
+/// or by positions otherwise.
+struct TypedRecord<'r, R> {
+    serializer: TypedSerializer<'r, R>,
+    record_type: &'r IRecordType,
+    fields: Vec<Option<IValue>>,
+    position: usize,
+    /// The discriminant if the record is a variant payload.
+    variant: Option<u32>,
+}
+
+impl<'r, R: RecordResolvable> TypedRecord<'r, R> {
+    fn serialize_at<T>(&mut self, position: usize, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let field = &self.record_type.fields[position];
+        let segment = match &field.name {
+            Some(name) => PathSegment::Field(name.clone()),
+            None => PathSegment::Field(position.to_string()),
+        };
+
+        let value = value
+            .serialize(self.serializer.with_type(&field.ty))
+            .map_err(|e| e.at(segment))?;
+        self.fields[position] = Some(value);
+
+        Ok(())
+    }
+
+    fn serialize_next<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let position = self.position;
+        if position >= self.fields.len() {
+            return Err(SerializeError::UnknownField {
+                path: Default::default(),
+                record_name: self.record_type.name.clone(),
+                field_name: position.to_string(),
+            });
+        }
+
+        self.position += 1;
+        self.serialize_at(position, value)
+    }
+
+    fn serialize_named<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let by_name = self
+            .record_type
+            .fields
+            .iter()
+            .position(|field| field.name.as_deref() == Some(key));
+
+        // anonymous fields are matched by positions
+        let position = match by_name {
+            Some(position) => position,
+            None => match self.record_type.fields.get(self.position) {
+                Some(field) if field.name.is_none() => self.position,
+                _ => {
+                    return Err(SerializeError::UnknownField {
+                        path: Default::default(),
+                        record_name: self.record_type.name.clone(),
+                        field_name: key.to_string(),
+                    })
+                }
+            },
+        };
+
+        self.position = position + 1;
+        self.serialize_at(position, value)
+    }
+
+    fn end(self) -> Result<IValue, SerializeError> {
+        let mut fields = Vec::with_capacity(self.fields.len());
+
+        for (field, field_type) in self.fields.into_iter().zip(self.record_type.fields.iter()) {
+            let field = match field {
+                Some(field) => field,
+                None => match self.serializer.with_type(&field_type.ty).ty()? {
+                    IType::Optional(_) => IValue::Optional(None),
+                    _ => {
+                        return Err(SerializeError::MissingField {
+                            path: Default::default(),
+                            record_name: self.record_type.name.clone(),
+                            field_name: match &field_type.name {
+                                Some(name) => name.clone(),
+                                None => fields.len().to_string(),
+                            },
+                        })
+                    }
+                },
+            };
+
+            fields.push(field);
+        }
+
+        let record = IValue::Record(
+            NEVec::new(fields).map_err(|_| SerializeError::RecordNeedsAtLeastOneField)?,
+        );
+
+        Ok(match self.variant {
+            Some(discriminant) => IValue::Variant(discriminant, Some(Box::new(record))),
+            None => record,
+        })
+    }
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeTuple for TypedRecord<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        TypedRecord::end(self)
+    }
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeTupleStruct for TypedRecord<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        TypedRecord::end(self)
+    }
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeTupleVariant for TypedRecord<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_next(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        TypedRecord::end(self)
+    }
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeStruct for TypedRecord<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_named(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        TypedRecord::end(self)
+    }
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeStructVariant for TypedRecord<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_named(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        TypedRecord::end(self)
+    }
+}
+
+/// Entries of a map.
+struct TypedMap<'r, R> {
+    key_serializer: TypedSerializer<'r, R>,
+    value_serializer: TypedSerializer<'r, R>,
+    entries: Vec<(IValue, IValue)>,
+    key: Option<IValue>,
+}
+
+impl<'r, R: RecordResolvable> ser::SerializeMap for TypedMap<'r, R> {
+    type Ok = IValue;
+    type Error = SerializeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = key.serialize(self.key_serializer).map_err(|e| {
+            e.at(PathSegment::Field("key".to_string()))
+                .at(PathSegment::Index(self.entries.len()))
+        })?;
+        self.key = Some(key);
+
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or(SerializeError::InternalValuesCorrupted)?;
+        let value = value.serialize(self.value_serializer).map_err(|e| {
+            e.at(PathSegment::Field("value".to_string()))
+                .at(PathSegment::Index(self.entries.len()))
+        })?;
+        self.entries.push((key, value));
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(IValue::Map(self.entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ne_vec, IEnumType, IRecordFieldType, IVariantCaseType, IVariantType};
+
+    use it_lilo::traits::RecordResolvableError;
+    use std::collections::BTreeMap;
+
+    const POINT: u64 = 0;
+    const SHAPE: u64 = 1;
+    const PAIR: u64 = 2;
+    const CIRCLE: u64 = 3;
+    const KIND: u64 = 4;
+    const COLOR: u64 = 5;
+    const NAME: u64 = 6;
+
+    struct Resolver {
+        records: Vec<IRecordType>,
+        variant: IVariantType,
+        enum_type: IEnumType,
+        alias: IType,
+    }
+
+    impl Resolver {
+        fn new() -> Self {
+            let field = |name: &str, ty| IRecordFieldType {
+                name: Some(name.to_string()),
+                ty,
+            };
+
+            Self {
+                records: vec![
+                    IRecordType {
+                        name: "Point".to_string(),
+                        fields: ne_vec![field("x", IType::S32), field("y", IType::S32)],
+                    },
+                    IRecordType {
+                        name: "Shape".to_string(),
+                        fields: ne_vec![
+                            field("name", IType::Alias(NAME)),
+                            field("points", IType::Array(Box::new(IType::Record(POINT)))),
+                            field("data", IType::ByteArray),
+                            field("color", IType::Enum(COLOR)),
+                            field("kind", IType::Variant(KIND)),
+                            field("label", IType::Optional(Box::new(IType::String))),
+                        ],
+                    },
+                    IRecordType {
+                        name: "Pair".to_string(),
+                        fields: ne_vec![
+                            IRecordFieldType {
+                                name: None,
+                                ty: IType::U64,
+                            },
+                            IRecordFieldType {
+                                name: None,
+                                ty: IType::F32,
+                            },
+                        ],
+                    },
+                    IRecordType {
+                        name: "Circle".to_string(),
+                        fields: ne_vec![field("radius", IType::U16)],
+                    },
+                ],
+                variant: IVariantType {
+                    name: "Kind".to_string(),
+                    cases: ne_vec![
+                        IVariantCaseType {
+                            name: "polygon".to_string(),
+                            ty: None,
+                        },
+                        IVariantCaseType {
+                            name: "circle".to_string(),
+                            ty: Some(IType::Record(CIRCLE)),
+                        },
+                        IVariantCaseType {
+                            name: "custom".to_string(),
+                            ty: Some(IType::String),
+                        },
+                    ],
+                },
+                enum_type: IEnumType {
+                    name: "Color".to_string(),
+                    members: ne_vec!["red".to_string(), "green".to_string()],
+                },
+                alias: IType::String,
+            }
+        }
+    }
+
+    impl RecordResolvable for Resolver {
+        fn resolve_record(&self, id: u64) -> Result<&IRecordType, RecordResolvableError> {
+            self.records
+                .get(id as usize)
+                .ok_or(RecordResolvableError::RecordNotFound(id))
+        }
+
+        fn resolve_variant(&self, id: u64) -> Result<&IVariantType, RecordResolvableError> {
+            match id {
+                KIND => Ok(&self.variant),
+                _ => Err(RecordResolvableError::VariantNotFound(id)),
+            }
+        }
+
+        fn resolve_enum(&self, id: u64) -> Result<&IEnumType, RecordResolvableError> {
+            match id {
+                COLOR => Ok(&self.enum_type),
+                _ => Err(RecordResolvableError::EnumNotFound(id)),
+            }
+        }
+
+        fn resolve_alias(&self, id: u64) -> Result<&IType, RecordResolvableError> {
+            match id {
+                NAME => Ok(&self.alias),
+                _ => Err(RecordResolvableError::AliasNotFound(id)),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Point {
+        y: i64,
+        x: i64,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Color {
+        Green,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Kind {
+        Polygon,
+        Circle { radius: u8 },
+        Custom(String),
+    }
+
+    #[derive(Serialize)]
+    struct Shape {
+        name: String,
+        points: Vec<Point>,
+        data: Vec<u8>,
+        color: Color,
+        kind: Kind,
+    }
+
+    #[test]
+    fn test_record() {
+        let resolver = Resolver::new();
+        let shape = Shape {
+            name: "line".to_string(),
+            points: vec![Point { x: 1, y: 2 }, Point { x: -3, y: 4 }],
+            data: vec![1, 2, 3],
+            color: Color::Green,
+            kind: Kind::Polygon,
+        };
+
+        assert_eq!(
+            to_typed_interface_value(&shape, &IType::Record(SHAPE), &resolver).unwrap(),
+            IValue::Record(ne_vec![
+                IValue::String("line".to_string()),
+                IValue::Array(vec![
+                    IValue::Record(ne_vec![IValue::S32(1), IValue::S32(2)]),
+                    IValue::Record(ne_vec![IValue::S32(-3), IValue::S32(4)]),
+                ]),
+                IValue::ByteArray(vec![1, 2, 3]),
+                IValue::Enum(1),
+                IValue::Variant(0, None),
+                IValue::Optional(None),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_variant() {
+        let resolver = Resolver::new();
+        let ty = IType::Variant(KIND);
+
+        assert_eq!(
+            to_typed_interface_value(&Kind::Circle { radius: 7 }, &ty, &resolver).unwrap(),
+            IValue::Variant(1, Some(Box::new(IValue::Record(ne_vec![IValue::U16(7)]))))
+        );
+        assert_eq!(
+            to_typed_interface_value(&Kind::Custom("star".to_string()), &ty, &resolver).unwrap(),
+            IValue::Variant(2, Some(Box::new(IValue::String("star".to_string()))))
+        );
+
+        let ty = IType::Result(Box::new(IType::U8), Box::new(IType::String));
+        let value: Result<i32, String> = Ok(42);
+        assert_eq!(
+            to_typed_interface_value(&value, &ty, &resolver).unwrap(),
+            IValue::Result(Ok(Box::new(IValue::U8(42))))
+        );
+    }
+
+    #[test]
+    fn test_tuple_and_map() {
+        let resolver = Resolver::new();
+
+        assert_eq!(
+            to_typed_interface_value(&(7u8, 1.5f64), &IType::Record(PAIR), &resolver).unwrap(),
+            IValue::Record(ne_vec![IValue::U64(7), IValue::F32(1.5)])
+        );
+
+        let ty = IType::Map(Box::new(IType::String), Box::new(IType::S8));
+        let mut map = BTreeMap::new();
+        map.insert("a", 1i64);
+        map.insert("b", -1i64);
+
+        assert_eq!(
+            to_typed_interface_value(&map, &ty, &resolver).unwrap(),
+            IValue::Map(vec![
+                (IValue::String("a".to_string()), IValue::S8(1)),
+                (IValue::String("b".to_string()), IValue::S8(-1)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let resolver = Resolver::new();
+        let ty = IType::Array(Box::new(IType::Record(POINT)));
+
+        let points = vec![Point { x: 1, y: 2 }, Point { x: 1 << 40, y: 2 }];
+        let error = to_typed_interface_value(&points, &ty, &resolver).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "[1].x: 1099511627776 is out of range of type 's32'"
+        );
+
+        #[derive(Serialize)]
+        struct Point3 {
+            x: i32,
+            y: i32,
+            z: i32,
+        }
+
+        let error = to_typed_interface_value(
+            &Point3 { x: 1, y: 2, z: 3 },
+            &IType::Record(POINT),
+            &resolver,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "record with name 'Point' doesn't have field 'z'"
+        );
+
+        #[derive(Serialize)]
+        struct OnlyX {
+            x: i32,
+        }
+
+        let error = to_typed_interface_value(&OnlyX { x: 1 }, &IType::Record(POINT), &resolver)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "field 'y' of record with name 'Point' is missing"
+        );
+
+        let error = to_typed_interface_value(&"red", &IType::Enum(COLOR), &resolver).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a value of type 'enum 5', but string was found"
+        );
+
+        let error = to_typed_interface_value(&1.1f64, &IType::Record(PAIR), &resolver).unwrap_err();
+        assert!(matches!(
+            error,
+            SerializeError::TypeMismatch {
+                received: "f64",
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,439 @@
+//! A view of an interface value along with its type, it restores record
+//! field names and variant, flags and enum names lost in `IValue`.
+
+use crate::IType;
+use crate::IValue;
+
+use it_lilo::traits::RecordResolvable;
+use it_lilo::utils::resolve_alias;
+
+use std::fmt;
+use std::fmt::Write;
+
+/// An interface value viewed through its type.
+///
+/// `IValue::Record` is positional, so the view resolves the record type
+/// to access fields by their names and to print them, e.g. a lifted result
+/// is displayed as `User { name: "alice", addresses: [Address { zip: 1 }] }`
+/// instead of nested positional lists.
+pub struct TypedIValue<'a, R: RecordResolvable> {
+    value: &'a IValue,
+    ty: &'a IType,
+    resolver: &'a R,
+}
+
+impl<'a, R: RecordResolvable> TypedIValue<'a, R> {
+    /// Creates a view of `value` of type `ty`, ids in `ty` are resolved with `resolver`.
+    pub fn new(value: &'a IValue, ty: &'a IType, resolver: &'a R) -> Self {
+        Self {
+            value,
+            ty,
+            resolver,
+        }
+    }
+
+    /// Returns the viewed value.
+    pub fn value(&self) -> &'a IValue {
+        self.value
+    }
+
+    /// Returns the type of the viewed value.
+    pub fn ty(&self) -> &'a IType {
+        self.ty
+    }
+
+    /// Returns a record field by its name, anonymous fields are named by their position.
+    ///
+    /// Returns `None` if the value isn't a record or doesn't have such field.
+    pub fn get(&self, name: &str) -> Option<TypedIValue<'a, R>> {
+        let (record_type_id, fields) = match (self.resolved_ty()?, self.value) {
+            (IType::Record(record_type_id), IValue::Record(fields)) => (*record_type_id, fields),
+            _ => return None,
+        };
+
+        let record_type = self.resolver.resolve_record(record_type_id).ok()?;
+        let position = record_type
+            .fields
+            .iter()
+            .enumerate()
+            .position(|(position, field)| match &field.name {
+                Some(field_name) => field_name == name,
+                None => position.to_string() == name,
+            })?;
+
+        Some(self.inner(fields.get(position)?, &record_type.fields[position].ty))
+    }
+
+    /// Returns an array element by its index.
+    ///
+    /// Returns `None` if the value isn't an array or the index is out of bounds.
+    pub fn at(&self, index: usize) -> Option<TypedIValue<'a, R>> {
+        match (self.resolved_ty()?, self.value) {
+            (IType::Array(ty), IValue::Array(values)) => Some(self.inner(values.get(index)?, ty)),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of the viewed value.
+    pub fn to_ivalue(&self) -> IValue {
+        self.value.clone()
+    }
+
+    fn inner(&self, value: &'a IValue, ty: &'a IType) -> TypedIValue<'a, R> {
+        TypedIValue::new(value, ty, self.resolver)
+    }
+
+    fn resolved_ty(&self) -> Option<&'a IType> {
+        resolve_alias(self.resolver, self.ty).ok()
+    }
+
+    /// Writes the value, returns `Ok(false)` if it doesn't match the type.
+    fn write(&self, f: &mut String) -> Result<bool, fmt::Error> {
+        let ty = match self.resolved_ty() {
+            Some(ty) => ty,
+            None => return Ok(false),
+        };
+
+        match (ty, self.value) {
+            (IType::Boolean, IValue::Boolean(value)) => write!(f, "{}", value)?,
+            (IType::S8, IValue::S8(value)) => write!(f, "{}", value)?,
+            (IType::S16, IValue::S16(value)) => write!(f, "{}", value)?,
+            (IType::S32, IValue::S32(value)) => write!(f, "{}", value)?,
+            (IType::S64, IValue::S64(value)) => write!(f, "{}", value)?,
+            (IType::U8, IValue::U8(value)) => write!(f, "{}", value)?,
+            (IType::U16, IValue::U16(value)) => write!(f, "{}", value)?,
+            (IType::U32, IValue::U32(value)) => write!(f, "{}", value)?,
+            (IType::U64, IValue::U64(value)) => write!(f, "{}", value)?,
+            (IType::I32, IValue::I32(value)) => write!(f, "{}", value)?,
+            (IType::I64, IValue::I64(value)) => write!(f, "{}", value)?,
+            (IType::U128, IValue::U128(value)) => write!(f, "{}", value)?,
+            (IType::S128, IValue::S128(value)) => write!(f, "{}", value)?,
+            (IType::F32, IValue::F32(value)) => write!(f, "{:?}", value)?,
+            (IType::F64, IValue::F64(value)) => write!(f, "{:?}", value)?,
+            (IType::Char, IValue::Char(value)) => write!(f, "{:?}", value)?,
+            (IType::String, IValue::String(value)) => write!(f, "{:?}", value)?,
+            (IType::Handle(_), IValue::Handle(value)) => write!(f, "handle {}", value)?,
+            (IType::ByteArray, IValue::ByteArray(bytes)) => write!(f, "{:?}", bytes)?,
+            (IType::Array(ty), IValue::ByteArray(bytes)) if ty.as_ref() == &IType::U8 => {
+                write!(f, "{:?}", bytes)?
+            }
+            (IType::ByteArray, IValue::Array(values)) => {
+                return self.write_list(f, values.iter().map(|v| (v, &IType::U8)))
+            }
+            (IType::Array(ty), IValue::Array(values)) => {
+                return self.write_list(f, values.iter().map(|v| (v, ty.as_ref())))
+            }
+            (IType::Record(record_type_id), IValue::Record(fields)) => {
+                let record_type = match self.resolver.resolve_record(*record_type_id) {
+                    Ok(record_type) if record_type.fields.len() == fields.len() => record_type,
+                    _ => return Ok(false),
+                };
+
+                write!(f, "{} {{ ", record_type.name)?;
+                for (position, (field_type, field)) in
+                    record_type.fields.iter().zip(fields.iter()).enumerate()
+                {
+                    if position != 0 {
+                        write!(f, ", ")?;
+                    }
+                    match &field_type.name {
+                        Some(name) => write!(f, "{}: ", name)?,
+                        None => write!(f, "{}: ", position)?,
+                    }
+                    if !self.inner(field, &field_type.ty).write(f)? {
+                        return Ok(false);
+                    }
+                }
+                write!(f, " }}")?;
+            }
+            (IType::Optional(_), IValue::Optional(None)) => write!(f, "None")?,
+            (IType::Optional(ty), IValue::Optional(Some(value))) => {
+                return self.write_wrapped(f, "Some", value, ty)
+            }
+            (IType::Result(ty, _), IValue::Result(Ok(value))) => {
+                return self.write_wrapped(f, "Ok", value, ty)
+            }
+            (IType::Result(_, ty), IValue::Result(Err(value))) => {
+                return self.write_wrapped(f, "Err", value, ty)
+            }
+            (IType::Variant(variant_type_id), IValue::Variant(discriminant, payload)) => {
+                let variant_type = match self.resolver.resolve_variant(*variant_type_id) {
+                    Ok(variant_type) => variant_type,
+                    Err(_) => return Ok(false),
+                };
+                let case = match variant_type.cases.get(*discriminant as usize) {
+                    Some(case) => case,
+                    None => return Ok(false),
+                };
+
+                let name = format!("{}::{}", variant_type.name, case.name);
+                match (&case.ty, payload) {
+                    (None, None) => write!(f, "{}", name)?,
+                    (Some(ty), Some(value)) => return self.write_wrapped(f, &name, value, ty),
+                    _ => return Ok(false),
+                }
+            }
+            (IType::Map(key_ty, value_ty), IValue::Map(entries)) => {
+                write!(f, "{{")?;
+                for (position, (key, value)) in entries.iter().enumerate() {
+                    if position != 0 {
+                        write!(f, ", ")?;
+                    }
+                    if !self.inner(key, key_ty).write(f)? {
+                        return Ok(false);
+                    }
+                    write!(f, ": ")?;
+                    if !self.inner(value, value_ty).write(f)? {
+                        return Ok(false);
+                    }
+                }
+                write!(f, "}}")?;
+            }
+            (IType::Flags(flags_type_id), IValue::Flags(bits)) => {
+                let flags_type = match self.resolver.resolve_flags(*flags_type_id) {
+                    Ok(flags_type) => flags_type,
+                    Err(_) => return Ok(false),
+                };
+
+                let names = flags_type
+                    .flags
+                    .iter()
+                    .enumerate()
+                    .filter(|(flag, _)| {
+                        // flags beyond 64 don't fit in the bits, so they are never set
+                        1u64.checked_shl(*flag as u32)
+                            .map_or(false, |mask| bits & mask != 0)
+                    })
+                    .map(|(_, name)| name.as_str())
+                    .collect::<Vec<_>>();
+                write!(f, "{} {{ {} }}", flags_type.name, names.join(" | "))?;
+            }
+            (IType::Enum(enum_type_id), IValue::Enum(discriminant)) => {
+                let enum_type = match self.resolver.resolve_enum(*enum_type_id) {
+                    Ok(enum_type) => enum_type,
+                    Err(_) => return Ok(false),
+                };
+
+                match enum_type.members.get(*discriminant as usize) {
+                    Some(member) => write!(f, "{}::{}", enum_type.name, member)?,
+                    None => return Ok(false),
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn write_list(
+        &self,
+        f: &mut String,
+        values: impl Iterator<Item = (&'a IValue, &'a IType)>,
+    ) -> Result<bool, fmt::Error> {
+        write!(f, "[")?;
+        for (position, (value, ty)) in values.enumerate() {
+            if position != 0 {
+                write!(f, ", ")?;
+            }
+            if !self.inner(value, ty).write(f)? {
+                return Ok(false);
+            }
+        }
+        write!(f, "]")?;
+
+        Ok(true)
+    }
+
+    fn write_wrapped(
+        &self,
+        f: &mut String,
+        name: &str,
+        value: &'a IValue,
+        ty: &'a IType,
+    ) -> Result<bool, fmt::Error> {
+        write!(f, "{}(", name)?;
+        if !self.inner(value, ty).write(f)? {
+            return Ok(false);
+        }
+        write!(f, ")")?;
+
+        Ok(true)
+    }
+}
+
+impl<'a, R: RecordResolvable> Clone for TypedIValue<'a, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, R: RecordResolvable> Copy for TypedIValue<'a, R> {}
+
+/// Values that don't match their types are displayed as `{:?}` of the value.
+impl<'a, R: RecordResolvable> fmt::Display for TypedIValue<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = String::new();
+
+        if self.write(&mut buffer)? {
+            f.write_str(&buffer)
+        } else {
+            write!(f, "{:?}", self.value)
+        }
+    }
+}
+
+impl<'a, R: RecordResolvable> fmt::Debug for TypedIValue<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<'a, R: RecordResolvable> From<TypedIValue<'a, R>> for IValue {
+    fn from(typed_value: TypedIValue<'a, R>) -> Self {
+        typed_value.to_ivalue()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ne_vec, IEnumType, IFlagsType, IRecordFieldType, IRecordType, NEVec};
+
+    use it_lilo::traits::RecordResolvableError;
+
+    struct Resolver {
+        records: Vec<IRecordType>,
+        enum_type: IEnumType,
+        flags_type: IFlagsType,
+    }
+
+    impl Resolver {
+        fn new() -> Self {
+            let field = |name: &str, ty| IRecordFieldType {
+                name: Some(name.to_string()),
+                ty,
+            };
+
+            Self {
+                records: vec![
+                    IRecordType {
+                        name: "Account".to_string(),
+                        fields: ne_vec![
+                            field("owner", IType::String),
+                            field("balance", IType::U64),
+                            field("tags", IType::Array(Box::new(IType::Record(1)))),
+                        ],
+                    },
+                    IRecordType {
+                        name: "Tag".to_string(),
+                        fields: ne_vec![
+                            field("color", IType::Enum(0)),
+                            field("label", IType::Optional(Box::new(IType::String))),
+                        ],
+                    },
+                ],
+                enum_type: IEnumType {
+                    name: "Color".to_string(),
+                    members: ne_vec!["red".to_string(), "green".to_string()],
+                },
+                // more flags than fit in the bits, the type can still be built by hand
+                flags_type: IFlagsType {
+                    name: "Wide".to_string(),
+                    flags: NEVec::new((0..70).map(|flag| format!("f{}", flag)).collect()).unwrap(),
+                },
+            }
+        }
+    }
+
+    impl RecordResolvable for Resolver {
+        fn resolve_record(&self, id: u64) -> Result<&IRecordType, RecordResolvableError> {
+            self.records
+                .get(id as usize)
+                .ok_or(RecordResolvableError::RecordNotFound(id))
+        }
+
+        fn resolve_enum(&self, id: u64) -> Result<&IEnumType, RecordResolvableError> {
+            match id {
+                0 => Ok(&self.enum_type),
+                _ => Err(RecordResolvableError::EnumNotFound(id)),
+            }
+        }
+
+        fn resolve_flags(&self, id: u64) -> Result<&IFlagsType, RecordResolvableError> {
+            match id {
+                0 => Ok(&self.flags_type),
+                _ => Err(RecordResolvableError::FlagsNotFound(id)),
+            }
+        }
+    }
+
+    fn account() -> IValue {
+        IValue::Record(ne_vec![
+            IValue::String("alice".to_string()),
+            IValue::U64(100),
+            IValue::Array(vec![IValue::Record(ne_vec![
+                IValue::Enum(1),
+                IValue::Optional(Some(Box::new(IValue::String("vip".to_string())))),
+            ])]),
+        ])
+    }
+
+    #[test]
+    fn test_display() {
+        let resolver = Resolver::new();
+        let value = account();
+        let ty = IType::Record(0);
+
+        assert_eq!(
+            TypedIValue::new(&value, &ty, &resolver).to_string(),
+            r#"Account { owner: "alice", balance: 100, tags: [Tag { color: Color::green, label: Some("vip") }] }"#
+        );
+    }
+
+    #[test]
+    fn test_display_mismatch() {
+        let resolver = Resolver::new();
+        let value = IValue::Record(ne_vec![IValue::U8(1)]);
+        let ty = IType::Record(0);
+
+        assert_eq!(
+            TypedIValue::new(&value, &ty, &resolver).to_string(),
+            format!("{:?}", value)
+        );
+    }
+
+    #[test]
+    fn test_display_wide_flags() {
+        let resolver = Resolver::new();
+        let value = IValue::Flags(1 | 1 << 63);
+        let ty = IType::Flags(0);
+
+        assert_eq!(
+            TypedIValue::new(&value, &ty, &resolver).to_string(),
+            "Wide { f0 | f63 }"
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let resolver = Resolver::new();
+        let value = account();
+        let ty = IType::Record(0);
+        let typed_value = TypedIValue::new(&value, &ty, &resolver);
+
+        let owner = typed_value.get("owner").unwrap();
+        assert_eq!(owner.ty(), &IType::String);
+        assert_eq!(IValue::from(owner), IValue::String("alice".to_string()));
+
+        let color = typed_value
+            .get("tags")
+            .and_then(|tags| tags.at(0))
+            .and_then(|tag| tag.get("color"))
+            .unwrap();
+        assert_eq!(color.to_string(), "Color::green");
+
+        assert!(typed_value.get("missing").is_none());
+        assert!(owner.get("owner").is_none());
+        assert_eq!(typed_value.to_ivalue(), value);
+    }
+}